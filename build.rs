@@ -0,0 +1,15 @@
+fn main() {
+	#[cfg(feature = "capi")]
+	generate_c_header();
+}
+
+#[cfg(feature = "capi")]
+fn generate_c_header() {
+	let crate_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap();
+	println!("cargo:rerun-if-changed=src/capi.rs");
+	println!("cargo:rerun-if-changed=cbindgen.toml");
+
+	cbindgen::generate(&crate_dir)
+		.expect("Unable to generate the C header")
+		.write_to_file(std::path::Path::new(&crate_dir).join("include/formate_js.h"));
+}
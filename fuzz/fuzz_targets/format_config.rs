@@ -0,0 +1,29 @@
+//! Formats random text with a random combination of options.
+#![no_main]
+
+use arbitrary::{Arbitrary, Unstructured};
+use formate_js::{ConfigurationOptions, Formatter};
+use formate_js_fuzz::{Extension, arbitrary_options, check};
+use libfuzzer_sys::fuzz_target;
+
+#[derive(Debug)]
+struct Input {
+	options: ConfigurationOptions,
+	extension: Extension,
+	text: String,
+}
+
+impl<'a> Arbitrary<'a> for Input {
+	fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+		Ok(Input {
+			options: arbitrary_options(u)?,
+			extension: u.arbitrary()?,
+			text: u.arbitrary()?,
+		})
+	}
+}
+
+fuzz_target!(|input: Input| {
+	let formatter = Formatter::new(&input.options).expect("the options are valid");
+	check(&formatter, &input.extension.path(), input.text);
+});
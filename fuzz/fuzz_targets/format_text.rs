@@ -0,0 +1,21 @@
+//! Formats random text with the default options.
+#![no_main]
+
+use arbitrary::Arbitrary;
+use formate_js::Formatter;
+use formate_js_fuzz::{Extension, check, default_options};
+use libfuzzer_sys::fuzz_target;
+use std::sync::LazyLock;
+
+static FORMATTER: LazyLock<Formatter> =
+	LazyLock::new(|| Formatter::new(&default_options()).unwrap());
+
+#[derive(Arbitrary, Debug)]
+struct Input {
+	extension: Extension,
+	text: String,
+}
+
+fuzz_target!(|input: Input| {
+	check(&FORMATTER, &input.extension.path(), input.text);
+});
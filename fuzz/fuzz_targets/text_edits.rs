@@ -0,0 +1,61 @@
+//! Finds the edits between random texts, and between random text and its formatted text.
+#![no_main]
+
+use arbitrary::Arbitrary;
+use formate_js::Formatter;
+use formate_js::edits::{TextEdit, text_edits};
+use formate_js::formatter::FormatPanicError;
+use formate_js::mapping::OffsetEncoding;
+use formate_js_fuzz::{Extension, default_options};
+use libfuzzer_sys::fuzz_target;
+use std::sync::LazyLock;
+use std::sync::atomic::AtomicBool;
+
+static FORMATTER: LazyLock<Formatter> =
+	LazyLock::new(|| Formatter::new(&default_options()).unwrap());
+
+#[derive(Arbitrary, Debug)]
+struct Input {
+	extension: Extension,
+	text: String,
+	other: String,
+}
+
+/// Applies the edits, checking that they are in order and do not overlap.
+fn apply(text: &str, edits: &[TextEdit]) -> String {
+	for pair in edits.windows(2) {
+		assert!(pair[0].end < pair[1].start, "The edits overlap: {:?}", pair);
+	}
+	let mut text = text.to_string();
+	for edit in edits.iter().rev() {
+		text.replace_range(edit.start..edit.end, &edit.new_text);
+	}
+	text
+}
+
+fuzz_target!(|input: Input| {
+	let edits = text_edits(&input.text, &input.other, OffsetEncoding::Utf8);
+	assert_eq!(apply(&input.text, &edits), input.other);
+
+	let path = input.extension.path();
+	let formatted = match FORMATTER.format(&path, None, input.text.clone()) {
+		Ok(result) => result.text,
+		Err(_) => return,
+	};
+	match FORMATTER.format_edits_cancellable(
+		&path,
+		None,
+		input.text.clone(),
+		OffsetEncoding::Utf8,
+		&AtomicBool::new(false),
+	) {
+		Ok(edits) => assert_eq!(apply(&input.text, &edits), formatted),
+		Err(error) => match error.downcast_ref::<FormatPanicError>() {
+			Some(error) => panic!("{}", error.crash_report()),
+			None => panic!(
+				"Finding the edits failed after formatting succeeded: {}",
+				error
+			),
+		},
+	}
+});
@@ -0,0 +1,210 @@
+//! Shared checks for the fuzz targets.
+
+use arbitrary::{Arbitrary, Unstructured};
+use formate_js::formatter::FormatPanicError;
+use formate_js::{ConfigurationOptions, Formatter};
+use serde_json::{Map, Value};
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+/// Syntax trees deeper than this are refused, so that fuzzing on the main thread cannot overflow its stack.
+pub const MAX_NESTING_DEPTH: usize = 500;
+
+/// The file extensions the formatter handles.
+#[derive(Arbitrary, Clone, Copy, Debug)]
+pub enum Extension {
+	Js,
+	Jsx,
+	Mjs,
+	Cjs,
+	Ts,
+	Tsx,
+	Mts,
+	Cts,
+	Dts,
+}
+
+impl Extension {
+	pub fn path(self) -> PathBuf {
+		let file_name = match self {
+			Extension::Js => "fuzz.js",
+			Extension::Jsx => "fuzz.jsx",
+			Extension::Mjs => "fuzz.mjs",
+			Extension::Cjs => "fuzz.cjs",
+			Extension::Ts => "fuzz.ts",
+			Extension::Tsx => "fuzz.tsx",
+			Extension::Mts => "fuzz.mts",
+			Extension::Cts => "fuzz.cts",
+			Extension::Dts => "fuzz.d.ts",
+		};
+		PathBuf::from(file_name)
+	}
+}
+
+/// Formats the text, panicking if the formatter crashed or formatting its output changes it again.
+///
+/// Errors for text which does not parse are expected and ignored.
+pub fn check(formatter: &Formatter, path: &Path, text: String) {
+	let first = match formatter.format(path, None, text) {
+		Ok(result) if result.skipped.is_none() => result,
+		Ok(_) => return,
+		Err(error) => {
+			if let Some(error) = error.downcast_ref::<FormatPanicError>() {
+				panic!("{}", error.crash_report());
+			}
+			return;
+		}
+	};
+
+	match formatter.format(path, None, first.text.clone()) {
+		Ok(second) => assert!(
+			!second.changed,
+			"Formatting is not idempotent.\n--- first ---\n{}\n--- second ---\n{}",
+			first.text, second.text
+		),
+		Err(error) => panic!(
+			"The formatted text could not be formatted again: {}\n--- formatted ---\n{}",
+			error, first.text
+		),
+	}
+}
+
+/// The defaults, with nesting limited so the fuzzer cannot overflow the stack.
+pub fn default_options() -> ConfigurationOptions {
+	ConfigurationOptions {
+		max_nesting_depth: Some(MAX_NESTING_DEPTH),
+		..Default::default()
+	}
+}
+
+// Every value accepted by one of dprint's enum options.
+const ENUM_VALUES: &[&str] = &[
+	"always",
+	"alwaysDouble",
+	"alwaysSingle",
+	"asNeeded",
+	"asi",
+	"auto",
+	"blankLine",
+	"camelCase",
+	"caseInsensitive",
+	"caseSensitive",
+	"comma",
+	"consistent",
+	"crlf",
+	"first",
+	"force",
+	"last",
+	"lf",
+	"maintain",
+	"never",
+	"newLine",
+	"nextLine",
+	"none",
+	"onlyMultiLine",
+	"onlySingleItem",
+	"prefer",
+	"preferDouble",
+	"preferNone",
+	"preferSingle",
+	"preserve",
+	"sameLine",
+	"sameLineUnlessHanging",
+	"semiColon",
+	"system",
+	"whenMultiple",
+	"whenNotSingleLine",
+];
+
+const COMMENT_TEXTS: &[&str] = &[
+	"dprint-ignore",
+	"dprint-ignore-file",
+	"formate-ignore",
+	"formate-ignore-file",
+	"formate-ignore-start",
+	"formate-ignore-end",
+	"prettier-ignore",
+	"",
+];
+
+// Options which are not about the output, or which the fuzzer should not change.
+const FIXED_OPTIONS: &[&str] = &[
+	"max_file_size",
+	"max_average_line_length",
+	"max_nesting_depth",
+	"worker_stack_size",
+	"verify_idempotent",
+];
+
+/// The valid values of each enum option, found by trying every known value.
+static ENUM_OPTIONS: LazyLock<Vec<(String, Vec<&'static str>)>> = LazyLock::new(|| {
+	let defaults = options_map(&default_options());
+	defaults
+		.iter()
+		.filter(|(key, value)| value.is_string() && !key.starts_with("ignore_"))
+		.map(|(key, _)| {
+			let values = ENUM_VALUES
+				.iter()
+				.copied()
+				.filter(|value| {
+					let mut options = defaults.clone();
+					options.insert(key.clone(), Value::from(*value));
+					from_map(options).is_some_and(|options| options.resolve().is_ok())
+				})
+				.collect();
+			(key.clone(), values)
+		})
+		.collect()
+});
+
+fn options_map(options: &ConfigurationOptions) -> Map<String, Value> {
+	match serde_json::to_value(options).unwrap() {
+		Value::Object(map) => map,
+		_ => unreachable!(),
+	}
+}
+
+fn from_map(map: Map<String, Value>) -> Option<ConfigurationOptions> {
+	serde_json::from_value(Value::Object(map)).ok()
+}
+
+/// Picks a random combination of valid options.
+pub fn arbitrary_options(u: &mut Unstructured<'_>) -> arbitrary::Result<ConfigurationOptions> {
+	let mut map = options_map(&default_options());
+	let keys = map.keys().cloned().collect::<Vec<_>>();
+
+	for key in keys {
+		if FIXED_OPTIONS.contains(&key.as_str()) || !u.ratio(1, 4)? {
+			continue;
+		}
+
+		let value = match &map[&key] {
+			Value::Bool(_) => Value::Bool(u.arbitrary()?),
+			Value::Number(_) => Value::from(match key.as_str() {
+				"line_width" => u.int_in_range(0..=200)?,
+				"indent_width" => u.int_in_range(0..=16)?,
+				_ => u.int_in_range(0..=4)?,
+			}),
+			Value::Array(_) => Value::from(
+				(0..u.int_in_range(0..=2)?)
+					.map(|_| u.choose(COMMENT_TEXTS).copied())
+					.collect::<arbitrary::Result<Vec<_>>>()?,
+			),
+			Value::String(_) if key.starts_with("ignore_") => {
+				Value::from(*u.choose(COMMENT_TEXTS)?)
+			}
+			Value::String(_) => {
+				let values = &ENUM_OPTIONS
+					.iter()
+					.find(|(name, _)| *name == key)
+					.expect("every enum option is known")
+					.1;
+				Value::from(*u.choose(values)?)
+			}
+			_ => continue,
+		};
+		map.insert(key, value);
+	}
+
+	Ok(from_map(map).expect("the options are valid"))
+}
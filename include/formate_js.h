@@ -0,0 +1,79 @@
+#ifndef FORMATE_JS_H
+#define FORMATE_JS_H
+
+/* This file is generated by cbindgen when building with the `capi` feature. Do not edit it by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// The most jobs which timed out which may still be running in the background.
+//
+// Printing cannot be interrupted, so a job which timed out keeps its thread busy until it finishes.
+// Once this many are, new jobs are refused with [`TooManyTimedOutError`] rather than piling up.
+#define MAX_TIMED_OUT_JOBS 8
+
+// A formatter created from a JSON configuration.
+typedef struct FormateJsConfig FormateJsConfig;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Creates a formatter from a JSON object of configuration options.
+//
+// The options are the same as the keyword arguments of the formate hook. Pass `NULL` to use the defaults.
+// Returns `NULL` on error. The formatter must be freed with [`formate_js_config_free`].
+//
+// # Safety
+//
+// `json` must be `NULL` or a valid NUL-terminated string.
+struct FormateJsConfig *formate_js_config_new(const char *json);
+
+// Frees a formatter created by [`formate_js_config_new`]. Does nothing if `config` is `NULL`.
+//
+// # Safety
+//
+// `config` must be `NULL` or a pointer returned by [`formate_js_config_new`] which has not been freed.
+void formate_js_config_free(struct FormateJsConfig *config);
+
+// Formats `text_len` bytes of UTF-8 `text` as the file at `path`.
+//
+// Returns the formatted text as a NUL-terminated string, which must be freed with [`formate_js_string_free`],
+// or `NULL` on error. If `out_len` is not `NULL` it is set to the length of the formatted text, in bytes.
+//
+// # Safety
+//
+// `config` must be a valid formatter, `path` a valid NUL-terminated string,
+// `text` must point to at least `text_len` readable bytes, and `out_len` must be `NULL` or writable.
+char *formate_js_format(const struct FormateJsConfig *config,
+                        const char *path,
+                        const char *text,
+                        size_t text_len,
+                        size_t *out_len);
+
+// Frees a string returned by [`formate_js_format`]. Does nothing if `value` is `NULL`.
+//
+// # Safety
+//
+// `value` must be `NULL` or a pointer returned by [`formate_js_format`] which has not been freed.
+void formate_js_string_free(char *value);
+
+// Returns the message of the last error on this thread, or `NULL` if the last call succeeded.
+//
+// The string is owned by the library and is valid until the next call on this thread.
+const char *formate_js_last_error(void);
+
+// Returns the 1-based line of the last error on this thread, or 0 if it is unknown.
+size_t formate_js_last_error_line(void);
+
+// Returns the 1-based column of the last error on this thread, or 0 if it is unknown.
+size_t formate_js_last_error_column(void);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* FORMATE_JS_H */
@@ -27,7 +27,8 @@ Formate plugin for reformatting JavaScript and TypeScript files with dprint.
 #
 
 # stdlib
-from typing import Mapping, Optional
+from functools import lru_cache
+from typing import Any, Mapping, Optional, Tuple
 
 # 3rd party
 from domdf_python_tools.paths import PathPlus
@@ -35,9 +36,26 @@ from domdf_python_tools.typing import PathLike
 from formate.config import formats_filetypes, wants_filename, wants_global_config
 
 # this package
-from ._formate_js import Configuration, ConfigurationBuilder, FormatTextOptions, format_text
+from ._formate_js import (
+		Configuration,
+		ConfigurationBuilder,
+		FormatError,
+		FormatResult,
+		Formatter,
+		FormatTimeout,
+		NonIdempotentFormatting
+		)
 
-__all__ = ["javascript_hook", "Configuration", "ConfigurationBuilder"]
+__all__ = [
+		"javascript_hook",
+		"Configuration",
+		"ConfigurationBuilder",
+		"FormatError",
+		"FormatResult",
+		"Formatter",
+		"FormatTimeout",
+		"NonIdempotentFormatting",
+		]
 
 
 @formats_filetypes(".ts", ".js")
@@ -55,7 +73,8 @@ def javascript_hook(
 	:param source: The source to reformat.
 	:param formate_filename: The name of the file being formatted.
 	:param formate_global_config: The global configuration dictionary. Optional.
-	:param \*\*kwargs:
+	:param \*\*kwargs: Options for :class:`~.Configuration`, plus an optional ``timeout``
+		for formatting the file, in seconds.
 
 	:returns: The reformatted source.
 	"""
@@ -77,13 +96,19 @@ def javascript_hook(
 					# All spaces
 					kwargs.setdefault("indent_width", len(indent_setting))
 
-	config = Configuration(**kwargs)
+	timeout = kwargs.pop("timeout", None)
+	formatter = _get_formatter(tuple(sorted((key, _freeze(value)) for key, value in kwargs.items())))
 	filename_p = PathPlus(formate_filename)
-	options = FormatTextOptions(filename_p.as_posix(), filename_p.suffix, source, config)
-	result = format_text(options)
+	return formatter.format(source, filename_p.as_posix(), filename_p.suffix, timeout=timeout).text
 
-	if result is None:
-		# No change
-		return source
 
-	return result
+def _freeze(value: Any) -> Any:
+	if isinstance(value, list):
+		return tuple(value)
+	return value
+
+
+@lru_cache(maxsize=64)
+def _get_formatter(options: Tuple[Tuple[str, Any], ...]) -> Formatter:
+	# Resolving the configuration is relatively expensive, so reuse formatters between calls with the same kwargs.
+	return Formatter(Configuration(**dict(options)))
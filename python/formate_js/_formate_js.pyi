@@ -1,7 +1,7 @@
 # stdlib
 import os
 import pathlib
-from typing import Any, Dict, Iterator, List, Optional, Tuple
+from typing import Any, Callable, Dict, Iterator, List, Literal, Optional, Tuple
 
 # this package
 from formate_js.enums import (
@@ -264,6 +264,10 @@ class Configuration:
 	export_declaration_sort_type_only_exports: NamedTypeImportsExportsOrder
 	ignore_node_comment_text: str
 	ignore_file_comment_text: str
+	ignore_node_comment_aliases: List[str]
+	ignore_file_comment_aliases: List[str]
+	ignore_range_start_comment_text: str
+	ignore_range_end_comment_text: str
 	arrow_function_brace_position: BracePosition
 	class_declaration_brace_position: BracePosition
 	class_expression_brace_position: BracePosition
@@ -410,6 +414,12 @@ class Configuration:
 	switch_statement_space_around: bool
 	tuple_type_space_around: bool
 	while_statement_space_around: bool
+	max_file_size: Optional[int]
+	max_average_line_length: Optional[int]
+	skip_minified_files: bool
+	max_nesting_depth: Optional[int]
+	worker_stack_size: int
+	verify_idempotent: bool
 
 	def __init__(
 			self,
@@ -440,6 +450,10 @@ class Configuration:
 			export_declaration_sort_type_only_exports: NamedTypeImportsExportsOrder = ...,
 			ignore_node_comment_text: str = ...,
 			ignore_file_comment_text: str = ...,
+			ignore_node_comment_aliases: List[str] = ...,
+			ignore_file_comment_aliases: List[str] = ...,
+			ignore_range_start_comment_text: str = ...,
+			ignore_range_end_comment_text: str = ...,
 			arrow_function_brace_position: BracePosition = ...,
 			class_declaration_brace_position: BracePosition = ...,
 			class_expression_brace_position: BracePosition = ...,
@@ -586,6 +600,12 @@ class Configuration:
 			switch_statement_space_around: bool = ...,
 			tuple_type_space_around: bool = ...,
 			while_statement_space_around: bool = ...,
+			max_file_size: Optional[int] = ...,
+			max_average_line_length: Optional[int] = ...,
+			skip_minified_files: bool = ...,
+			max_nesting_depth: Optional[int] = ...,
+			worker_stack_size: int = ...,
+			verify_idempotent: bool = ...,
 			): ...
 
 	def to_dict(self, changed: bool = ...) -> Dict[str, Any]: ...
@@ -611,4 +631,138 @@ class FormatTextOptions:
 			config: Configuration,
 			): ...
 
-def format_text(options: FormatTextOptions) -> Optional[str]: ...
+class FormatResult:
+	text: str
+	changed: bool
+	original_len: int
+	formatted_len: int
+	changed_lines: int
+	parse_duration: float
+	print_duration: float
+	syntax: str
+	ignore_regions: List[Tuple[int, int]]
+	skipped: Optional[str]
+	mapped_offsets: Optional[List[int]]
+	source_map: Optional[str]
+
+class FormatError(ValueError):
+	path: pathlib.Path
+	crash_report: Optional[str]
+
+class FormatTimeout(FormatError): ...
+
+class NonIdempotentFormatting(FormatError):
+	diff: str
+
+class Formatter:
+	def __init__(self, config: Configuration) -> None: ...
+
+	def format(
+			self,
+			text: str,
+			path: PathLike,
+			extension: Optional[str] = ...,
+			timeout: Optional[float] = ...,
+			offsets: Optional[List[int]] = ...,
+			offset_encoding: Literal["utf-8", "utf-16", "utf-32"] = ...,
+			source_map: bool = ...,
+			) -> FormatResult: ...
+
+class Position:
+	line: int
+	character: int
+
+	def to_lsp(self) -> Dict[str, int]: ...
+
+class Range:
+	start: Position
+	end: Position
+
+	def to_lsp(self) -> Dict[str, Dict[str, int]]: ...
+
+class TextEdit:
+	start: int
+	end: int
+	range: Range
+	new_text: str
+
+	def to_lsp(self) -> Dict[str, Any]: ...
+	def to_tuple(self) -> Tuple[int, int, str]: ...
+
+def format_text(
+		options: FormatTextOptions,
+		timeout: Optional[float] = ...,
+		offsets: Optional[List[int]] = ...,
+		offset_encoding: Literal["utf-8", "utf-16", "utf-32"] = ...,
+		source_map: bool = ...,
+		) -> FormatResult: ...
+
+def format_text_edits(
+		options: FormatTextOptions,
+		timeout: Optional[float] = ...,
+		offset_encoding: Literal["utf-8", "utf-16", "utf-32"] = ...,
+		) -> List[TextEdit]: ...
+
+def format_changed_regions(
+		old_text: str,
+		new_text: str,
+		options: Configuration,
+		path: PathLike = ...,
+		extension: Optional[str] = ...,
+		timeout: Optional[float] = ...,
+		) -> FormatResult: ...
+
+def debug_print_items(
+		options: FormatTextOptions,
+		format: Literal["text", "json"] = ...,
+		) -> str: ...
+
+def format_file(
+		path: PathLike,
+		config: Configuration,
+		write: bool = ...,
+		backup_suffix: Optional[str] = ...,
+		timeout: Optional[float] = ...,
+		cache_dir: Optional[PathLike] = ...,
+		) -> FormatResult: ...
+
+class ProjectSummary:
+	changed: List[pathlib.Path]
+	unchanged: List[pathlib.Path]
+	skipped: List[Tuple[pathlib.Path, str]]
+	failed: List[Tuple[pathlib.Path, str]]
+
+def format_project(
+		root: PathLike,
+		config: Configuration,
+		include: Optional[List[str]] = ...,
+		exclude: Optional[List[str]] = ...,
+		skip_node_modules: bool = ...,
+		write: bool = ...,
+		cache_dir: Optional[PathLike] = ...,
+		) -> ProjectSummary: ...
+
+class LongLine:
+	line: int
+	width: int
+	kind: Optional[str]
+
+def find_overlong_lines(
+		options: FormatTextOptions,
+		timeout: Optional[float] = ...,
+		) -> List[LongLine]: ...
+
+class WatchEvent:
+	path: pathlib.Path
+	kind: Literal["formatted", "unchanged", "failed"]
+	error: Optional[str]
+
+def watch(
+		paths: List[PathLike],
+		config: Configuration,
+		on_event: Optional[Callable[[WatchEvent], Optional[bool]]] = ...,
+		include: Optional[List[str]] = ...,
+		exclude: Optional[List[str]] = ...,
+		debounce: float = ...,
+		timeout: Optional[float] = ...,
+		) -> None: ...
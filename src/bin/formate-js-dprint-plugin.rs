@@ -0,0 +1,14 @@
+//! A dprint process plugin which formats JavaScript and TypeScript over stdio.
+//!
+//! dprint starts plugins with `--parent-pid <pid>`, and the plugin exits when that process does.
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+	match formate_js::dprint_plugin::serve() {
+		Ok(()) => ExitCode::SUCCESS,
+		Err(error) => {
+			eprintln!("formate-js-dprint-plugin: {:#}", error);
+			ExitCode::FAILURE
+		}
+	}
+}
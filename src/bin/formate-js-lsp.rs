@@ -0,0 +1,15 @@
+//! A Language Server Protocol server which formats JavaScript and TypeScript over stdio.
+use std::io;
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+	match formate_js::lsp::serve(io::stdin().lock(), io::stdout().lock()) {
+		Ok(true) => ExitCode::SUCCESS,
+		// Exiting without being asked to shut down first is an error.
+		Ok(false) => ExitCode::FAILURE,
+		Err(error) => {
+			eprintln!("formate-js-lsp: {:#}", error);
+			ExitCode::FAILURE
+		}
+	}
+}
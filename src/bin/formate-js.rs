@@ -0,0 +1,4 @@
+//! Formats JavaScript and TypeScript files from the command line.
+fn main() -> std::process::ExitCode {
+	formate_js::cli::main(std::env::args_os().skip(1))
+}
@@ -0,0 +1,132 @@
+//! A persistent record of file contents which are already formatted, so they can be skipped without parsing.
+//!
+//! The cache is a directory of empty files named after a hash of the contents, the syntax the file is
+//! parsed as, whether its name marks it as minified, the formatter's resolved configuration and the
+//! version of formate-js, so changing the configuration or upgrading misses every existing entry. Processes sharing the directory coordinate through a lock file in it.
+//!
+//! A formatter with an external formatter for embedded code is never cached, as nothing identifies what
+//! the external formatter does.
+
+use crate::formatter::Formatter;
+use crate::parse;
+use crate::skip::has_minified_file_name;
+use anyhow::{Context, Result};
+use std::fs::{self, File, OpenOptions};
+use std::io;
+use std::path::{Path, PathBuf};
+
+const LOCK_FILE: &str = ".lock";
+
+/// A directory recording which file contents are already formatted with a given configuration.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Cache {
+	directory: PathBuf,
+	/// A hash of the formatter's resolved options and the version of formate-js,
+	/// or `None` if the formatter's output cannot be cached.
+	fingerprint: Option<blake3::Hash>,
+}
+
+impl Cache {
+	/// Opens the cache in `directory` for files formatted by `formatter`, creating the directory if needed.
+	pub fn open(directory: &Path, formatter: &Formatter) -> Result<Self> {
+		fs::create_dir_all(directory)
+			.with_context(|| format!("Could not create '{}'", directory.display()))?;
+
+		let mut hasher = blake3::Hasher::new();
+		hasher.update(env!("CARGO_PKG_VERSION").as_bytes());
+		hasher.update(b"\0");
+		hasher.update(&serde_json::to_vec(&formatter.config)?);
+		hasher.update(b"\0");
+		let options = format!(
+			"{:?} {:?} {:?}",
+			formatter.ignore_options, formatter.skip_options, formatter.max_nesting_depth,
+		);
+		hasher.update(options.as_bytes());
+
+		Ok(Cache {
+			directory: directory.to_path_buf(),
+			fingerprint: formatter
+				.external_formatter
+				.is_none()
+				.then(|| hasher.finalize()),
+		})
+	}
+
+	/// The directory the cache is stored in.
+	pub fn directory(&self) -> &Path {
+		&self.directory
+	}
+
+	/// The entry for `contents` read from the file at `path`.
+	///
+	/// Formatting only depends on the path through these two, so other files which agree on them share it.
+	fn entry(&self, path: &Path, contents: &[u8]) -> Option<PathBuf> {
+		let mut hasher = blake3::Hasher::new();
+		hasher.update(self.fingerprint?.as_bytes());
+		let path_key = format!(
+			"{:?} {}\0",
+			parse::media_type(path, None),
+			has_minified_file_name(path)
+		);
+		hasher.update(path_key.as_bytes());
+		hasher.update(contents);
+		let hash = hasher.finalize().to_hex();
+		Some(self.directory.join(&hash[..2]).join(&hash[2..]))
+	}
+
+	/// Locks the cache until the returned file is dropped.
+	///
+	/// Entries are looked up and added under a shared lock, as adding one is idempotent,
+	/// while [`Cache::clear`] takes an exclusive lock.
+	fn lock(&self, exclusive: bool) -> io::Result<File> {
+		let file = OpenOptions::new()
+			.create(true)
+			.truncate(false)
+			.write(true)
+			.open(self.directory.join(LOCK_FILE))?;
+		if exclusive {
+			file.lock()?;
+		} else {
+			file.lock_shared()?;
+		}
+		Ok(file)
+	}
+
+	/// Whether `contents` is recorded as already formatted for the file at `path`.
+	/// Always `false` for a formatter which is not cached.
+	pub fn contains(&self, path: &Path, contents: &[u8]) -> bool {
+		let Some(entry) = self.entry(path, contents) else {
+			return false;
+		};
+		let Ok(_lock) = self.lock(false) else {
+			return false;
+		};
+		entry.is_file()
+	}
+
+	/// Records that `contents` is already formatted for the file at `path`.
+	/// Does nothing for a formatter which is not cached.
+	pub fn insert(&self, path: &Path, contents: &[u8]) -> io::Result<()> {
+		let Some(entry) = self.entry(path, contents) else {
+			return Ok(());
+		};
+		let _lock = self.lock(false)?;
+		if let Some(parent) = entry.parent() {
+			fs::create_dir_all(parent)?;
+		}
+		File::create(entry)?;
+		Ok(())
+	}
+
+	/// Removes every entry, including those recorded with other configurations.
+	pub fn clear(&self) -> io::Result<()> {
+		let _lock = self.lock(true)?;
+		for entry in fs::read_dir(&self.directory)? {
+			let entry = entry?;
+			if entry.file_type()?.is_dir() {
+				fs::remove_dir_all(entry.path())?;
+			}
+		}
+		Ok(())
+	}
+}
@@ -0,0 +1,195 @@
+//! A C interface to the formatter.
+//!
+//! Every function records the error from a failed call for the calling thread,
+//! which can be read with [`formate_js_last_error`] until the next call on that thread.
+
+use crate::configuration::ConfigurationOptions;
+use crate::formatter::Formatter;
+use crate::ignore::IgnoreError;
+use crate::parse::ParseError;
+use crate::worker::format_on_worker;
+use std::cell::RefCell;
+use std::ffi::{CStr, CString, c_char};
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::path::PathBuf;
+use std::ptr;
+use std::sync::Arc;
+
+/// A formatter created from a JSON configuration.
+pub struct FormateJsConfig(Arc<Formatter>);
+
+struct LastError {
+	message: CString,
+	line: usize,
+	column: usize,
+}
+
+thread_local! {
+	static LAST_ERROR: RefCell<Option<LastError>> = const { RefCell::new(None) };
+}
+
+fn set_last_error(error: &anyhow::Error) {
+	let (line, column) = error_position(error);
+	// Interior NUL bytes can only come from the formatted text, which is echoed in syntax errors.
+	let message = CString::new(error.to_string().replace('\0', "\\0")).unwrap_or_default();
+	LAST_ERROR.with(|last_error| {
+		*last_error.borrow_mut() = Some(LastError {
+			message,
+			line,
+			column,
+		})
+	});
+}
+
+fn clear_last_error() {
+	LAST_ERROR.with(|last_error| *last_error.borrow_mut() = None);
+}
+
+/// The 1-based line and column of the error, or zero for whichever is unknown.
+fn error_position(error: &anyhow::Error) -> (usize, usize) {
+	if let Some(error) = error.downcast_ref::<ParseError>() {
+		(error.line, error.column)
+	} else if let Some(error) = error.downcast_ref::<IgnoreError>() {
+		(error.line, 0)
+	} else if let Some(error) = error.downcast_ref::<serde_json::Error>() {
+		(error.line(), error.column())
+	} else {
+		(0, 0)
+	}
+}
+
+/// Runs `f`, recording its error (or panic) as the last error and returning `None` if it fails.
+fn ffi_call<T>(f: impl FnOnce() -> anyhow::Result<T>) -> Option<T> {
+	let result = match catch_unwind(AssertUnwindSafe(f)) {
+		Ok(result) => result,
+		Err(_) => Err(anyhow::anyhow!("The formatter panicked")),
+	};
+	match result {
+		Ok(value) => {
+			clear_last_error();
+			Some(value)
+		}
+		Err(error) => {
+			set_last_error(&error);
+			None
+		}
+	}
+}
+
+unsafe fn str_arg<'a>(value: *const c_char, name: &str) -> anyhow::Result<&'a str> {
+	if value.is_null() {
+		anyhow::bail!("'{}' must not be NULL", name);
+	}
+	Ok(unsafe { CStr::from_ptr(value) }.to_str()?)
+}
+
+/// Creates a formatter from a JSON object of configuration options.
+///
+/// The options are the same as the keyword arguments of the formate hook. Pass `NULL` to use the defaults.
+/// Returns `NULL` on error. The formatter must be freed with [`formate_js_config_free`].
+///
+/// # Safety
+///
+/// `json` must be `NULL` or a valid NUL-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn formate_js_config_new(json: *const c_char) -> *mut FormateJsConfig {
+	ffi_call(|| {
+		let options = if json.is_null() {
+			ConfigurationOptions::default()
+		} else {
+			serde_json::from_str(unsafe { str_arg(json, "json") }?)?
+		};
+		let formatter = Formatter::new(&options)?;
+		Ok(Box::into_raw(Box::new(FormateJsConfig(Arc::new(
+			formatter,
+		)))))
+	})
+	.unwrap_or(ptr::null_mut())
+}
+
+/// Frees a formatter created by [`formate_js_config_new`]. Does nothing if `config` is `NULL`.
+///
+/// # Safety
+///
+/// `config` must be `NULL` or a pointer returned by [`formate_js_config_new`] which has not been freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn formate_js_config_free(config: *mut FormateJsConfig) {
+	if !config.is_null() {
+		drop(unsafe { Box::from_raw(config) });
+	}
+}
+
+/// Formats `text_len` bytes of UTF-8 `text` as the file at `path`.
+///
+/// Returns the formatted text as a NUL-terminated string, which must be freed with [`formate_js_string_free`],
+/// or `NULL` on error. If `out_len` is not `NULL` it is set to the length of the formatted text, in bytes.
+///
+/// # Safety
+///
+/// `config` must be a valid formatter, `path` a valid NUL-terminated string,
+/// `text` must point to at least `text_len` readable bytes, and `out_len` must be `NULL` or writable.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn formate_js_format(
+	config: *const FormateJsConfig,
+	path: *const c_char,
+	text: *const c_char,
+	text_len: usize,
+	out_len: *mut usize,
+) -> *mut c_char {
+	ffi_call(|| {
+		if config.is_null() {
+			anyhow::bail!("'config' must not be NULL");
+		}
+		if text.is_null() {
+			anyhow::bail!("'text' must not be NULL");
+		}
+		let formatter = unsafe { (*config).0.clone() };
+		let path = unsafe { str_arg(path, "path") }?;
+		let text = unsafe { std::slice::from_raw_parts(text.cast::<u8>(), text_len) };
+		let text = String::from_utf8(text.to_vec())?;
+
+		// Formatting runs on a thread with a large stack, as the caller's may be too small for deeply nested files.
+		let result = format_on_worker(formatter, PathBuf::from(path), None, text, None)?;
+		let formatted = CString::new(result.text)?;
+		if !out_len.is_null() {
+			unsafe { *out_len = formatted.as_bytes().len() };
+		}
+		Ok(formatted.into_raw())
+	})
+	.unwrap_or(ptr::null_mut())
+}
+
+/// Frees a string returned by [`formate_js_format`]. Does nothing if `value` is `NULL`.
+///
+/// # Safety
+///
+/// `value` must be `NULL` or a pointer returned by [`formate_js_format`] which has not been freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn formate_js_string_free(value: *mut c_char) {
+	if !value.is_null() {
+		drop(unsafe { CString::from_raw(value) });
+	}
+}
+
+/// Returns the message of the last error on this thread, or `NULL` if the last call succeeded.
+///
+/// The string is owned by the library and is valid until the next call on this thread.
+#[unsafe(no_mangle)]
+pub extern "C" fn formate_js_last_error() -> *const c_char {
+	LAST_ERROR.with(|last_error| match &*last_error.borrow() {
+		Some(last_error) => last_error.message.as_ptr(),
+		None => ptr::null(),
+	})
+}
+
+/// Returns the 1-based line of the last error on this thread, or 0 if it is unknown.
+#[unsafe(no_mangle)]
+pub extern "C" fn formate_js_last_error_line() -> usize {
+	LAST_ERROR.with(|last_error| last_error.borrow().as_ref().map_or(0, |error| error.line))
+}
+
+/// Returns the 1-based column of the last error on this thread, or 0 if it is unknown.
+#[unsafe(no_mangle)]
+pub extern "C" fn formate_js_last_error_column() -> usize {
+	LAST_ERROR.with(|last_error| last_error.borrow().as_ref().map_or(0, |error| error.column))
+}
@@ -0,0 +1,543 @@
+//! The `formate-js` command, which formats files without needing Python.
+//!
+//! The configuration is read from the nearest `formate.toml` (or `pyproject.toml`), the same as the
+//! formate hook, or dprint configuration file.
+
+use crate::dprint_plugin::{DPRINT_CONFIG_NAMES, load_dprint_config};
+use crate::files::{FileOptions, format_file, read_text};
+use crate::formate_config::{HookConfiguration, formate_toml_in, load_formate_toml};
+use crate::formatter::Formatter;
+use crate::git::{Repository, StagedOptions, WorktreeUpdate};
+use crate::globs::glob_set;
+use crate::parse::is_formattable;
+use crate::project::{ProjectOptions, find_project_files};
+use crate::watch::{WatchEventKind, WatchOptions, Watcher};
+use crate::worker::{format_on_worker, map_on_workers};
+use anyhow::{Context, Result, bail};
+use globset::GlobBuilder;
+use lexopt::prelude::*;
+use similar::TextDiff;
+use std::ffi::OsString;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::sync::Arc;
+use std::time::Duration;
+
+const USAGE: &str = "\
+Usage: formate-js [OPTIONS] [PATHS]...
+       formate-js watch [--include GLOB] [--exclude GLOB] [--config PATH] [PATHS]...
+
+Formats JavaScript and TypeScript files in place.
+PATHS may be files, directories or glob patterns, and default to the current directory.
+Directories and glob patterns skip files listed in .gitignore, .ignore, .prettierignore,
+.dprintignore and .formate-js-ignore files.
+With watch, files and directories in PATHS are watched, and files are formatted whenever they are saved.
+With --staged or --since, PATHS are git pathspecs limiting which files are formatted.
+
+Options:
+  --check                Report files which are not formatted instead of formatting them
+  --diff                 Print the changes formatting would make instead of making them
+  --stdin-filepath PATH  Format standard input as if it were the file at PATH, writing to standard output
+  --staged               Format the content staged in git, in the index and the work tree,
+                         keeping unstaged changes
+  --index-only           With --staged, only update the index
+  --since REF            Format the files in the work tree which differ from the commit REF
+  --exclude GLOB         Skip files and directories matching GLOB; may be given more than once
+  --include GLOB         With watch, format the files matching GLOB rather than those with
+                         JavaScript and TypeScript extensions; may be given more than once
+  --config PATH          Read the configuration from PATH rather than searching for it
+  -h, --help             Print this help
+  -V, --version          Print the version
+
+Exits with status 1 if --check or --diff finds files which are not formatted,
+and 2 if any file could not be formatted.
+";
+
+/// The exit status when `--check` or `--diff` finds files which are not formatted.
+const EXIT_UNFORMATTED: u8 = 1;
+/// The exit status when a file could not be formatted, or the arguments or configuration are invalid.
+const EXIT_ERROR: u8 = 2;
+
+/// The options given on the command line.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Options {
+	pub paths: Vec<String>,
+	pub excludes: Vec<String>,
+	pub includes: Vec<String>,
+	pub watch: bool,
+	pub check: bool,
+	pub diff: bool,
+	pub stdin_filepath: Option<PathBuf>,
+	pub staged: bool,
+	pub index_only: bool,
+	pub since: Option<String>,
+	pub config: Option<PathBuf>,
+}
+
+enum Command {
+	Help,
+	Version,
+	Format(Options),
+}
+
+fn parse_args(args: impl IntoIterator<Item = OsString>) -> Result<Command, lexopt::Error> {
+	let mut options = Options::default();
+	let mut parser = lexopt::Parser::from_args(args);
+	let mut first = true;
+	while let Some(arg) = parser.next()? {
+		let is_first = std::mem::replace(&mut first, false);
+		match arg {
+			Value(command) if is_first && command == "watch" => options.watch = true,
+			Short('h') | Long("help") => return Ok(Command::Help),
+			Short('V') | Long("version") => return Ok(Command::Version),
+			Long("check") => options.check = true,
+			Long("diff") => options.diff = true,
+			Long("stdin-filepath") => options.stdin_filepath = Some(parser.value()?.into()),
+			Long("staged") => options.staged = true,
+			Long("index-only") => options.index_only = true,
+			Long("since") => options.since = Some(parser.value()?.string()?),
+			Long("exclude") => options.excludes.push(parser.value()?.string()?),
+			Long("include") => options.includes.push(parser.value()?.string()?),
+			Long("config") => options.config = Some(parser.value()?.into()),
+			Value(path) => options.paths.push(path.string()?),
+			_ => return Err(arg.unexpected()),
+		}
+	}
+	if !options.watch && !options.includes.is_empty() {
+		return Err("--include can only be used with watch".into());
+	}
+	if options.watch && (options.check || options.diff || options.stdin_filepath.is_some()) {
+		return Err("--check, --diff and --stdin-filepath cannot be used with watch".into());
+	}
+	let modes = [
+		options.watch,
+		options.stdin_filepath.is_some(),
+		options.staged,
+		options.since.is_some(),
+	];
+	if modes.into_iter().filter(|mode| *mode).count() > 1 {
+		return Err("only one of watch, --stdin-filepath, --staged and --since can be used".into());
+	}
+	if options.index_only && !options.staged {
+		return Err("--index-only can only be used with --staged".into());
+	}
+	Ok(Command::Format(options))
+}
+
+/// Runs the command with the arguments after the program name.
+pub fn main(args: impl IntoIterator<Item = OsString>) -> ExitCode {
+	let options = match parse_args(args) {
+		Ok(Command::Help) => {
+			print!("{}", USAGE);
+			return ExitCode::SUCCESS;
+		}
+		Ok(Command::Version) => {
+			println!("formate-js {}", env!("CARGO_PKG_VERSION"));
+			return ExitCode::SUCCESS;
+		}
+		Ok(Command::Format(options)) => options,
+		Err(error) => {
+			eprintln!("formate-js: {}\n\n{}", error, USAGE);
+			return ExitCode::from(EXIT_ERROR);
+		}
+	};
+
+	let result = match &options.stdin_filepath {
+		Some(path) => format_stdin(&options, path),
+		None if options.watch => watch(&options),
+		None if options.staged => format_staged(&options),
+		None => format_paths(&options),
+	};
+	match result {
+		Ok(status) => ExitCode::from(status),
+		Err(error) => {
+			eprintln!("formate-js: {:#}", error);
+			ExitCode::from(EXIT_ERROR)
+		}
+	}
+}
+
+/// Finds the configuration file for files in `directory`.
+///
+/// This is the nearest formate or dprint configuration file, preferring formate's in the same directory.
+pub fn find_config(directory: &Path) -> Option<PathBuf> {
+	directory.ancestors().find_map(|directory| {
+		formate_toml_in(directory).or_else(|| {
+			DPRINT_CONFIG_NAMES
+				.iter()
+				.map(|name| directory.join(name))
+				.find(|path| path.is_file())
+		})
+	})
+}
+
+/// Reads the configuration from a formate or dprint configuration file, along with any warnings about it.
+///
+/// A formate configuration file which does not enable the hook gives the default configuration.
+pub fn load_config(path: &Path) -> Result<(HookConfiguration, Vec<String>)> {
+	let is_dprint_config = path
+		.extension()
+		.is_some_and(|extension| extension == "json" || extension == "jsonc");
+	if is_dprint_config {
+		load_dprint_config(path)
+	} else {
+		Ok((load_formate_toml(path)?.unwrap_or_default(), Vec::new()))
+	}
+}
+
+/// Loads the configuration given on the command line, or the one which applies to files in `directory`.
+fn formatter(options: &Options, directory: &Path) -> Result<(Arc<Formatter>, Option<Duration>)> {
+	let config_path = options.config.clone().or_else(|| find_config(directory));
+	let config = match config_path {
+		Some(config_path) => {
+			let (config, warnings) = load_config(&config_path)?;
+			for warning in warnings {
+				eprintln!(
+					"formate-js: ignoring an option in '{}': {}",
+					config_path.display(),
+					warning
+				);
+			}
+			config
+		}
+		None => HookConfiguration::default(),
+	};
+	let formatter = Formatter::new(&config.options)?;
+	Ok((
+		Arc::new(formatter),
+		config.timeout.map(Duration::from_secs_f64),
+	))
+}
+
+fn unified_diff(path: &Path, original: &str, formatted: &str) -> String {
+	let path = path.display().to_string();
+	TextDiff::from_lines(original, formatted)
+		.unified_diff()
+		.header(&path, &path)
+		.to_string()
+}
+
+fn format_stdin(options: &Options, path: &Path) -> Result<u8> {
+	let mut text = String::new();
+	io::stdin()
+		.read_to_string(&mut text)
+		.context("Could not read standard input")?;
+
+	let directory = std::env::current_dir()?.join(path);
+	let (formatter, timeout) = formatter(options, directory.parent().unwrap_or(&directory))?;
+	let result = format_on_worker(formatter, path.to_path_buf(), None, text.clone(), timeout)?;
+
+	let mut stdout = io::stdout().lock();
+	if options.diff {
+		if result.changed {
+			write!(stdout, "{}", unified_diff(path, &text, &result.text))?;
+		}
+	} else if !options.check {
+		write!(stdout, "{}", result.text)?;
+	}
+	Ok(if result.changed && (options.check || options.diff) {
+		EXIT_UNFORMATTED
+	} else {
+		0
+	})
+}
+
+fn is_glob(pattern: &str) -> bool {
+	pattern.contains(['*', '?', '[', '{'])
+}
+
+/// The files under `directory`, in order, found as [`format_project`](crate::project::format_project)
+/// finds them, honouring ignore files. Only those with JavaScript and TypeScript extensions are
+/// returned unless `all_files` is set.
+fn directory_files(directory: &Path, all_files: bool) -> Result<Vec<PathBuf>> {
+	let options = ProjectOptions {
+		includes: if all_files {
+			vec!["*".to_string()]
+		} else {
+			Vec::new()
+		},
+		..ProjectOptions::default()
+	};
+	if directory.as_os_str().is_empty() {
+		let files = find_project_files(Path::new("."), &options)?;
+		Ok(files
+			.into_iter()
+			.map(|path| {
+				path.strip_prefix(".")
+					.map_or(path.clone(), Path::to_path_buf)
+			})
+			.collect())
+	} else {
+		find_project_files(directory, &options)
+	}
+}
+
+/// Expands the paths and glob patterns given on the command line into the files to format.
+///
+/// Directories give the files in them with JavaScript and TypeScript extensions that ignore files
+/// don't exclude, and files given by name are skipped unless they have one.
+pub fn collect_files(paths: &[String], excludes: &[String]) -> Result<Vec<PathBuf>> {
+	let excludes = glob_set(excludes)?;
+	let is_excluded = |path: &Path| excludes.is_match(path.strip_prefix("./").unwrap_or(path));
+
+	let mut files = Vec::new();
+	for pattern in paths {
+		if is_glob(pattern) {
+			let pattern = pattern.trim_start_matches("./");
+			let matcher = GlobBuilder::new(pattern)
+				.literal_separator(true)
+				.build()
+				.with_context(|| format!("Invalid glob pattern '{}'", pattern))?
+				.compile_matcher();
+			// Only look through the directory the pattern starts with.
+			let base = Path::new(pattern)
+				.components()
+				.take_while(|component| !is_glob(&component.as_os_str().to_string_lossy()))
+				.collect::<PathBuf>();
+			let candidates = if base.is_dir() || base.as_os_str().is_empty() {
+				directory_files(&base, true)?
+			} else {
+				Vec::new()
+			};
+			files.extend(
+				candidates
+					.into_iter()
+					.filter(|path| matcher.is_match(path) && !is_excluded(path)),
+			);
+			continue;
+		}
+
+		let path = PathBuf::from(pattern);
+		if path.is_dir() {
+			files.extend(
+				directory_files(&path, false)?
+					.into_iter()
+					.filter(|path| !is_excluded(path)),
+			);
+		} else if path.exists() {
+			if is_formattable(&path) && !is_excluded(&path) {
+				files.push(path);
+			}
+		} else {
+			bail!("'{}' does not exist", pattern);
+		}
+	}
+
+	let mut seen = std::collections::HashSet::new();
+	files.retain(|path| seen.insert(path.clone()));
+	Ok(files)
+}
+
+/// Makes `path` relative to `directory` if it is inside it.
+fn relative_to(path: &Path, directory: &Path) -> PathBuf {
+	path.strip_prefix(directory).unwrap_or(path).to_path_buf()
+}
+
+/// The paths given on the command line as git pathspecs, which are absolute so git can run anywhere.
+fn pathspecs(options: &Options, current_dir: &Path) -> Vec<String> {
+	options
+		.paths
+		.iter()
+		.map(|path| current_dir.join(path).display().to_string())
+		.collect()
+}
+
+/// Lists the files in the work tree which differ from the commit `reference`.
+fn changed_files(options: &Options, reference: &str) -> Result<Vec<PathBuf>> {
+	let current_dir = std::env::current_dir()?;
+	let repository = Repository::discover(&current_dir)?;
+	let excludes = glob_set(&options.excludes)?;
+	Ok(repository
+		.changed_since(reference, &pathspecs(options, &current_dir))?
+		.into_iter()
+		.map(|path| relative_to(&path, &current_dir))
+		.filter(|path| !excludes.is_match(path))
+		.collect())
+}
+
+fn format_paths(options: &Options) -> Result<u8> {
+	let files = match &options.since {
+		Some(reference) => changed_files(options, reference)?,
+		None if options.paths.is_empty() => collect_files(&[".".to_string()], &options.excludes)?,
+		None => collect_files(&options.paths, &options.excludes)?,
+	};
+	let (formatter, timeout) = formatter(options, &std::env::current_dir()?)?;
+	let file_options = FileOptions {
+		write: !(options.check || options.diff),
+		timeout,
+		..FileOptions::default()
+	};
+	let stack_size = formatter.worker_stack_size;
+	let results = map_on_workers(stack_size, files, move |path| {
+		let result = format_file(formatter.clone(), &path, &file_options);
+		(path, result)
+	});
+
+	let mut errors = 0;
+	let mut changed = 0;
+	let mut unchanged = 0;
+	let mut stdout = io::stdout().lock();
+	for (path, result) in results {
+		let result = match result {
+			Ok(result) => result,
+			Err(error) => {
+				eprintln!("formate-js: {:#}", error);
+				errors += 1;
+				continue;
+			}
+		};
+		if !result.changed {
+			unchanged += 1;
+			continue;
+		}
+
+		changed += 1;
+		if options.diff {
+			// The file was not written, so it still holds the original text.
+			let (original, _) = read_text(&path)?;
+			write!(stdout, "{}", unified_diff(&path, &original, &result.text))?;
+		}
+		if options.check {
+			eprintln!("Would reformat {}", path.display());
+		} else if !options.diff {
+			eprintln!("Reformatted {}", path.display());
+		}
+	}
+
+	Ok(summary(options, changed, unchanged, errors))
+}
+
+/// Formats the content staged in git, and the same files in the work tree unless `--index-only` is given.
+fn format_staged(options: &Options) -> Result<u8> {
+	let current_dir = std::env::current_dir()?;
+	let repository = Repository::discover(&current_dir)?;
+	let (formatter, timeout) = formatter(options, &current_dir)?;
+	let write = !(options.check || options.diff);
+	let results = repository.format_staged(
+		&formatter,
+		&StagedOptions {
+			pathspecs: pathspecs(options, &current_dir),
+			excludes: options.excludes.clone(),
+			write,
+			update_worktree: write && !options.index_only,
+			timeout,
+		},
+	)?;
+
+	let mut errors = 0;
+	let mut changed = 0;
+	let mut unchanged = 0;
+	let mut stdout = io::stdout().lock();
+	for staged in results {
+		let path = relative_to(
+			&repository.top_level().join(&staged.file.path),
+			&current_dir,
+		);
+		let result = match staged.result {
+			Ok(result) => result,
+			Err(error) => {
+				eprintln!("formate-js: {:#}", error);
+				errors += 1;
+				continue;
+			}
+		};
+		if !result.changed {
+			unchanged += 1;
+			continue;
+		}
+
+		changed += 1;
+		if options.diff {
+			write!(
+				stdout,
+				"{}",
+				unified_diff(&path, &staged.original, &result.text)
+			)?;
+		}
+		if options.check {
+			eprintln!("Would reformat {}", path.display());
+		} else if !options.diff {
+			eprintln!("Reformatted {}", path.display());
+			if staged.worktree == WorktreeUpdate::Conflict {
+				eprintln!(
+					"formate-js: only the staged copy of {} was reformatted, as its unstaged changes conflict with the formatting",
+					path.display()
+				);
+			}
+		}
+	}
+
+	Ok(summary(options, changed, unchanged, errors))
+}
+
+/// Prints how many files were reformatted, returning the exit status.
+fn summary(options: &Options, changed: usize, unchanged: usize, errors: usize) -> u8 {
+	let verb = if options.check || options.diff {
+		"would be reformatted"
+	} else {
+		"reformatted"
+	};
+	eprintln!(
+		"{} {} {}, {} {} left unchanged{}",
+		changed,
+		plural(changed, "file"),
+		verb,
+		unchanged,
+		plural(unchanged, "file"),
+		if errors > 0 {
+			format!(", {} {} failed to format", errors, plural(errors, "file"))
+		} else {
+			String::new()
+		}
+	);
+
+	if errors > 0 {
+		EXIT_ERROR
+	} else if changed > 0 && (options.check || options.diff) {
+		EXIT_UNFORMATTED
+	} else {
+		0
+	}
+}
+
+/// Formats files in the paths whenever they change, until the process is killed.
+fn watch(options: &Options) -> Result<u8> {
+	let paths = if options.paths.is_empty() {
+		vec![PathBuf::from(".")]
+	} else {
+		options.paths.iter().map(PathBuf::from).collect()
+	};
+	let (formatter, timeout) = formatter(options, &std::env::current_dir()?)?;
+	let mut watcher = Watcher::new(
+		&paths,
+		formatter,
+		&WatchOptions {
+			includes: options.includes.clone(),
+			excludes: options.excludes.clone(),
+			timeout,
+			..WatchOptions::default()
+		},
+	)?;
+	eprintln!("Watching for changes");
+
+	loop {
+		for event in watcher.poll(Duration::from_secs(60))? {
+			match event.kind {
+				WatchEventKind::Formatted => eprintln!("Reformatted {}", event.path.display()),
+				WatchEventKind::Unchanged => {}
+				WatchEventKind::Failed => {
+					eprintln!("formate-js: {}", event.error.unwrap_or_default())
+				}
+			}
+		}
+	}
+}
+
+fn plural(count: usize, word: &str) -> String {
+	if count == 1 {
+		word.to_string()
+	} else {
+		format!("{}s", word)
+	}
+}
@@ -0,0 +1,377 @@
+//! A dprint process plugin, so the dprint CLI formats with the same engine and options as formate.
+//!
+//! The protocol is handled by dprint-core, and [`serve`] runs the plugin over stdin and stdout.
+//! The plugin configuration takes the hook's options, either in snake case or in dprint's camel case
+//! (`quoteStyle`, or `arrowFunction.useParentheses` with a dot), along with `timeout` in seconds.
+//! dprint's global `lineWidth`, `indentWidth`, `useTabs` and `newLineKind` apply unless overridden.
+//! CSS, HTML and SQL in tagged templates are formatted by asking dprint to format them with its other plugins.
+
+use crate::configuration::ConfigurationOptions;
+use crate::formate_config::HookConfiguration;
+use crate::formatter::{ExternalFormatter, Formatter};
+use crate::parse::FILE_EXTENSIONS;
+use crate::regions::format_range;
+use crate::worker::format_on_worker_cancellable;
+use anyhow::{Context, Result, bail};
+use dprint_core::async_runtime::{LocalBoxFuture, async_trait};
+use dprint_core::configuration::{
+	ConfigKeyMap, ConfigKeyValue, ConfigurationDiagnostic, GlobalConfiguration,
+};
+use dprint_core::plugins::process::{
+	get_parent_process_id_from_cli_args, handle_process_stdio_messages,
+	start_parent_process_checker_task,
+};
+use dprint_core::plugins::{
+	AsyncPluginHandler, CancellationToken, FileMatchingInfo, FormatRequest, HostFormatRequest,
+	PluginInfo, PluginResolveConfigurationResult,
+};
+use dprint_plugin_typescript::configuration::Configuration;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, mpsc};
+use std::time::Duration;
+use tokio::sync::mpsc as async_mpsc;
+
+/// The key of the plugin's table in the dprint configuration file.
+pub const CONFIG_KEY: &str = "formateJs";
+
+/// The template tags whose contents are passed to dprint to format.
+const EMBEDDED_LANGUAGES: &[&str] = &["css", "html", "sql"];
+
+/// The names dprint looks for its configuration file under.
+pub const DPRINT_CONFIG_NAMES: &[&str] = &[
+	"dprint.json",
+	"dprint.jsonc",
+	".dprint.json",
+	".dprint.jsonc",
+];
+
+/// The global options dprint passes to every plugin, and the options they set.
+const GLOBAL_OPTIONS: &[(&str, &str)] = &[
+	("lineWidth", "line_width"),
+	("indentWidth", "indent_width"),
+	("useTabs", "use_tabs"),
+	("newLineKind", "new_line_kind"),
+];
+
+/// A plugin configuration registered by dprint. It serializes to the resolved options.
+#[derive(Clone, Serialize)]
+pub struct PluginConfiguration {
+	#[serde(flatten)]
+	options: ConfigurationOptions,
+	#[serde(skip)]
+	formatter: Formatter,
+	#[serde(skip)]
+	timeout: Option<Duration>,
+}
+
+/// Converts a key of the plugin configuration into the name of an option.
+///
+/// `arrowFunction.useParentheses` becomes `arrow_function_use_parentheses`, and snake case is left as it is.
+fn option_name(key: &str) -> String {
+	let mut name = String::with_capacity(key.len() + 4);
+	for c in key.chars() {
+		if c == '.' {
+			name.push('_');
+		} else if c.is_ascii_uppercase() {
+			name.push('_');
+			name.push(c.to_ascii_lowercase());
+		} else {
+			name.push(c);
+		}
+	}
+	name
+}
+
+fn options_from(values: &Map<String, Value>) -> Result<ConfigurationOptions> {
+	let options: ConfigurationOptions = serde_json::from_value(Value::Object(values.clone()))?;
+	options.resolve()?;
+	Ok(options)
+}
+
+/// Resolves the options the same way the formate hook does, ignoring any invalid ones with a diagnostic.
+fn resolve_config(
+	global_config: &Map<String, Value>,
+	plugin_config: &Map<String, Value>,
+) -> PluginResolveConfigurationResult<PluginConfiguration> {
+	let Value::Object(mut values) = serde_json::to_value(ConfigurationOptions::default())
+		.expect("the options can be serialized")
+	else {
+		unreachable!("the options serialize to an object");
+	};
+	let mut timeout = None;
+	let mut diagnostics = Vec::new();
+	let mut diagnostic = |property_name: &str, message: String| {
+		diagnostics.push(ConfigurationDiagnostic {
+			property_name: property_name.to_string(),
+			message,
+		});
+	};
+
+	let global_options = GLOBAL_OPTIONS
+		.iter()
+		.filter_map(|(key, name)| Some((*key, name.to_string(), global_config.get(*key)?)));
+	let plugin_options = plugin_config
+		.iter()
+		.map(|(key, value)| (key.as_str(), option_name(key), value));
+	for (key, name, value) in global_options.chain(plugin_options) {
+		if name == "timeout" {
+			// Validated as the `timeout` in formate.toml is, so zero gives up at once there too.
+			match value.as_f64().map(Duration::try_from_secs_f64) {
+				Some(Ok(seconds)) => timeout = Some(seconds),
+				_ => diagnostic(key, "Expected a non-negative number of seconds".to_string()),
+			}
+			continue;
+		}
+		let Some(previous) = values.insert(name.clone(), value.clone()) else {
+			values.remove(&name);
+			diagnostic(key, "Unknown property in configuration".to_string());
+			continue;
+		};
+		if let Err(error) = options_from(&values) {
+			values.insert(name, previous);
+			diagnostic(key, format!("{:#}", error));
+		}
+	}
+
+	let options = options_from(&values).expect("only valid options were kept");
+	PluginResolveConfigurationResult {
+		file_matching: FileMatchingInfo {
+			file_extensions: FILE_EXTENSIONS.iter().map(ToString::to_string).collect(),
+			file_names: Vec::new(),
+		},
+		diagnostics,
+		config: PluginConfiguration {
+			formatter: Formatter::new(&options).expect("only valid options were kept"),
+			options,
+			timeout,
+		},
+	}
+}
+
+/// Reads the options from a dprint configuration file, as the plugin would be given them.
+///
+/// The options are taken from the plugin's table, or the TypeScript plugin's if there is none.
+/// Also returns a message for each option which had to be ignored.
+pub fn load_dprint_config(path: &Path) -> Result<(HookConfiguration, Vec<String>)> {
+	let text = std::fs::read_to_string(path)
+		.with_context(|| format!("Could not read '{}'", path.display()))?;
+	let config: Map<String, Value> = serde_json::from_str(&strip_jsonc(&text))
+		.with_context(|| format!("Invalid configuration in '{}'", path.display()))?;
+	let plugin_config = match config.get(CONFIG_KEY).or_else(|| config.get("typescript")) {
+		None => Map::new(),
+		Some(Value::Object(plugin_config)) => plugin_config.clone(),
+		Some(_) => bail!("'{}' must be an object in '{}'", CONFIG_KEY, path.display()),
+	};
+
+	let resolved = resolve_config(&config, &plugin_config);
+	let warnings = resolved
+		.diagnostics
+		.iter()
+		.map(ToString::to_string)
+		.collect();
+	let hook_config = HookConfiguration {
+		options: resolved.config.options,
+		timeout: resolved.config.timeout.map(|timeout| timeout.as_secs_f64()),
+	};
+	Ok((hook_config, warnings))
+}
+
+/// Removes the comments and trailing commas which dprint allows in its configuration files.
+fn strip_jsonc(text: &str) -> String {
+	let mut json = String::with_capacity(text.len());
+	let mut chars = text.chars().peekable();
+	while let Some(c) = chars.next() {
+		match c {
+			'"' => {
+				json.push(c);
+				while let Some(c) = chars.next() {
+					json.push(c);
+					match c {
+						'\\' => json.extend(chars.next()),
+						'"' => break,
+						_ => {}
+					}
+				}
+			}
+			'/' if chars.peek() == Some(&'/') => while chars.next_if(|c| *c != '\n').is_some() {},
+			'/' if chars.peek() == Some(&'*') => {
+				chars.next();
+				let mut previous = None;
+				for c in chars.by_ref() {
+					if previous == Some('*') && c == '/' {
+						break;
+					}
+					previous = Some(c);
+				}
+			}
+			']' | '}' => {
+				let trimmed = json.trim_end().len();
+				if json[..trimmed].ends_with(',') {
+					json.remove(trimmed - 1);
+				}
+				json.push(c);
+			}
+			_ => json.push(c),
+		}
+	}
+	json
+}
+
+/// A request from a worker for dprint to format embedded code, and where to send dprint's answer.
+type HostRequest = (
+	HostFormatRequest,
+	mpsc::Sender<dprint_core::plugins::FormatResult>,
+);
+
+/// Asks dprint to format CSS, HTML and SQL in tagged templates, with the indentation of the file.
+///
+/// The formatter runs on a worker thread, so the requests are passed to the plugin's task to send.
+fn host_formatter(
+	requests: async_mpsc::UnboundedSender<HostRequest>,
+	token: Arc<dyn CancellationToken>,
+) -> Arc<ExternalFormatter> {
+	Arc::new(
+		move |language: &str, text: String, config: &Configuration| -> Result<Option<String>> {
+			if !EMBEDDED_LANGUAGES.contains(&language) {
+				return Ok(None);
+			}
+
+			let mut override_config = ConfigKeyMap::new();
+			override_config.insert(
+				"indentWidth".to_string(),
+				ConfigKeyValue::from_i32(config.indent_width.into()),
+			);
+			override_config.insert(
+				"useTabs".to_string(),
+				ConfigKeyValue::from_bool(config.use_tabs),
+			);
+			let request = HostFormatRequest {
+				file_path: PathBuf::from(format!("file.{}", language)),
+				file_bytes: text.into_bytes(),
+				range: None,
+				override_config,
+				token: token.clone(),
+			};
+
+			let (sender, receiver) = mpsc::channel();
+			let answer = requests
+				.send((request, sender))
+				.ok()
+				.and_then(|()| receiver.recv().ok());
+			let Some(answer) = answer else {
+				bail!("dprint stopped before formatting the embedded {}", language);
+			};
+			Ok(answer?.map(|text| String::from_utf8_lossy(&text).into_owned()))
+		},
+	)
+}
+
+/// Answers dprint's requests with [`Formatter`].
+pub struct PluginHandler;
+
+#[async_trait(?Send)]
+impl AsyncPluginHandler for PluginHandler {
+	type Configuration = PluginConfiguration;
+
+	fn plugin_info(&self) -> PluginInfo {
+		PluginInfo {
+			name: env!("CARGO_PKG_NAME").to_string(),
+			version: env!("CARGO_PKG_VERSION").to_string(),
+			config_key: CONFIG_KEY.to_string(),
+			help_url: env!("CARGO_PKG_HOMEPAGE").to_string(),
+			config_schema_url: String::new(),
+			update_url: None,
+		}
+	}
+
+	fn license_text(&self) -> String {
+		include_str!("../LICENSE").to_string()
+	}
+
+	async fn resolve_config(
+		&self,
+		config: ConfigKeyMap,
+		global_config: GlobalConfiguration,
+	) -> PluginResolveConfigurationResult<PluginConfiguration> {
+		// dprint sends the global options which are not set as nulls.
+		let to_object = |value: serde_json::Result<Value>| match value {
+			Ok(Value::Object(object)) => object
+				.into_iter()
+				.filter(|(_, value)| !value.is_null())
+				.collect(),
+			_ => Map::new(),
+		};
+		resolve_config(
+			&to_object(serde_json::to_value(global_config)),
+			&to_object(serde_json::to_value(config)),
+		)
+	}
+
+	/// Formats the file on a worker thread, sending its requests to format embedded code on to dprint.
+	async fn format(
+		&self,
+		request: FormatRequest<PluginConfiguration>,
+		mut format_with_host: impl FnMut(
+			HostFormatRequest,
+		)
+			-> LocalBoxFuture<'static, dprint_core::plugins::FormatResult>
+		+ 'static,
+	) -> dprint_core::plugins::FormatResult {
+		// dprint decodes files lossily, so its plugins do too.
+		let text = String::from_utf8_lossy(&request.file_bytes).into_owned();
+		let range = request.range.clone();
+		let (host_requests, mut host_receiver) = async_mpsc::unbounded_channel();
+		let mut formatter = request.config.formatter.clone();
+		formatter.external_formatter = Some(host_formatter(host_requests, request.token.clone()));
+
+		let cancelled = Arc::new(AtomicBool::new(false));
+		let formatting = {
+			let formatter = Arc::new(formatter);
+			let path = request.file_path.clone();
+			let timeout = request.config.timeout;
+			let cancelled = cancelled.clone();
+			tokio::task::spawn_blocking(move || match range {
+				Some(range) => format_range(formatter, path, None, text, range, timeout, cancelled),
+				None => {
+					format_on_worker_cancellable(formatter, path, None, text, timeout, cancelled)
+				}
+			})
+		};
+		tokio::pin!(formatting);
+
+		let result = loop {
+			tokio::select! {
+				result = &mut formatting => break result??,
+				Some((host_request, sender)) = host_receiver.recv() => {
+					let _ = sender.send(format_with_host(host_request).await);
+				}
+				() = request.token.wait_cancellation() => {
+					// dprint does not expect an answer to a request it cancelled.
+					cancelled.store(true, Ordering::Relaxed);
+					return Ok(None);
+				}
+			}
+		};
+		Ok(result.changed.then(|| result.text.into_bytes()))
+	}
+}
+
+/// Serves dprint's requests over stdin and stdout until dprint asks the plugin to close and closes stdin.
+///
+/// The plugin also exits if the process given by `--parent-pid` stops.
+pub fn serve() -> Result<()> {
+	let runtime = tokio::runtime::Builder::new_current_thread()
+		.enable_time()
+		.build()
+		.context("Could not start the plugin's runtime")?;
+	// Dropping the runtime afterwards waits for the answers to be written, and for dprint to close stdin.
+	runtime.block_on(async {
+		if let Some(parent_process_id) = get_parent_process_id_from_cli_args() {
+			start_parent_process_checker_task(parent_process_id);
+		}
+		handle_process_stdio_messages(PluginHandler).await
+	})
+}
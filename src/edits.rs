@@ -0,0 +1,238 @@
+//! The smallest edits which turn the original text into the formatted text.
+//!
+//! Editors which apply these edits instead of replacing the whole buffer keep their undo history,
+//! folds and markers in the unchanged parts of the file.
+
+use crate::mapping::OffsetEncoding;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+use serde::Serialize;
+use similar::{Algorithm, DiffOp, DiffTag, TextDiff, capture_diff_slices_deadline};
+use std::ops;
+use std::time::{Duration, Instant};
+
+/// Diffing gives up on finding the smallest diff after this long, which still gives correct edits.
+const DIFF_DEADLINE: Duration = Duration::from_secs(1);
+
+#[cfg_attr(
+	feature = "python",
+	pyclass(name = "Position", module = "_formate_js", frozen, get_all)
+)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+/// A position in a text, as in the Language Server Protocol.
+pub struct Position {
+	/// The 0-based line.
+	pub line: usize,
+	/// The 0-based offset from the start of the line, in the requested encoding.
+	pub character: usize,
+}
+
+#[cfg_attr(
+	feature = "python",
+	pyclass(name = "Range", module = "_formate_js", frozen, get_all)
+)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+/// A range of a text, as in the Language Server Protocol.
+pub struct Range {
+	pub start: Position,
+	pub end: Position,
+}
+
+#[cfg_attr(
+	feature = "python",
+	pyclass(name = "TextEdit", module = "_formate_js", frozen, get_all)
+)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+/// Replaces part of the original text. Serializes to a Language Server Protocol ``TextEdit``.
+pub struct TextEdit {
+	/// The offset of the start of the replaced text, in the requested encoding.
+	#[serde(skip)]
+	pub start: usize,
+	/// The offset of the end of the replaced text, in the requested encoding.
+	#[serde(skip)]
+	pub end: usize,
+	/// The replaced text, as lines and characters.
+	pub range: Range,
+	/// The text to replace it with.
+	pub new_text: String,
+}
+
+/// Returns the edits which turn `original` into `formatted`, in order and without overlapping.
+///
+/// Changed lines are found first, and then the changed characters within them.
+pub fn text_edits(original: &str, formatted: &str, encoding: OffsetEncoding) -> Vec<TextEdit> {
+	let deadline = Instant::now() + DIFF_DEADLINE;
+	let line_diff = TextDiff::configure()
+		.algorithm(Algorithm::Myers)
+		.deadline(deadline)
+		.diff_lines(original, formatted);
+	let old_starts = line_starts(line_diff.old_slices());
+	let new_starts = line_starts(line_diff.new_slices());
+
+	let mut edits = Vec::new();
+	let mut locator = Locator::new(original, encoding);
+	for (old_lines, new_lines) in changed_runs(line_diff.ops()) {
+		let old_start = old_starts[old_lines.start];
+		let old_text = &original[old_start..old_starts[old_lines.end]];
+		let new_text = &formatted[new_starts[new_lines.start]..new_starts[new_lines.end]];
+
+		for (start, end, replacement) in char_edits(old_text, new_text, deadline) {
+			let (start, start_position) = locator.locate(old_start + start);
+			let (end, end_position) = locator.locate(old_start + end);
+			edits.push(TextEdit {
+				start,
+				end,
+				range: Range {
+					start: start_position,
+					end: end_position,
+				},
+				new_text: replacement,
+			});
+		}
+	}
+	edits
+}
+
+/// The byte offset of the start of each line, and of the end of the text.
+fn line_starts(lines: &[&str]) -> Vec<usize> {
+	std::iter::once(0)
+		.chain(lines.iter().scan(0, |offset, line| {
+			*offset += line.len();
+			Some(*offset)
+		}))
+		.collect()
+}
+
+/// The changed characters between two blocks of lines, as byte ranges of `old` and their replacements.
+fn char_edits(old: &str, new: &str, deadline: Instant) -> Vec<(usize, usize, String)> {
+	let (old_offsets, old_chars): (Vec<_>, Vec<_>) = old.char_indices().unzip();
+	let new_chars = new.chars().collect::<Vec<_>>();
+	let byte_offset = |index: usize| old_offsets.get(index).copied().unwrap_or(old.len());
+
+	let ops =
+		capture_diff_slices_deadline(Algorithm::Myers, &old_chars, &new_chars, Some(deadline));
+	let edits = changed_runs(&ops)
+		.into_iter()
+		.map(|(old_range, new_range)| {
+			let replacement = new_chars[new_range].iter().collect::<String>();
+			(
+				byte_offset(old_range.start),
+				byte_offset(old_range.end),
+				replacement,
+			)
+		});
+
+	// Editors treat CRLF as a single line break, so an edit must not start or end inside one.
+	// The carriage return or line feed taken in is unchanged text, so widening can only make
+	// an edit touch the next one, which are then merged.
+	let mut widened = Vec::with_capacity(edits.len());
+	for (mut start, mut end, mut replacement) in edits {
+		if splits_crlf(old, start) {
+			start -= 1;
+			replacement.insert(0, '\r');
+		}
+		if splits_crlf(old, end) {
+			end += 1;
+			replacement.push('\n');
+		}
+		push_edit(&mut widened, (start, end, replacement));
+	}
+	assert!(
+		widened.iter().all(|(start, end, _)| start <= end)
+			&& widened.windows(2).all(|pair| pair[0].1 < pair[1].0),
+		"character edits overlap"
+	);
+	widened
+}
+
+/// The changes between two diffed sequences, as ranges of the old and new items,
+/// with changes that have nothing unchanged between them merged.
+///
+/// The ranges are counted from the lengths of the operations, as `similar` does not always
+/// give a deleted or inserted run its position in the other sequence.
+fn changed_runs(ops: &[DiffOp]) -> Vec<(ops::Range<usize>, ops::Range<usize>)> {
+	let mut runs: Vec<(ops::Range<usize>, ops::Range<usize>)> = Vec::new();
+	let (mut old, mut new) = (0, 0);
+	for op in ops {
+		let (tag, old_range, new_range) = op.as_tag_tuple();
+		let (old_end, new_end) = (old + old_range.len(), new + new_range.len());
+		if tag != DiffTag::Equal {
+			match runs.last_mut() {
+				Some((old_run, new_run)) if old_run.end == old && new_run.end == new => {
+					old_run.end = old_end;
+					new_run.end = new_end;
+				}
+				_ => runs.push((old..old_end, new..new_end)),
+			}
+		}
+		(old, new) = (old_end, new_end);
+	}
+	runs
+}
+
+/// Appends an edit, merging it into the last one if they touch.
+fn push_edit(
+	edits: &mut Vec<(usize, usize, String)>,
+	(start, end, replacement): (usize, usize, String),
+) {
+	match edits.last_mut() {
+		Some(last) if last.1 == start => {
+			last.1 = end;
+			last.2.push_str(&replacement);
+		}
+		_ => edits.push((start, end, replacement)),
+	}
+}
+
+/// Whether a byte offset falls between the carriage return and line feed of a CRLF line break.
+fn splits_crlf(text: &str, byte_offset: usize) -> bool {
+	text[..byte_offset].ends_with('\r') && text[byte_offset..].starts_with('\n')
+}
+
+/// Finds the offsets and positions of increasing byte offsets in a text.
+///
+/// As in the Language Server Protocol, CRLF, CR and LF each end a line.
+struct Locator<'a> {
+	text: &'a str,
+	encoding: OffsetEncoding,
+	byte_offset: usize,
+	offset: usize,
+	position: Position,
+	after_cr: bool,
+}
+
+impl<'a> Locator<'a> {
+	fn new(text: &'a str, encoding: OffsetEncoding) -> Self {
+		Locator {
+			text,
+			encoding,
+			byte_offset: 0,
+			offset: 0,
+			position: Position {
+				line: 0,
+				character: 0,
+			},
+			after_cr: false,
+		}
+	}
+
+	fn locate(&mut self, byte_offset: usize) -> (usize, Position) {
+		for c in self.text[self.byte_offset..byte_offset].chars() {
+			let len = self.encoding.len(c);
+			self.offset += len;
+			match c {
+				// The line feed of a CRLF belongs to the line break the carriage return started.
+				'\n' if self.after_cr => {}
+				'\r' | '\n' => {
+					self.position.line += 1;
+					self.position.character = 0;
+				}
+				_ => self.position.character += len,
+			}
+			self.after_cr = c == '\r';
+		}
+		self.byte_offset = byte_offset;
+		(self.offset, self.position.clone())
+	}
+}
@@ -0,0 +1,264 @@
+//! Reading files to format, and writing them back to disk in the encoding they were read in.
+
+use crate::cache::Cache;
+use crate::format_result::FormatResult;
+use crate::formatter::Formatter;
+use crate::parse;
+use crate::worker::format_on_worker;
+use anyhow::{Context, Result};
+use log::{debug, warn};
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+/// How the text of a file is encoded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Encoding {
+	/// UTF-8 without a byte order mark.
+	Utf8,
+	/// UTF-8 with a byte order mark.
+	Utf8Bom,
+	/// UTF-16 little-endian, with a byte order mark.
+	Utf16Le,
+	/// UTF-16 big-endian, with a byte order mark.
+	Utf16Be,
+}
+
+const UTF8_BOM: &[u8] = &[0xEF, 0xBB, 0xBF];
+
+/// The error when a file is neither UTF-8 nor UTF-16 with a byte order mark.
+#[derive(Debug)]
+pub struct DecodeError {
+	pub path: PathBuf,
+}
+
+impl fmt::Display for DecodeError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(
+			f,
+			"'{}' is not valid UTF-8, or UTF-16 with a byte order mark",
+			self.path.display()
+		)
+	}
+}
+
+impl std::error::Error for DecodeError {}
+
+/// Options for formatting a file on disk.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FileOptions {
+	/// Whether to write the formatted text back to the file. If not, nothing is changed.
+	pub write: bool,
+	/// If set, the original file is copied to its path with this suffix appended before it is reformatted.
+	pub backup_suffix: Option<String>,
+	/// The maximum time to spend formatting the file.
+	pub timeout: Option<Duration>,
+	/// Records which file contents are already formatted, so they are not parsed again.
+	pub cache: Option<Arc<Cache>>,
+}
+
+fn decode_utf16(bytes: &[u8], from_bytes: fn([u8; 2]) -> u16) -> Option<String> {
+	if !bytes.len().is_multiple_of(2) {
+		return None;
+	}
+	let units = bytes
+		.chunks_exact(2)
+		.map(|pair| from_bytes([pair[0], pair[1]]));
+	char::decode_utf16(units)
+		.collect::<Result<String, _>>()
+		.ok()
+}
+
+/// Decodes the contents of a file, detecting UTF-16 and byte order marks. The byte order mark is not
+/// included in the text.
+pub fn decode(bytes: &[u8]) -> Option<(String, Encoding)> {
+	match bytes {
+		[0xFF, 0xFE, rest @ ..] => {
+			decode_utf16(rest, u16::from_le_bytes).map(|text| (text, Encoding::Utf16Le))
+		}
+		[0xFE, 0xFF, rest @ ..] => {
+			decode_utf16(rest, u16::from_be_bytes).map(|text| (text, Encoding::Utf16Be))
+		}
+		_ => match bytes.strip_prefix(UTF8_BOM) {
+			Some(rest) => String::from_utf8(rest.to_vec())
+				.ok()
+				.map(|text| (text, Encoding::Utf8Bom)),
+			None => String::from_utf8(bytes.to_vec())
+				.ok()
+				.map(|text| (text, Encoding::Utf8)),
+		},
+	}
+}
+
+/// Encodes text in the encoding it was read in, adding back its byte order mark.
+pub fn encode(text: &str, encoding: Encoding) -> Vec<u8> {
+	let text = text.trim_start_matches('\u{feff}');
+	match encoding {
+		Encoding::Utf8 => text.as_bytes().to_vec(),
+		Encoding::Utf8Bom => [UTF8_BOM, text.as_bytes()].concat(),
+		Encoding::Utf16Le => "\u{feff}"
+			.encode_utf16()
+			.chain(text.encode_utf16())
+			.flat_map(u16::to_le_bytes)
+			.collect(),
+		Encoding::Utf16Be => "\u{feff}"
+			.encode_utf16()
+			.chain(text.encode_utf16())
+			.flat_map(u16::to_be_bytes)
+			.collect(),
+	}
+}
+
+/// Reads a file as text, returning the encoding it was in.
+pub fn read_text(path: &Path) -> Result<(String, Encoding)> {
+	decode_file(path, &read_file(path)?)
+}
+
+fn read_file(path: &Path) -> Result<Vec<u8>> {
+	fs::read(path).with_context(|| format!("Could not read '{}'", path.display()))
+}
+
+fn decode_file(path: &Path, bytes: &[u8]) -> Result<(String, Encoding)> {
+	decode(bytes).ok_or_else(|| {
+		DecodeError {
+			path: path.to_path_buf(),
+		}
+		.into()
+	})
+}
+
+/// Writes text to an existing file atomically, in the given encoding.
+///
+/// If `backup_suffix` is set, the original file is first copied to its path with the suffix appended.
+pub fn write_text(
+	path: &Path,
+	text: &str,
+	encoding: Encoding,
+	backup_suffix: Option<&str>,
+) -> Result<()> {
+	if let Some(suffix) = backup_suffix {
+		let mut backup = path.as_os_str().to_owned();
+		backup.push(suffix);
+		fs::copy(path, &backup)
+			.with_context(|| format!("Could not back up '{}'", path.display()))?;
+	}
+	write_atomically(path, &encode(text, encoding))
+		.with_context(|| format!("Could not write '{}'", path.display()))
+}
+
+/// Distinguishes the temporary files written by different threads.
+static TEMPORARY_FILES: AtomicUsize = AtomicUsize::new(0);
+
+/// A path next to `path` to write its new contents to before renaming them over it.
+fn temporary_path(path: &Path) -> PathBuf {
+	let name = path
+		.file_name()
+		.map(|name| name.to_string_lossy().into_owned())
+		.unwrap_or_default();
+	path.with_file_name(format!(
+		".{}.formate-js-{}-{}.tmp",
+		name,
+		std::process::id(),
+		TEMPORARY_FILES.fetch_add(1, Ordering::Relaxed)
+	))
+}
+
+/// Replaces the contents of an existing file by writing them to a temporary file next to it and renaming
+/// that over it, so the file is never left half written. The file's permissions are kept, and a symbolic
+/// link is written through rather than replaced.
+pub fn write_atomically(path: &Path, contents: &[u8]) -> io::Result<()> {
+	let path = fs::canonicalize(path)?;
+	let permissions = fs::metadata(&path)?.permissions();
+	let temporary = temporary_path(&path);
+
+	let result = (|| {
+		let mut file = File::create(&temporary)?;
+		file.write_all(contents)?;
+		file.sync_all()?;
+		fs::set_permissions(&temporary, permissions)?;
+		fs::rename(&temporary, &path)
+	})();
+	if result.is_err() {
+		let _ = fs::remove_file(&temporary);
+	}
+	result
+}
+
+/// Formats a file on disk with `format`, writing it back if formatting changed it.
+///
+/// Contents found in the cache are returned unchanged without calling `format`, and contents which are
+/// formatted, either already or once written, are added to it.
+pub(crate) fn format_file_with(
+	path: &Path,
+	options: &FileOptions,
+	format: impl FnOnce(String) -> Result<FormatResult>,
+) -> Result<FormatResult> {
+	let bytes = read_file(path)?;
+	let (text, encoding) = decode_file(path, &bytes)?;
+	let cache = options.cache.as_deref();
+	if cache.is_some_and(|cache| cache.contains(path, &bytes)) {
+		debug!(
+			"'{}' is already formatted according to the cache",
+			path.display()
+		);
+		let syntax = parse::syntax_name(parse::media_type(path, None));
+		return Ok(FormatResult::new(
+			text,
+			None,
+			syntax,
+			Duration::ZERO,
+			Duration::ZERO,
+			&[],
+		));
+	}
+
+	let result = format(text)?;
+	// The cache only saves time, so failing to update it is not an error.
+	let record = |cache: &Cache, contents: &[u8]| {
+		if let Err(error) = cache.insert(path, contents) {
+			warn!(
+				"Could not update the cache in '{}': {}",
+				cache.directory().display(),
+				error
+			);
+		}
+	};
+	if let Some(cache) = cache
+		&& !result.changed
+		&& result.skipped.is_none()
+	{
+		record(cache, &bytes);
+	}
+	if options.write && result.changed {
+		write_text(
+			path,
+			&result.text,
+			encoding,
+			options.backup_suffix.as_deref(),
+		)?;
+		debug!("Wrote '{}'", path.display());
+		if let Some(cache) = cache {
+			record(cache, &encode(&result.text, encoding));
+		}
+	}
+	Ok(result)
+}
+
+/// Formats a file on disk on a worker thread, writing it back if formatting changed it.
+///
+/// The file keeps its encoding, byte order mark and permissions, and its line endings follow the
+/// configuration's `new_line_kind`. An unchanged file is not written,
+/// so its modification time is left alone.
+pub fn format_file(
+	formatter: Arc<Formatter>,
+	path: &Path,
+	options: &FileOptions,
+) -> Result<FormatResult> {
+	format_file_with(path, options, |text| {
+		format_on_worker(formatter, path.to_path_buf(), None, text, options.timeout)
+	})
+}
@@ -0,0 +1,108 @@
+use crate::ignore::IgnoreRegion;
+use crate::skip::SkipReason;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+use similar::{ChangeTag, TextDiff};
+use std::time::Duration;
+
+#[cfg_attr(
+	feature = "python",
+	pyclass(name = "FormatResult", module = "_formate_js", frozen, get_all)
+)]
+#[derive(Clone, Debug)]
+/// The result of formatting a file.
+pub struct FormatResult {
+	/// The formatted text, or the original text if formatting made no changes.
+	pub text: String,
+	/// Whether formatting changed the text.
+	pub changed: bool,
+	/// The length of the original text, in characters.
+	pub original_len: usize,
+	/// The length of the formatted text, in characters.
+	pub formatted_len: usize,
+	/// The number of lines added plus the number of lines removed by formatting.
+	pub changed_lines: usize,
+	/// The time taken to parse the file, in seconds.
+	pub parse_duration: f64,
+	/// The time taken to generate and print the formatted text, in seconds.
+	pub print_duration: f64,
+	/// The syntax the file was parsed with (``typescript``, ``tsx``, ``javascript`` or ``jsx``).
+	pub syntax: String,
+	/// The regions kept as-is because of range ignore comments, as 1-based inclusive ``(start_line, end_line)`` pairs.
+	pub ignore_regions: Vec<(usize, usize)>,
+	/// Why the file was left as-is without being parsed (``"minified"`` or ``"oversized"``), if it was skipped.
+	pub skipped: Option<String>,
+	/// The requested offsets in the original text mapped to the formatted text, if any were requested.
+	pub mapped_offsets: Option<Vec<usize>>,
+	/// A version 3 source map from the formatted text to the original text, as JSON, if one was requested.
+	pub source_map: Option<String>,
+}
+
+impl FormatResult {
+	pub(crate) fn new(
+		original: String,
+		formatted: Option<String>,
+		syntax: &str,
+		parse_duration: Duration,
+		print_duration: Duration,
+		ignore_regions: &[IgnoreRegion],
+	) -> Self {
+		let original_len = original.chars().count();
+		let (text, changed, changed_lines) = match formatted {
+			Some(formatted) => {
+				let changed_lines = count_changed_lines(&original, &formatted);
+				(formatted, true, changed_lines)
+			}
+			None => (original, false, 0),
+		};
+
+		FormatResult {
+			formatted_len: if changed {
+				text.chars().count()
+			} else {
+				original_len
+			},
+			text,
+			changed,
+			original_len,
+			changed_lines,
+			parse_duration: parse_duration.as_secs_f64(),
+			print_duration: print_duration.as_secs_f64(),
+			syntax: syntax.to_string(),
+			ignore_regions: ignore_regions
+				.iter()
+				.map(|region| (region.start_line, region.end_line))
+				.collect(),
+			skipped: None,
+			mapped_offsets: None,
+			source_map: None,
+		}
+	}
+
+	/// Replaces the formatted text, such as with only some of the formatting applied to `original`.
+	pub(crate) fn with_text(self, original: &str, text: String) -> Self {
+		let changed = text != original;
+		FormatResult {
+			formatted_len: text.chars().count(),
+			changed_lines: count_changed_lines(original, &text),
+			text,
+			changed,
+			..self
+		}
+	}
+
+	/// The result for a file which was skipped before parsing.
+	pub(crate) fn skipped(original: String, syntax: &str, reason: SkipReason) -> Self {
+		FormatResult {
+			skipped: Some(reason.as_str().to_string()),
+			..FormatResult::new(original, None, syntax, Duration::ZERO, Duration::ZERO, &[])
+		}
+	}
+}
+
+fn count_changed_lines(original: &str, formatted: &str) -> usize {
+	TextDiff::from_lines(original, formatted)
+		.iter_all_changes()
+		.filter(|change| change.tag() != ChangeTag::Equal)
+		.count()
+}
@@ -0,0 +1,129 @@
+use crate::configuration::ConfigurationOptions;
+use anyhow::{Context, Result, bail};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use toml::{Table, Value};
+
+/// The name the formate hook is registered under.
+pub const HOOK_NAME: &str = "format_js";
+
+/// The options for the formate hook, as read from a formate configuration file.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct HookConfiguration {
+	pub options: ConfigurationOptions,
+	/// The maximum time to spend formatting a file, in seconds.
+	pub timeout: Option<f64>,
+}
+
+/// Reads the hook configuration from a `formate.toml` or `pyproject.toml` file.
+///
+/// Returns `None` if the hook is not enabled in the file.
+pub fn load_formate_toml(path: &Path) -> Result<Option<HookConfiguration>> {
+	let text = std::fs::read_to_string(path)
+		.with_context(|| format!("Could not read '{}'", path.display()))?;
+	parse_formate_toml(&text)
+		.with_context(|| format!("Invalid configuration in '{}'", path.display()))
+}
+
+/// Finds the formate configuration file which applies to files in `directory`.
+///
+/// This is the nearest `formate.toml`, or `pyproject.toml` with a `[tool.formate]` table,
+/// in `directory` or one of its parents.
+pub fn find_formate_toml(directory: &Path) -> Option<PathBuf> {
+	directory.ancestors().find_map(formate_toml_in)
+}
+
+/// Returns the formate configuration file in `directory` itself, if there is one.
+pub(crate) fn formate_toml_in(directory: &Path) -> Option<PathBuf> {
+	let formate_toml = directory.join("formate.toml");
+	if formate_toml.is_file() {
+		return Some(formate_toml);
+	}
+	let pyproject_toml = directory.join("pyproject.toml");
+	let text = std::fs::read_to_string(&pyproject_toml).ok()?;
+	let pyproject: Table = text.parse().ok()?;
+	pyproject
+		.get("tool")
+		.and_then(|tool| tool.get("formate"))
+		.map(|_| pyproject_toml)
+}
+
+/// Parses the hook configuration from the contents of a `formate.toml` or `pyproject.toml` file.
+///
+/// Returns `None` if the hook is not enabled in the file.
+pub fn parse_formate_toml(text: &str) -> Result<Option<HookConfiguration>> {
+	let mut config: Table = text.parse()?;
+	if let Some(Value::Table(tool)) = config.get_mut("tool") {
+		match tool.remove("formate") {
+			Some(Value::Table(formate)) => config = formate,
+			Some(_) => bail!("'tool.formate' must be a table"),
+			None => {}
+		}
+	}
+
+	let kwargs = match config.get("hooks").and_then(|hooks| hooks.get(HOOK_NAME)) {
+		None => return Ok(None),
+		Some(Value::Integer(_)) => Table::new(),
+		Some(Value::Table(hook)) => match hook.get("kwargs") {
+			None => Table::new(),
+			Some(Value::Table(kwargs)) => kwargs.clone(),
+			Some(_) => bail!("'hooks.{}.kwargs' must be a table", HOOK_NAME),
+		},
+		Some(_) => bail!("'hooks.{}' must be a priority or a table", HOOK_NAME),
+	};
+
+	let global_config = match config.get("config") {
+		None => Table::new(),
+		Some(Value::Table(global_config)) => global_config.clone(),
+		Some(_) => bail!("'config' must be a table"),
+	};
+
+	from_kwargs(kwargs, &global_config).map(Some)
+}
+
+/// Resolves the hook's keyword arguments the same way the python hook does,
+/// taking `line_length` and `indent` from formate's global configuration unless overridden.
+pub fn from_kwargs(mut kwargs: Table, global_config: &Table) -> Result<HookConfiguration> {
+	if !kwargs.contains_key("line_width")
+		&& let Some(line_length) = global_config.get("line_length")
+	{
+		kwargs.insert("line_width".to_string(), line_length.clone());
+	}
+
+	if !kwargs.contains_key("use_tabs")
+		&& let Some(Value::String(indent)) = global_config.get("indent")
+	{
+		if indent == "\t" {
+			if !kwargs.contains_key("indent_width") {
+				kwargs.insert("use_tabs".to_string(), Value::Boolean(true));
+				kwargs.insert("indent_width".to_string(), Value::Integer(4));
+			}
+		} else {
+			kwargs.insert("use_tabs".to_string(), Value::Boolean(false));
+			if !indent.is_empty() && indent.chars().all(|c| c == ' ') {
+				kwargs
+					.entry("indent_width")
+					.or_insert(Value::Integer(indent.len() as i64));
+			}
+		}
+	}
+
+	let timeout = match kwargs.remove("timeout") {
+		None => None,
+		Some(Value::Float(timeout)) => Some(timeout),
+		Some(Value::Integer(timeout)) => Some(timeout as f64),
+		Some(_) => bail!("'timeout' must be a number"),
+	};
+	if let Some(timeout) = timeout
+		&& Duration::try_from_secs_f64(timeout).is_err()
+	{
+		bail!(
+			"'timeout' must be a non-negative number of seconds, not {}",
+			timeout
+		);
+	}
+
+	let options: ConfigurationOptions = Value::Table(kwargs).try_into()?;
+	options.resolve()?;
+	Ok(HookConfiguration { options, timeout })
+}
@@ -0,0 +1,398 @@
+use crate::configuration::ConfigurationOptions;
+use crate::edits::{TextEdit, text_edits};
+use crate::format_result::FormatResult;
+use crate::ignore::{IgnoreOptions, has_file_ignore_comment, prepare};
+use crate::mapping::OffsetEncoding;
+use crate::parse;
+use crate::skip::{SkipOptions, skip_reason};
+use crate::worker::{FormatCancelledError, map_on_workers};
+use anyhow::Context;
+use dprint_plugin_typescript::configuration::Configuration;
+use dprint_plugin_typescript::format_parsed_source;
+use log::debug;
+use similar::TextDiff;
+use std::any::Any;
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+/// Formats code embedded in tagged templates, such as ``css`...` ``, given the tag and the code.
+///
+/// Returns `None` to leave the template as it is.
+pub type ExternalFormatter =
+	dyn Fn(&str, String, &Configuration) -> anyhow::Result<Option<String>> + Send + Sync;
+
+/// Formats files using a configuration which has already been resolved.
+#[derive(Clone)]
+pub struct Formatter {
+	pub config: Configuration,
+	pub ignore_options: IgnoreOptions,
+	pub skip_options: SkipOptions,
+	/// Files with syntax trees deeper than this are refused rather than risking a stack overflow.
+	pub max_nesting_depth: Option<usize>,
+	/// The stack size of the threads files are formatted on, in bytes.
+	pub worker_stack_size: usize,
+	/// Format the output a second time, returning [`NonIdempotentFormattingError`] if it changes again.
+	pub verify_idempotent: bool,
+	/// Formats code embedded in tagged templates. Templates are left as they are without one.
+	pub external_formatter: Option<Arc<ExternalFormatter>>,
+}
+
+/// Returned when a file is nested too deeply to be formatted safely.
+#[derive(Debug)]
+pub struct NestingTooDeepError {
+	pub path: PathBuf,
+	pub max_nesting_depth: usize,
+}
+
+impl fmt::Display for NestingTooDeepError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(
+			f,
+			"'{}' is nested more than {} levels deep, which is too deep to format",
+			self.path.display(),
+			self.max_nesting_depth
+		)
+	}
+}
+
+impl std::error::Error for NestingTooDeepError {}
+
+/// Returned when formatting the formatted text a second time changes it again.
+#[derive(Debug)]
+pub struct NonIdempotentFormattingError {
+	pub path: PathBuf,
+	/// A unified diff from the output of the first pass to the output of the second.
+	pub diff: String,
+}
+
+impl fmt::Display for NonIdempotentFormattingError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(
+			f,
+			"Formatting '{}' is not idempotent; a second pass changed the output:\n{}",
+			self.path.display(),
+			self.diff.trim_end()
+		)
+	}
+}
+
+impl std::error::Error for NonIdempotentFormattingError {}
+
+/// Returned when the parser or dprint panicked while formatting a file.
+#[derive(Debug)]
+pub struct FormatPanicError {
+	pub path: PathBuf,
+	/// The panic message.
+	pub message: String,
+	/// The start of the file, to help reproduce the crash.
+	pub snippet: String,
+}
+
+impl FormatPanicError {
+	/// A report of the crash which can be pasted into a bug report.
+	pub fn crash_report(&self) -> String {
+		format!(
+			"formate-js {} panicked: {}\n\n--- start of '{}' ---\n{}",
+			env!("CARGO_PKG_VERSION"),
+			self.message,
+			self.path.display(),
+			self.snippet
+		)
+	}
+}
+
+impl fmt::Display for FormatPanicError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(
+			f,
+			"The formatter crashed while formatting '{}': {}",
+			self.path.display(),
+			self.message
+		)
+	}
+}
+
+impl std::error::Error for FormatPanicError {}
+
+/// The first few lines of the text, for crash reports.
+fn crash_snippet(text: &str) -> String {
+	const MAX_LINES: usize = 10;
+	const MAX_CHARS: usize = 500;
+
+	let mut snippet = text.lines().take(MAX_LINES).collect::<Vec<_>>().join("\n");
+	if let Some((index, _)) = snippet.char_indices().nth(MAX_CHARS) {
+		snippet.truncate(index);
+	}
+	snippet
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+	if let Some(message) = payload.downcast_ref::<&str>() {
+		message.to_string()
+	} else if let Some(message) = payload.downcast_ref::<String>() {
+		message.clone()
+	} else {
+		"unknown panic".to_string()
+	}
+}
+
+/// Runs `job`, returning a panic in it as a [`FormatPanicError`] for the file at `path`.
+fn catch_panic<T>(
+	path: &Path,
+	snippet: String,
+	job: impl FnOnce() -> anyhow::Result<T>,
+) -> anyhow::Result<T> {
+	panic::catch_unwind(AssertUnwindSafe(job)).unwrap_or_else(|payload| {
+		Err(FormatPanicError {
+			path: path.to_path_buf(),
+			message: panic_message(&*payload),
+			snippet,
+		}
+		.into())
+	})
+}
+
+impl Formatter {
+	/// Creates a formatter, returning an error if any of the options has an invalid value.
+	pub fn new(options: &ConfigurationOptions) -> anyhow::Result<Self> {
+		Ok(Formatter {
+			config: options.resolve()?,
+			ignore_options: IgnoreOptions::from(options),
+			skip_options: SkipOptions::from(options),
+			max_nesting_depth: options.max_nesting_depth,
+			worker_stack_size: options.worker_stack_size,
+			verify_idempotent: options.verify_idempotent,
+			external_formatter: None,
+		})
+	}
+
+	/// Formats a file, honouring the formate-specific ignore comments before handing over to dprint.
+	pub fn format(
+		&self,
+		path: &Path,
+		extension: Option<&str>,
+		text: String,
+	) -> anyhow::Result<FormatResult> {
+		self.format_cancellable(path, extension, text, &AtomicBool::new(false))
+	}
+
+	/// Like [`Formatter::format`], but gives up with [`FormatCancelledError`] if `cancelled` is set.
+	///
+	/// The flag is checked between parsing and printing, as neither step can be interrupted.
+	/// A panic while formatting is caught and returned as a [`FormatPanicError`].
+	pub fn format_cancellable(
+		&self,
+		path: &Path,
+		extension: Option<&str>,
+		text: String,
+		cancelled: &AtomicBool,
+	) -> anyhow::Result<FormatResult> {
+		let snippet = crash_snippet(&text);
+		catch_panic(path, snippet, || {
+			let result = self.format_inner(path, extension, text, cancelled)?;
+			if self.verify_idempotent && result.changed {
+				self.verify_idempotent(path, extension, &result.text, cancelled)?;
+			}
+			Ok(result)
+		})
+	}
+
+	/// Like [`Formatter::format_cancellable`], but returns the edits which turn `text` into the formatted
+	/// text, counting offsets in `encoding`. There are none if the file is already formatted.
+	///
+	/// A panic while finding the edits is returned as a [`FormatPanicError`] too.
+	pub fn format_edits_cancellable(
+		&self,
+		path: &Path,
+		extension: Option<&str>,
+		text: String,
+		encoding: OffsetEncoding,
+		cancelled: &AtomicBool,
+	) -> anyhow::Result<Vec<TextEdit>> {
+		let snippet = crash_snippet(&text);
+		let original = text.clone();
+		let result = self.format_cancellable(path, extension, text, cancelled)?;
+		if !result.changed {
+			return Ok(Vec::new());
+		}
+		catch_panic(path, snippet, || {
+			Ok(text_edits(&original, &result.text, encoding))
+		})
+	}
+
+	/// Formats the output of the first pass again, and returns an error if that changes it.
+	fn verify_idempotent(
+		&self,
+		path: &Path,
+		extension: Option<&str>,
+		first_pass: &str,
+		cancelled: &AtomicBool,
+	) -> anyhow::Result<()> {
+		let second_pass = self
+			.format_inner(path, extension, first_pass.to_string(), cancelled)
+			.with_context(|| {
+				format!(
+					"Could not format the output of formatting '{}' a second time",
+					path.display()
+				)
+			})?;
+		if !second_pass.changed {
+			return Ok(());
+		}
+
+		let diff = TextDiff::from_lines(first_pass, &second_pass.text)
+			.unified_diff()
+			.header("first pass", "second pass")
+			.to_string();
+		Err(NonIdempotentFormattingError {
+			path: path.to_path_buf(),
+			diff,
+		}
+		.into())
+	}
+
+	fn format_inner(
+		&self,
+		path: &Path,
+		extension: Option<&str>,
+		text: String,
+		cancelled: &AtomicBool,
+	) -> anyhow::Result<FormatResult> {
+		let check_cancelled = || {
+			if cancelled.load(Ordering::Relaxed) {
+				Err(FormatCancelledError)
+			} else {
+				Ok(())
+			}
+		};
+
+		let media_type = parse::media_type(path, extension);
+		if let Some(reason) = skip_reason(path, &text, &self.skip_options) {
+			debug!("Skipping '{}' as it is {}", path.display(), reason.as_str());
+			return Ok(FormatResult::skipped(
+				text,
+				parse::syntax_name(media_type),
+				reason,
+			));
+		}
+		if has_file_ignore_comment(&text, &self.ignore_options) {
+			debug!(
+				"Leaving '{}' as it is because of a file ignore comment",
+				path.display()
+			);
+			return Ok(FormatResult::new(
+				text,
+				None,
+				parse::syntax_name(media_type),
+				Duration::ZERO,
+				Duration::ZERO,
+				&[],
+			));
+		}
+
+		let too_deep = |max_nesting_depth| NestingTooDeepError {
+			path: path.to_path_buf(),
+			max_nesting_depth,
+		};
+		if let Some(max_nesting_depth) = self.max_nesting_depth
+			&& parse::brackets_deeper_than(&text, max_nesting_depth)
+		{
+			return Err(too_deep(max_nesting_depth).into());
+		}
+
+		let had_bom = text.starts_with(parse::BOM);
+		let source_text: Arc<str> = text.strip_prefix(parse::BOM).unwrap_or(&text).into();
+
+		check_cancelled()?;
+		let parse_start = Instant::now();
+		let mut parsed_source = parse::parse_source(path, extension, source_text)?;
+		if let Some(max_nesting_depth) = self.max_nesting_depth
+			&& parse::nesting_depth(&parsed_source, max_nesting_depth) > max_nesting_depth
+		{
+			return Err(too_deep(max_nesting_depth).into());
+		}
+
+		let prepared = prepare(&parsed_source, &self.ignore_options)?;
+		for region in &prepared.regions {
+			debug!(
+				"Leaving lines {}-{} of '{}' as they are because of an ignore comment",
+				region.start_line,
+				region.end_line,
+				path.display()
+			);
+		}
+		if prepared.is_modified() {
+			check_cancelled()?;
+			parsed_source = parse::parse_source(path, extension, prepared.text.as_str().into())?;
+		}
+		let parse_duration = parse_start.elapsed();
+		debug!(
+			"Parsed '{}' as {} in {:.3}ms",
+			path.display(),
+			parse::syntax_name(parsed_source.media_type()),
+			parse_duration.as_secs_f64() * 1000.0
+		);
+
+		check_cancelled()?;
+		let print_start = Instant::now();
+		let external_formatter = self
+			.external_formatter
+			.as_deref()
+			.map(|formatter| formatter as &dprint_plugin_typescript::ExternalFormatter);
+		let formatted = format_parsed_source(&parsed_source, &self.config, external_formatter)?;
+		let print_duration = print_start.elapsed();
+		debug!(
+			"Printed '{}' in {:.3}ms",
+			path.display(),
+			print_duration.as_secs_f64() * 1000.0
+		);
+
+		let formatted = match formatted {
+			Some(formatted) => Some(formatted),
+			None if had_bom => Some(parsed_source.text().to_string()),
+			None => None,
+		};
+		check_cancelled()?;
+		let formatted = match formatted {
+			Some(formatted) if prepared.is_modified() => Some(prepared.restore(formatted)?),
+			formatted => formatted,
+		};
+
+		Ok(FormatResult::new(
+			text.clone(),
+			formatted.filter(|formatted| *formatted != text),
+			parse::syntax_name(parsed_source.media_type()),
+			parse_duration,
+			print_duration,
+			&prepared.regions,
+		))
+	}
+
+	/// Returns whether the file is already formatted.
+	pub fn check(
+		&self,
+		path: &Path,
+		extension: Option<&str>,
+		text: String,
+	) -> anyhow::Result<bool> {
+		Ok(!self.format(path, extension, text)?.changed)
+	}
+
+	/// Formats several files in parallel, returning the results in the same order as `files`.
+	///
+	/// Files are formatted on threads with a stack of [`Formatter::worker_stack_size`] bytes.
+	pub fn format_batch(
+		&self,
+		files: Vec<(PathBuf, String)>,
+	) -> Vec<(PathBuf, anyhow::Result<FormatResult>)> {
+		let formatter = Arc::new(self.clone());
+		map_on_workers(self.worker_stack_size, files, move |(path, text)| {
+			let result = formatter.format(&path, None, text);
+			(path, result)
+		})
+	}
+}
@@ -0,0 +1,419 @@
+//! Formatting the files staged in git, or the files changed since a commit.
+//!
+//! This runs the local `git` command's plumbing, so it honours `GIT_INDEX_FILE` in hooks and never
+//! touches the network.
+
+use crate::format_result::FormatResult;
+use crate::formatter::Formatter;
+use crate::globs::glob_set;
+use crate::parse::is_formattable;
+use crate::worker::{FormatCancelledError, FormatTimeoutError, map_on_workers};
+use anyhow::{Context, Result, anyhow, bail};
+use std::ffi::OsStr;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+
+/// A file staged in the git index.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StagedFile {
+	/// The path relative to the top of the work tree.
+	pub path: PathBuf,
+	/// The file mode, such as `100644`.
+	pub mode: String,
+	/// The object ID of the staged content.
+	pub blob: String,
+}
+
+/// What happened to the work tree copy of a staged file which was reformatted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WorktreeUpdate {
+	/// The work tree was not updated.
+	Skipped,
+	/// The work tree matched the index, so the formatted content was written to it.
+	Written,
+	/// The formatting was merged with the unstaged changes in the work tree.
+	Merged,
+	/// The unstaged changes conflict with the formatting, so the work tree was left alone.
+	Conflict,
+}
+
+/// Options for formatting the files staged in the index.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StagedOptions {
+	/// Pathspecs limiting which staged files are formatted.
+	pub pathspecs: Vec<String>,
+	/// Glob patterns for files to skip, relative to the top of the work tree.
+	/// A pattern without a `/` matches a file or directory with that name anywhere.
+	pub excludes: Vec<String>,
+	/// Whether to write the formatted content to the index. If not, nothing is changed.
+	pub write: bool,
+	/// Whether to also write the formatted content to the work tree, keeping unstaged changes.
+	pub update_worktree: bool,
+	/// The maximum time to spend formatting the staged files. Those not formatted by then fail with
+	/// [`FormatTimeoutError`](crate::worker::FormatTimeoutError).
+	pub timeout: Option<Duration>,
+}
+
+/// The outcome of formatting a staged file.
+#[derive(Debug)]
+pub struct StagedResult {
+	pub file: StagedFile,
+	/// The staged content before formatting, which is empty if it could not be read.
+	pub original: String,
+	pub result: Result<FormatResult>,
+	pub worktree: WorktreeUpdate,
+}
+
+/// A git repository with a work tree.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Repository {
+	top_level: PathBuf,
+	git_dir: PathBuf,
+}
+
+fn run(command: &mut Command, stdin: Option<&[u8]>) -> Result<Vec<u8>> {
+	let mut child = command
+		.stdin(if stdin.is_some() {
+			Stdio::piped()
+		} else {
+			Stdio::null()
+		})
+		.stdout(Stdio::piped())
+		.stderr(Stdio::piped())
+		.spawn()
+		.context("Could not run git")?;
+	if let Some(stdin) = stdin {
+		child.stdin.take().unwrap().write_all(stdin)?;
+	}
+	let output = child.wait_with_output()?;
+	if !output.status.success() {
+		bail!(
+			"git failed: {}",
+			String::from_utf8_lossy(&output.stderr).trim()
+		);
+	}
+	Ok(output.stdout)
+}
+
+impl Repository {
+	/// Finds the repository whose work tree contains `directory`.
+	pub fn discover(directory: &Path) -> Result<Self> {
+		let output = run(
+			Command::new("git").current_dir(directory).args([
+				"rev-parse",
+				"--show-toplevel",
+				"--absolute-git-dir",
+			]),
+			None,
+		)?;
+		let output = String::from_utf8(output).context("git gave a path which is not UTF-8")?;
+		let mut lines = output.lines();
+		match (lines.next(), lines.next()) {
+			(Some(top_level), Some(git_dir)) => Ok(Repository {
+				top_level: PathBuf::from(top_level),
+				git_dir: PathBuf::from(git_dir),
+			}),
+			_ => bail!("'{}' is not in a git work tree", directory.display()),
+		}
+	}
+
+	/// The top of the work tree.
+	pub fn top_level(&self) -> &Path {
+		&self.top_level
+	}
+
+	fn git<I, S>(&self, args: I) -> Command
+	where
+		I: IntoIterator<Item = S>,
+		S: AsRef<OsStr>,
+	{
+		let mut command = Command::new("git");
+		command.current_dir(&self.top_level).args(args);
+		command
+	}
+
+	/// Lists the JavaScript and TypeScript files whose staged content differs from `HEAD`.
+	///
+	/// Deleted files, symbolic links and submodules are left out.
+	pub fn staged_files(&self, pathspecs: &[String]) -> Result<Vec<StagedFile>> {
+		let output = run(
+			self.git([
+				"diff",
+				"--cached",
+				"--raw",
+				"-z",
+				"--no-abbrev",
+				"--no-renames",
+				"--diff-filter=ACM",
+				"--",
+			])
+			.args(pathspecs),
+			None,
+		)?;
+
+		let mut files = Vec::new();
+		let mut fields = output.split(|byte| *byte == 0);
+		while let (Some(status), Some(path)) = (fields.next(), fields.next()) {
+			// ":<old mode> <new mode> <old blob> <new blob> <status>"
+			let status = String::from_utf8_lossy(status);
+			let [_, mode, _, blob, _] = status
+				.trim_start_matches(':')
+				.split(' ')
+				.collect::<Vec<_>>()[..]
+			else {
+				bail!("Unexpected output from git diff: '{}'", status);
+			};
+			let path = PathBuf::from(String::from_utf8_lossy(path).into_owned());
+			if (mode == "100644" || mode == "100755") && is_formattable(&path) {
+				files.push(StagedFile {
+					path,
+					mode: mode.to_string(),
+					blob: blob.to_string(),
+				});
+			}
+		}
+		Ok(files)
+	}
+
+	/// Reads a file's staged content.
+	pub fn read_blob(&self, file: &StagedFile) -> Result<String> {
+		let content = run(&mut self.git(["cat-file", "blob", &file.blob]), None)?;
+		String::from_utf8(content)
+			.map_err(|_| anyhow!("'{}' is not valid UTF-8", file.path.display()))
+	}
+
+	/// Replaces the staged content of files, returning the new object IDs in the same order.
+	pub fn stage(&self, files: &[(&StagedFile, &str)]) -> Result<Vec<String>> {
+		let mut blobs = Vec::new();
+		let mut index_info = Vec::new();
+		for (file, text) in files {
+			let blob = run(
+				&mut self.git(["hash-object", "-w", "--no-filters", "--stdin"]),
+				Some(text.as_bytes()),
+			)?;
+			let blob = String::from_utf8_lossy(&blob).trim().to_string();
+			write!(
+				index_info,
+				"{} {}\t{}\0",
+				file.mode,
+				blob,
+				file.path.display()
+			)?;
+			blobs.push(blob);
+		}
+		if !files.is_empty() {
+			run(
+				&mut self.git(["update-index", "-z", "--index-info"]),
+				Some(&index_info),
+			)?;
+		}
+		Ok(blobs)
+	}
+
+	/// Writes a staged file's formatted content to the work tree, merging it with any unstaged changes.
+	pub fn update_worktree(
+		&self,
+		file: &StagedFile,
+		original: &str,
+		formatted: &str,
+	) -> Result<WorktreeUpdate> {
+		let path = self.top_level.join(&file.path);
+		let Ok(current) = std::fs::read(&path) else {
+			// The file was deleted or replaced after it was staged.
+			return Ok(WorktreeUpdate::Conflict);
+		};
+		if current == original.as_bytes() {
+			std::fs::write(&path, formatted)
+				.with_context(|| format!("Could not write '{}'", path.display()))?;
+			return Ok(WorktreeUpdate::Written);
+		}
+		if current == formatted.as_bytes() {
+			return Ok(WorktreeUpdate::Written);
+		}
+
+		// Apply the formatting to the work tree with a three-way merge, the staged content being the base.
+		let temporary = |name: &str| {
+			self.git_dir
+				.join(format!("formate-js-{}-{}", std::process::id(), name))
+		};
+		let (base, ours) = (temporary("base"), temporary("formatted"));
+		std::fs::write(&base, original)?;
+		std::fs::write(&ours, formatted)?;
+		let output = self
+			.git(["merge-file", "-p", "--quiet"])
+			.args([&ours, &base, &path])
+			.output();
+		let _ = std::fs::remove_file(&base);
+		let _ = std::fs::remove_file(&ours);
+
+		let output = output.context("Could not run git")?;
+		match output.status.code() {
+			Some(0) => {
+				std::fs::write(&path, output.stdout)
+					.with_context(|| format!("Could not write '{}'", path.display()))?;
+				Ok(WorktreeUpdate::Merged)
+			}
+			Some(conflicts) if conflicts > 0 && conflicts < 128 => Ok(WorktreeUpdate::Conflict),
+			_ => bail!(
+				"git merge-file failed: {}",
+				String::from_utf8_lossy(&output.stderr).trim()
+			),
+		}
+	}
+
+	/// Formats the JavaScript and TypeScript files staged in the index, in parallel.
+	///
+	/// Files are formatted on worker threads, and those not formatted after `options.timeout` are given up on.
+	/// Only the staged content is formatted, so parts of a file which are not staged are left out of the commit.
+	/// Files which fail to format are reported in their result rather than stopping the others.
+	pub fn format_staged(
+		&self,
+		formatter: &Formatter,
+		options: &StagedOptions,
+	) -> Result<Vec<StagedResult>> {
+		let excludes = glob_set(&options.excludes)?;
+		let files = self
+			.staged_files(&options.pathspecs)?
+			.into_iter()
+			.filter(|file| !excludes.is_match(&file.path))
+			.collect::<Vec<_>>();
+
+		let mut originals = Vec::new();
+		let mut texts = Vec::new();
+		let mut read_errors = Vec::new();
+		for file in &files {
+			match self.read_blob(file) {
+				Ok(text) => {
+					texts.push((file.path.clone(), text.clone()));
+					originals.push(text);
+					read_errors.push(None);
+				}
+				Err(error) => {
+					originals.push(String::new());
+					read_errors.push(Some(error));
+				}
+			}
+		}
+		let mut formatted = format_with_timeout(formatter, texts, options.timeout).into_iter();
+
+		let mut results = Vec::new();
+		for ((file, original), read_error) in files.into_iter().zip(originals).zip(read_errors) {
+			let result = match read_error {
+				Some(error) => Err(error),
+				None => formatted.next().unwrap(),
+			};
+			results.push(StagedResult {
+				file,
+				original,
+				result,
+				worktree: WorktreeUpdate::Skipped,
+			});
+		}
+		if !options.write {
+			return Ok(results);
+		}
+
+		let changed = results
+			.iter()
+			.filter_map(|staged| match &staged.result {
+				Ok(result) if result.changed => Some((&staged.file, result.text.as_str())),
+				_ => None,
+			})
+			.collect::<Vec<_>>();
+		self.stage(&changed)?;
+
+		if options.update_worktree {
+			for staged in &mut results {
+				if let Ok(result) = &staged.result
+					&& result.changed
+				{
+					staged.worktree =
+						self.update_worktree(&staged.file, &staged.original, &result.text)?;
+				}
+			}
+		}
+		Ok(results)
+	}
+
+	/// Lists the JavaScript and TypeScript files in the work tree which differ from the commit `reference`.
+	///
+	/// The paths are absolute, and deleted files are left out.
+	pub fn changed_since(&self, reference: &str, pathspecs: &[String]) -> Result<Vec<PathBuf>> {
+		if reference.starts_with('-') {
+			bail!("'{}' is not a commit", reference);
+		}
+		let commit = format!("{}^{{commit}}", reference);
+		run(
+			&mut self.git(["rev-parse", "--verify", "--quiet", &commit]),
+			None,
+		)
+		.map_err(|_| anyhow!("'{}' is not a commit", reference))?;
+
+		let output = run(
+			self.git([
+				"diff",
+				"--name-only",
+				"-z",
+				"--no-renames",
+				"--diff-filter=ACM",
+				reference,
+				"--",
+			])
+			.args(pathspecs),
+			None,
+		)?;
+		Ok(output
+			.split(|byte| *byte == 0)
+			.filter(|path| !path.is_empty())
+			.map(|path| self.top_level.join(String::from_utf8_lossy(path).as_ref()))
+			.filter(|path| is_formattable(path) && path.is_file())
+			.collect())
+	}
+}
+
+/// Formats `files` on worker threads, giving up on the ones not formatted once `timeout` has passed,
+/// if there is one.
+///
+/// The timeout covers all of the files, so a single timer cancels the workers rather than each file
+/// getting a worker of its own. A file being printed then still finishes, as printing cannot be
+/// interrupted, and the rest fail with [`FormatTimeoutError`].
+fn format_with_timeout(
+	formatter: &Formatter,
+	files: Vec<(PathBuf, String)>,
+	timeout: Option<Duration>,
+) -> Vec<Result<FormatResult>> {
+	let formatter = Arc::new(formatter.clone());
+	let cancelled = Arc::new(AtomicBool::new(false));
+	let worker_cancelled = cancelled.clone();
+	thread::scope(|scope| {
+		let (finished, waiting) = mpsc::channel::<()>();
+		if let Some(timeout) = timeout {
+			scope.spawn(move || {
+				if let Err(RecvTimeoutError::Timeout) = waiting.recv_timeout(timeout) {
+					cancelled.store(true, Ordering::Relaxed);
+				}
+			});
+		}
+		let results = map_on_workers(formatter.worker_stack_size, files, move |(path, text)| {
+			let result = if worker_cancelled.load(Ordering::Relaxed) {
+				Err(FormatCancelledError.into())
+			} else {
+				formatter.format_cancellable(&path, None, text, &worker_cancelled)
+			};
+			match (result, timeout) {
+				(Err(error), Some(timeout)) if error.is::<FormatCancelledError>() => {
+					Err(FormatTimeoutError { path, timeout }.into())
+				}
+				(result, _) => result,
+			}
+		});
+		drop(finished);
+		results
+	})
+}
@@ -0,0 +1,37 @@
+//! Matching paths against the include and exclude patterns given by users.
+
+use anyhow::{Context, Result};
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use std::ffi::OsStr;
+
+/// Directories which are skipped when looking through a directory, unless given explicitly.
+const SKIPPED_DIRECTORIES: &[&str] = &[".git", ".hg", "node_modules"];
+
+/// Whether a directory with this name is one of the [`SKIPPED_DIRECTORIES`],
+/// leaving out `node_modules` unless `skip_node_modules` is set.
+pub(crate) fn is_skipped_directory(name: &OsStr, skip_node_modules: bool) -> bool {
+	SKIPPED_DIRECTORIES.iter().any(|skipped| name == *skipped)
+		&& (skip_node_modules || name != "node_modules")
+}
+
+/// Compiles include or exclude patterns. A pattern without a `/` matches a file or directory with that name anywhere.
+pub(crate) fn glob_set(patterns: &[String]) -> Result<GlobSet> {
+	let mut builder = GlobSetBuilder::new();
+	for pattern in patterns {
+		let pattern = pattern.trim_start_matches("./").trim_end_matches('/');
+		let globs = if pattern.contains('/') {
+			[pattern.to_string(), format!("{}/**", pattern)]
+		} else {
+			[format!("**/{}", pattern), format!("**/{}/**", pattern)]
+		};
+		for glob in globs {
+			builder.add(
+				GlobBuilder::new(&glob)
+					.literal_separator(true)
+					.build()
+					.with_context(|| format!("Invalid glob pattern '{}'", pattern))?,
+			);
+		}
+	}
+	Ok(builder.build()?)
+}
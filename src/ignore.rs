@@ -0,0 +1,307 @@
+use crate::parse;
+use deno_ast::swc::common::comments::CommentKind;
+use deno_ast::{ParsedSource, SourceRangedForSpanned, StartSourcePos};
+use dprint_core::formatting::ir_helpers::text_has_dprint_ignore;
+use std::fmt;
+
+/// Ignore comment handling that goes beyond dprint's single node/file ignore comment texts.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct IgnoreOptions {
+	/// The primary node ignore text, which is the one dprint itself understands.
+	pub node_comment_text: String,
+	/// Additional texts that are treated the same as [`IgnoreOptions::node_comment_text`].
+	pub node_comment_aliases: Vec<String>,
+	/// The primary file ignore text, which is the one dprint itself understands.
+	pub file_comment_text: String,
+	/// Additional texts that are treated the same as [`IgnoreOptions::file_comment_text`].
+	pub file_comment_aliases: Vec<String>,
+	/// Text marking the start of a region that is kept byte-for-byte. Empty to disable.
+	pub range_start_comment_text: String,
+	/// Text marking the end of a region that is kept byte-for-byte. Empty to disable.
+	pub range_end_comment_text: String,
+}
+
+/// A region of the original text which was kept as-is, as 1-based inclusive line numbers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct IgnoreRegion {
+	pub start_line: usize,
+	pub end_line: usize,
+}
+
+/// An error caused by unbalanced or nested range ignore comments.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IgnoreError {
+	pub message: String,
+	pub line: usize,
+}
+
+impl fmt::Display for IgnoreError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{} (line {})", self.message, self.line)
+	}
+}
+
+impl std::error::Error for IgnoreError {}
+
+/// Source text with ignore ranges and alias comments replaced, ready to be passed to dprint.
+#[derive(Debug)]
+pub struct PreparedText {
+	pub text: String,
+	pub regions: Vec<IgnoreRegion>,
+	// (placeholder, original text)
+	placeholders: Vec<(String, String)>,
+	// (replacement, original alias)
+	aliases: Vec<(String, String)>,
+}
+
+impl PreparedText {
+	/// Returns `true` if the text was modified and must be restored after formatting.
+	pub fn is_modified(&self) -> bool {
+		!self.placeholders.is_empty() || !self.aliases.is_empty()
+	}
+
+	/// Puts the original ignore ranges and alias comments back into the formatted text.
+	pub fn restore(&self, formatted: String) -> Result<String, IgnoreError> {
+		let mut text = formatted;
+
+		// Iterate in reverse so `...1` is never replaced inside `...10`.
+		for (replacement, alias) in self.aliases.iter().rev() {
+			text = text.replacen(replacement, alias, 1);
+		}
+
+		for ((placeholder, original), region) in self.placeholders.iter().zip(&self.regions).rev() {
+			match text.find(placeholder.as_str()) {
+				Some(pos) => text.replace_range(pos..pos + placeholder.len(), original),
+				None => {
+					return Err(IgnoreError {
+						message: "Ignored region was lost while formatting".to_string(),
+						line: region.start_line,
+					});
+				}
+			}
+		}
+
+		Ok(text)
+	}
+}
+
+/// Returns `true` if the leading comments of the file contain the file ignore text or one of its aliases.
+pub fn has_file_ignore_comment(text: &str, options: &IgnoreOptions) -> bool {
+	let aliases = std::iter::once(&options.file_comment_text)
+		.chain(&options.file_comment_aliases)
+		.filter(|a| !a.is_empty());
+	let aliases: Vec<&String> = aliases.collect();
+	if aliases.is_empty() {
+		return false;
+	}
+
+	leading_comments(text).into_iter().any(|body| {
+		let body = body.trim_start_matches(' ');
+		aliases.iter().any(|alias| body.starts_with(alias.as_str()))
+	})
+}
+
+/// Replaces range ignore regions with placeholders and node ignore aliases with the primary ignore text
+/// in the text of a parsed file.
+///
+/// The ignore comments are taken from the comments the parser found, so text which only looks like a
+/// comment, such as in a string, regular expression or JSX text, is left alone. This means the code in
+/// an ignored region must still parse.
+pub fn prepare(
+	parsed_source: &ParsedSource,
+	options: &IgnoreOptions,
+) -> Result<PreparedText, IgnoreError> {
+	let text = parsed_source.text().as_ref();
+	let mut prepared = PreparedText {
+		text: String::with_capacity(text.len()),
+		regions: Vec::new(),
+		placeholders: Vec::new(),
+		aliases: Vec::new(),
+	};
+
+	let node_aliases: Vec<&String> = options
+		.node_comment_aliases
+		.iter()
+		.filter(|a| !a.is_empty() && **a != options.node_comment_text)
+		.collect();
+	let ranges_enabled =
+		!options.range_start_comment_text.is_empty() && !options.range_end_comment_text.is_empty();
+	if node_aliases.is_empty() && !ranges_enabled {
+		prepared.text.push_str(text);
+		return Ok(prepared);
+	}
+
+	let mut last_end = 0;
+	let mut range_start: Option<(usize, usize)> = None;
+
+	for comment in comments(parsed_source) {
+		let body = comment.body(text);
+
+		if ranges_enabled && text_has_dprint_ignore(body, &options.range_start_comment_text) {
+			let line = line_number(text, comment.start);
+			if let Some((_, start_line)) = range_start {
+				return Err(IgnoreError {
+					message: format!(
+						"Nested '{}' comment; the one on line {} is not closed",
+						options.range_start_comment_text, start_line
+					),
+					line,
+				});
+			}
+			range_start = Some((comment.start, line));
+			continue;
+		}
+
+		if ranges_enabled && text_has_dprint_ignore(body, &options.range_end_comment_text) {
+			let line = line_number(text, comment.start);
+			let Some((start, start_line)) = range_start.take() else {
+				return Err(IgnoreError {
+					message: format!(
+						"'{}' comment without a preceding '{}' comment",
+						options.range_end_comment_text, options.range_start_comment_text
+					),
+					line,
+				});
+			};
+			let placeholder = format!("/*formatejsregion{}*/", prepared.placeholders.len());
+			prepared.text.push_str(&text[last_end..start]);
+			prepared.text.push_str(&placeholder);
+			prepared
+				.placeholders
+				.push((placeholder, text[start..comment.end].to_string()));
+			prepared.regions.push(IgnoreRegion {
+				start_line,
+				end_line: line,
+			});
+			last_end = comment.end;
+			continue;
+		}
+
+		if range_start.is_some() || text_has_dprint_ignore(body, &options.node_comment_text) {
+			continue;
+		}
+
+		let Some((alias, alias_pos)) = node_aliases
+			.iter()
+			.filter_map(|alias| find_ignore_text(body, alias).map(|pos| (*alias, pos)))
+			.min_by_key(|(_, pos)| *pos)
+		else {
+			continue;
+		};
+
+		let alias_start = comment.body_start + alias_pos;
+		let replacement = format!(
+			"{} formatejsalias{}",
+			options.node_comment_text,
+			prepared.aliases.len()
+		);
+		prepared.text.push_str(&text[last_end..alias_start]);
+		prepared.text.push_str(&replacement);
+		prepared.aliases.push((replacement, alias.to_string()));
+		last_end = alias_start + alias.len();
+	}
+
+	if let Some((_, start_line)) = range_start {
+		return Err(IgnoreError {
+			message: format!(
+				"'{}' comment without a matching '{}' comment",
+				options.range_start_comment_text, options.range_end_comment_text
+			),
+			line: start_line,
+		});
+	}
+
+	prepared.text.push_str(&text[last_end..]);
+	Ok(prepared)
+}
+
+/// Like dprint's [`text_has_dprint_ignore`] but returns the position of the match.
+fn find_ignore_text(text: &str, searching_text: &str) -> Option<usize> {
+	if text_has_dprint_ignore(text, searching_text) {
+		text.find(searching_text)
+	} else {
+		None
+	}
+}
+
+fn line_number(text: &str, pos: usize) -> usize {
+	text.as_bytes()[..pos]
+		.iter()
+		.filter(|b| **b == b'\n')
+		.count()
+		+ 1
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Comment {
+	start: usize,
+	end: usize,
+	body_start: usize,
+	body_end: usize,
+}
+
+impl Comment {
+	fn body<'a>(&self, text: &'a str) -> &'a str {
+		&text[self.body_start..self.body_end]
+	}
+}
+
+/// The comments the parser found, in order.
+fn comments(parsed_source: &ParsedSource) -> Vec<Comment> {
+	let source_start = StartSourcePos::START_SOURCE_POS;
+	let tokens = parsed_source.tokens();
+	let mut comments = parsed_source
+		.comments()
+		.get_vec()
+		.iter()
+		// The lexer can also record JSX text which looks like a comment as one, overlapping its token.
+		.filter(|comment| {
+			let index = tokens.partition_point(|token| token.span.hi <= comment.span.lo);
+			tokens
+				.get(index)
+				.is_none_or(|token| token.span.lo >= comment.span.hi)
+		})
+		.map(|comment| {
+			let range = comment.range();
+			let start = range.start.as_byte_index(source_start);
+			let end = range.end.as_byte_index(source_start);
+			Comment {
+				start,
+				end,
+				body_start: start + 2,
+				body_end: match comment.kind {
+					CommentKind::Line => end,
+					CommentKind::Block => end - 2,
+				},
+			}
+		})
+		.collect::<Vec<_>>();
+	comments.sort_by_key(|comment| comment.start);
+	comments
+}
+
+/// The bodies of the comments before any code, after the BOM and hashbang line if there are any.
+///
+/// These are found without parsing, so that a file which does not parse can still be ignored.
+fn leading_comments(text: &str) -> Vec<&str> {
+	let mut rest = text.strip_prefix(parse::BOM).unwrap_or(text);
+	if rest.starts_with("#!") {
+		rest = &rest[rest.find('\n').unwrap_or(rest.len())..];
+	}
+
+	let mut comments = Vec::new();
+	loop {
+		rest = rest.trim_start();
+		if let Some(comment) = rest.strip_prefix("//") {
+			let end = comment.find(['\r', '\n']).unwrap_or(comment.len());
+			comments.push(&comment[..end]);
+			rest = &comment[end..];
+		} else if let Some(comment) = rest.strip_prefix("/*") {
+			let end = comment.find("*/").unwrap_or(comment.len());
+			comments.push(&comment[..end]);
+			rest = comment.get(end + 2..).unwrap_or("");
+		} else {
+			return comments;
+		}
+	}
+}
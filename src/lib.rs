@@ -1,21 +1,40 @@
-use pyo3::prelude::*;
-mod configuration;
-/// Formate plugin for reformatting JavaScript and TypeScript files with dprint.
-mod configuration_builder;
-mod format_text;
-use crate::configuration::PyConfiguration;
-use crate::configuration_builder::PyConfigurationBuilder;
-use crate::format_text::{PyFormatTextOptions, format_text_py};
+//! Formate plugin for reformatting JavaScript and TypeScript files with dprint.
+//!
+//! The formatting logic is plain Rust and can be used without Python.
+//! The Python bindings are built when the default `python` feature is enabled,
+//! and a C interface when the `capi` feature is enabled.
+pub mod cache;
+#[cfg(feature = "capi")]
+pub mod capi;
+pub mod cli;
+pub mod configuration;
+pub mod dprint_plugin;
+pub mod edits;
+pub mod files;
+pub mod format_result;
+pub mod formate_config;
+pub mod formatter;
+pub mod git;
+mod globs;
+pub mod ignore;
+pub mod long_lines;
+pub mod lsp;
+pub mod mapping;
+mod parse;
+#[cfg(feature = "tracing")]
+pub mod print_items;
+pub mod project;
+#[cfg(feature = "python")]
+mod python;
+pub mod regions;
+pub mod skip;
+pub mod watch;
+pub mod worker;
 
-#[pymodule]
-fn _formate_js(_py: Python, m: &Bound<'_, PyModule>) -> PyResult<()> {
-	m.add_class::<PyConfigurationBuilder>().unwrap();
-	m.add_class::<PyConfiguration>().unwrap();
-	m.add_class::<PyFormatTextOptions>().unwrap();
-
-	let format_text = wrap_pyfunction!(format_text_py, m)?;
-	format_text.setattr("__module__", "_formate_js")?;
-	m.add_function(format_text).unwrap();
-
-	Ok(())
-}
+pub use crate::configuration::ConfigurationOptions;
+pub use crate::format_result::FormatResult;
+pub use crate::formate_config::{
+	HookConfiguration, find_formate_toml, load_formate_toml, parse_formate_toml,
+};
+pub use crate::formatter::Formatter;
+pub use crate::parse::ParseError;
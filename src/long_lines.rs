@@ -0,0 +1,166 @@
+//! Finding the lines which are still longer than the line width after formatting.
+//!
+//! dprint leaves a line long when it cannot be broken, such as for a long string, URL or chain,
+//! so this reports where that happened for lint-style checks.
+
+use crate::format_result::FormatResult;
+use crate::formatter::Formatter;
+use crate::parse;
+use crate::worker::run_on_worker;
+use anyhow::{Context, Result};
+use deno_ast::view::NodeTrait;
+use deno_ast::{ParsedSource, SourceRanged, SourceRangedForSpanned, StartSourcePos};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+use std::time::Duration;
+use unicode_width::UnicodeWidthStr;
+
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
+#[cfg_attr(
+	feature = "python",
+	pyclass(name = "LongLine", module = "_formate_js", frozen, get_all)
+)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// A line of formatted text which is wider than the line width.
+pub struct LongLine {
+	/// The 1-based line number.
+	pub line: usize,
+	/// The width of the line in columns, counting a tab as ``indent_width`` columns and wide characters as two.
+	pub width: usize,
+	/// The kind of the innermost syntax node making up most of the line, such as ``"Str"`` or ``"Comment"``,
+	/// or :py:obj:`None` if the file was skipped without being parsed.
+	pub kind: Option<String>,
+}
+
+/// The width of a line in columns, as dprint measures it.
+pub fn visual_width(line: &str, indent_width: usize) -> usize {
+	line.split('\t').map(UnicodeWidthStr::width).sum::<usize>()
+		+ line.matches('\t').count() * indent_width
+}
+
+/// The byte ranges of the lines wider than `line_width`, without their line breaks, and their widths.
+fn wide_lines(
+	text: &str,
+	line_width: usize,
+	indent_width: usize,
+) -> Vec<(usize, Range<usize>, usize)> {
+	let mut lines = Vec::new();
+	let mut start = 0;
+	for (index, line) in text.split_inclusive('\n').enumerate() {
+		let content = line.trim_end_matches(['\r', '\n']);
+		let width = visual_width(content, indent_width);
+		if width > line_width {
+			lines.push((index + 1, start..start + content.len(), width));
+		}
+		start += line.len();
+	}
+	lines
+}
+
+fn overlap(a: &Range<usize>, b: &Range<usize>) -> usize {
+	a.end.min(b.end).saturating_sub(a.start.max(b.start))
+}
+
+/// The kind of the innermost node which covers at least half of `range`, or `Comment` if comments do.
+fn dominant_kind(parsed_source: &ParsedSource, range: &Range<usize>) -> String {
+	let start = StartSourcePos::START_SOURCE_POS;
+	// Leave out the indentation.
+	let text = &parsed_source.text()[range.clone()];
+	let range = range.start + (text.len() - text.trim_start().len())..range.end;
+	let half = range.len().div_ceil(2);
+
+	let commented = parsed_source
+		.comments()
+		.get_vec()
+		.iter()
+		.map(|comment| {
+			let comment = comment.range();
+			let comment = comment.start.as_byte_index(start)..comment.end.as_byte_index(start);
+			overlap(&comment, &range)
+		})
+		.sum::<usize>();
+	if commented >= half {
+		return "Comment".to_string();
+	}
+
+	parsed_source.with_view(|program| {
+		let mut node = program.as_node();
+		// Descend without recursion, as the tree may be too deep for the stack.
+		while let Some(child) = node.children().into_iter().find(|child| {
+			let child = child.start().as_byte_index(start)..child.end().as_byte_index(start);
+			overlap(&child, &range) >= half
+		}) {
+			node = child;
+		}
+		format!("{:?}", node.kind())
+	})
+}
+
+/// Finds the lines of `result`'s text which are wider than the formatter's line width.
+fn long_lines(
+	formatter: &Formatter,
+	path: &Path,
+	extension: Option<&str>,
+	result: &FormatResult,
+) -> Result<Vec<LongLine>> {
+	let text = result.text.strip_prefix(parse::BOM).unwrap_or(&result.text);
+	let lines = wide_lines(
+		text,
+		formatter.config.line_width as usize,
+		formatter.config.indent_width as usize,
+	);
+	if lines.is_empty() {
+		return Ok(Vec::new());
+	}
+
+	let parsed_source = if result.skipped.is_none() {
+		Some(
+			parse::parse_source(path, extension, text.into())
+				.with_context(|| format!("Could not parse '{}'", path.display()))?,
+		)
+	} else {
+		None
+	};
+	Ok(lines
+		.into_iter()
+		.map(|(line, range, width)| LongLine {
+			line,
+			width,
+			kind: parsed_source
+				.as_ref()
+				.map(|parsed_source| dominant_kind(parsed_source, &range)),
+		})
+		.collect())
+}
+
+/// Formats a file on a worker thread, and finds the lines of the formatted text which are still wider
+/// than the line width, such as those holding long strings or URLs.
+///
+/// The formatted text is parsed on the same worker, as it needs as much stack as formatting did,
+/// and `timeout` covers both.
+///
+/// Returns the same errors as formatting the file.
+pub fn find_overlong_lines(
+	formatter: Arc<Formatter>,
+	path: PathBuf,
+	extension: Option<String>,
+	text: String,
+	timeout: Option<Duration>,
+) -> Result<Vec<LongLine>> {
+	let worker_path = path.clone();
+	run_on_worker(
+		formatter.worker_stack_size,
+		path,
+		timeout,
+		Arc::new(AtomicBool::new(false)),
+		move |cancelled| {
+			let extension = extension.as_deref();
+			let result = formatter.format_cancellable(&worker_path, extension, text, cancelled)?;
+			long_lines(&formatter, &worker_path, extension, &result)
+		},
+	)
+}
@@ -0,0 +1,396 @@
+//! A Language Server Protocol server which formats JavaScript and TypeScript documents.
+//!
+//! Documents are formatted with the configuration of the formate hook, read from the nearest
+//! `formate.toml` or `pyproject.toml`, so editors give the same output as running formate.
+//! dprint can only format whole files, so range and on-type formatting format the whole document
+//! and return only the edits which touch the requested lines.
+
+use crate::edits::{Position, Range, TextEdit};
+use crate::formate_config::{find_formate_toml, load_formate_toml};
+use crate::formatter::Formatter;
+use crate::mapping::OffsetEncoding;
+use crate::worker::format_edits_on_worker;
+use anyhow::{Context, Result};
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::io::{BufRead, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+// JSON-RPC and LSP error codes.
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const SERVER_NOT_INITIALIZED: i64 = -32002;
+const INVALID_REQUEST: i64 = -32600;
+const REQUEST_FAILED: i64 = -32803;
+
+/// Reads one message, or returns `None` at the end of the input.
+pub fn read_message(input: &mut impl BufRead) -> Result<Option<Value>> {
+	let mut content_length = None;
+	loop {
+		let mut header = String::new();
+		if input.read_line(&mut header)? == 0 {
+			return Ok(None);
+		}
+		let header = header.trim_end();
+		if header.is_empty() {
+			break;
+		}
+		if let Some((name, value)) = header.split_once(':')
+			&& name.eq_ignore_ascii_case("Content-Length")
+		{
+			content_length = Some(value.trim().parse::<usize>()?);
+		}
+	}
+
+	let content_length = content_length.context("Message without a Content-Length header")?;
+	let mut content = vec![0; content_length];
+	input.read_exact(&mut content)?;
+	Ok(Some(serde_json::from_slice(&content)?))
+}
+
+/// Writes one message.
+pub fn write_message(output: &mut impl Write, message: &Value) -> Result<()> {
+	let content = serde_json::to_string(message)?;
+	write!(
+		output,
+		"Content-Length: {}\r\n\r\n{}",
+		content.len(),
+		content
+	)?;
+	output.flush()?;
+	Ok(())
+}
+
+/// Serves requests from `input` until the client asks the server to exit.
+///
+/// Returns whether the client asked for a shutdown first, which is the exit code the protocol expects.
+pub fn serve(mut input: impl BufRead, mut output: impl Write) -> Result<bool> {
+	let mut server = Server::default();
+	while let Some(message) = read_message(&mut input)? {
+		let method = message.get("method").and_then(Value::as_str);
+		let id = message.get("id").cloned();
+		let params = message.get("params").cloned().unwrap_or(Value::Null);
+
+		match (method, id) {
+			(Some("exit"), _) => return Ok(server.shut_down),
+			(Some(method), Some(id)) => {
+				let response = match server.handle_request(method, params) {
+					Ok(result) => json!({"jsonrpc": "2.0", "id": id, "result": result}),
+					Err(ResponseError { code, message }) => json!({
+						"jsonrpc": "2.0",
+						"id": id,
+						"error": {"code": code, "message": message},
+					}),
+				};
+				write_message(&mut output, &response)?;
+			}
+			(Some(method), None) => server.handle_notification(method, params),
+			// Responses to requests from the server; it does not send any.
+			(None, _) => {}
+		}
+	}
+	Ok(false)
+}
+
+struct ResponseError {
+	code: i64,
+	message: String,
+}
+
+impl ResponseError {
+	fn new(code: i64, message: impl Into<String>) -> Self {
+		ResponseError {
+			code,
+			message: message.into(),
+		}
+	}
+}
+
+struct Document {
+	text: String,
+	language_id: String,
+}
+
+/// A formatter for the configuration file it was created from, and when that file was last modified.
+struct CachedFormatter {
+	modified: Option<SystemTime>,
+	formatter: Arc<Formatter>,
+	timeout: Option<Duration>,
+}
+
+#[derive(Default)]
+struct Server {
+	initialized: bool,
+	shut_down: bool,
+	encoding: OffsetEncoding,
+	documents: HashMap<String, Document>,
+	formatters: HashMap<Option<PathBuf>, CachedFormatter>,
+}
+
+impl Server {
+	fn handle_request(&mut self, method: &str, params: Value) -> Result<Value, ResponseError> {
+		if method == "initialize" {
+			return Ok(self.initialize(&params));
+		}
+		if !self.initialized {
+			return Err(ResponseError::new(
+				SERVER_NOT_INITIALIZED,
+				"The server has not been initialized",
+			));
+		}
+		if self.shut_down {
+			return Err(ResponseError::new(
+				INVALID_REQUEST,
+				"The server is shutting down",
+			));
+		}
+
+		match method {
+			"shutdown" => {
+				self.shut_down = true;
+				Ok(Value::Null)
+			}
+			"textDocument/formatting" => {
+				let edits = self.format(&params)?;
+				Ok(json!(edits))
+			}
+			"textDocument/rangeFormatting" => {
+				let range = position_range(&params["range"])?;
+				let edits = self.format(&params)?;
+				Ok(json!(edits_in_range(edits, &range)))
+			}
+			"textDocument/onTypeFormatting" => {
+				let position = position(&params["position"])?;
+				// The text being typed is often incomplete, so failing to format it is not an error.
+				let Ok(edits) = self.format(&params) else {
+					return Ok(Value::Null);
+				};
+				// A new line finishes the previous line, and other characters finish their own.
+				let line = match params["ch"].as_str() {
+					Some("\n") => position.line.saturating_sub(1),
+					_ => position.line,
+				};
+				let range = Range {
+					start: Position { line, character: 0 },
+					end: Position {
+						line: position.line,
+						character: usize::MAX,
+					},
+				};
+				Ok(json!(edits_in_range(edits, &range)))
+			}
+			_ => Err(ResponseError::new(
+				METHOD_NOT_FOUND,
+				format!("Unknown method '{}'", method),
+			)),
+		}
+	}
+
+	fn handle_notification(&mut self, method: &str, params: Value) {
+		match method {
+			"textDocument/didOpen" => {
+				let document = &params["textDocument"];
+				if let (Some(uri), Some(text)) =
+					(document["uri"].as_str(), document["text"].as_str())
+				{
+					self.documents.insert(
+						uri.to_string(),
+						Document {
+							text: text.to_string(),
+							language_id: document["languageId"]
+								.as_str()
+								.unwrap_or_default()
+								.to_string(),
+						},
+					);
+				}
+			}
+			"textDocument/didChange" => {
+				// Only full document sync is offered, so the last change holds the whole text.
+				let uri = params["textDocument"]["uri"].as_str().unwrap_or_default();
+				let text = params["contentChanges"]
+					.as_array()
+					.and_then(|changes| changes.last())
+					.and_then(|change| change["text"].as_str());
+				if let (Some(document), Some(text)) = (self.documents.get_mut(uri), text) {
+					document.text = text.to_string();
+				}
+			}
+			"textDocument/didClose" => {
+				if let Some(uri) = params["textDocument"]["uri"].as_str() {
+					self.documents.remove(uri);
+				}
+			}
+			"workspace/didChangeConfiguration" | "workspace/didChangeWatchedFiles" => {
+				self.formatters.clear();
+			}
+			_ => {}
+		}
+	}
+
+	fn initialize(&mut self, params: &Value) -> Value {
+		self.initialized = true;
+
+		// Offsets are UTF-16 unless the client offers something else, and UTF-8 is cheapest for us.
+		let encodings = params["capabilities"]["general"]["positionEncodings"]
+			.as_array()
+			.cloned()
+			.unwrap_or_default();
+		let encoding_name = if encodings.iter().any(|encoding| encoding == "utf-8") {
+			self.encoding = OffsetEncoding::Utf8;
+			"utf-8"
+		} else {
+			self.encoding = OffsetEncoding::Utf16;
+			"utf-16"
+		};
+
+		json!({
+			"capabilities": {
+				"positionEncoding": encoding_name,
+				// Full document sync.
+				"textDocumentSync": 1,
+				"documentFormattingProvider": true,
+				"documentRangeFormattingProvider": true,
+				"documentOnTypeFormattingProvider": {
+					"firstTriggerCharacter": ";",
+					"moreTriggerCharacter": ["}", "\n"],
+				},
+			},
+			"serverInfo": {
+				"name": "formate-js-lsp",
+				"version": env!("CARGO_PKG_VERSION"),
+			},
+		})
+	}
+
+	/// Formats the document named in the request, returning the edits to apply to it.
+	fn format(&mut self, params: &Value) -> Result<Vec<TextEdit>, ResponseError> {
+		let uri = params["textDocument"]["uri"]
+			.as_str()
+			.ok_or_else(|| ResponseError::new(INVALID_PARAMS, "Missing textDocument.uri"))?;
+		let document = self
+			.documents
+			.get(uri)
+			.ok_or_else(|| ResponseError::new(INVALID_PARAMS, format!("'{}' is not open", uri)))?;
+
+		let (path, extension) = match uri_to_path(uri) {
+			Some(path) => (path, None),
+			// Unsaved documents have no path, so the language decides how they are parsed.
+			None => (
+				PathBuf::from("untitled"),
+				Some(language_extension(&document.language_id).to_string()),
+			),
+		};
+		let text = document.text.clone();
+
+		let directory = path.parent().unwrap_or(Path::new("."));
+		let (formatter, timeout) = self
+			.formatter(directory)
+			.map_err(|error| ResponseError::new(REQUEST_FAILED, format!("{:#}", error)))?;
+		format_edits_on_worker(formatter, path, extension, text, timeout, self.encoding)
+			.map_err(|error| ResponseError::new(REQUEST_FAILED, error.to_string()))
+	}
+
+	/// The formatter for files in `directory`, created from the nearest formate configuration file.
+	fn formatter(&mut self, directory: &Path) -> Result<(Arc<Formatter>, Option<Duration>)> {
+		let config_file = find_formate_toml(directory);
+		let modified = config_file
+			.as_ref()
+			.and_then(|config_file| config_file.metadata().ok())
+			.and_then(|metadata| metadata.modified().ok());
+
+		if let Some(cached) = self.formatters.get(&config_file)
+			&& cached.modified == modified
+		{
+			return Ok((cached.formatter.clone(), cached.timeout));
+		}
+
+		// Without a configuration file, or with the hook disabled in it, the defaults are used.
+		let config = match &config_file {
+			Some(config_file) => load_formate_toml(config_file)?.unwrap_or_default(),
+			None => Default::default(),
+		};
+		let timeout = config
+			.timeout
+			.map(Duration::try_from_secs_f64)
+			.transpose()
+			.context("'timeout' must be a non-negative number")?;
+		let formatter = Arc::new(Formatter::new(&config.options)?);
+		self.formatters.insert(
+			config_file,
+			CachedFormatter {
+				modified,
+				formatter: formatter.clone(),
+				timeout,
+			},
+		);
+		Ok((formatter, timeout))
+	}
+}
+
+fn position(value: &Value) -> Result<Position, ResponseError> {
+	match (value["line"].as_u64(), value["character"].as_u64()) {
+		(Some(line), Some(character)) => Ok(Position {
+			line: line as usize,
+			character: character as usize,
+		}),
+		_ => Err(ResponseError::new(INVALID_PARAMS, "Invalid position")),
+	}
+}
+
+fn position_range(value: &Value) -> Result<Range, ResponseError> {
+	Ok(Range {
+		start: position(&value["start"])?,
+		end: position(&value["end"])?,
+	})
+}
+
+/// The edits which touch `range`.
+fn edits_in_range(edits: Vec<TextEdit>, range: &Range) -> Vec<TextEdit> {
+	edits
+		.into_iter()
+		.filter(|edit| edit.range.end >= range.start && edit.range.start <= range.end)
+		.collect()
+}
+
+/// The extension to parse an unsaved document with, from its language identifier.
+fn language_extension(language_id: &str) -> &'static str {
+	match language_id {
+		"javascript" => "js",
+		"javascriptreact" => "jsx",
+		"typescriptreact" => "tsx",
+		_ => "ts",
+	}
+}
+
+/// Converts a `file:` URI to a path, or returns `None` for other schemes.
+fn uri_to_path(uri: &str) -> Option<PathBuf> {
+	let path = uri.strip_prefix("file://")?;
+	// Skip the authority, which is normally empty.
+	let path = &path[path.find('/')?..];
+
+	let mut bytes = Vec::with_capacity(path.len());
+	let mut rest = path.as_bytes();
+	while let Some((&byte, tail)) = rest.split_first() {
+		match (byte, tail) {
+			(b'%', [high, low, tail @ ..]) => {
+				let hex = std::str::from_utf8(&[*high, *low]).ok()?.to_string();
+				bytes.push(u8::from_str_radix(&hex, 16).ok()?);
+				rest = tail;
+			}
+			_ => {
+				bytes.push(byte);
+				rest = tail;
+			}
+		}
+	}
+	let path = String::from_utf8(bytes).ok()?;
+
+	// Windows paths look like `/C:/path`.
+	if cfg!(windows) && path.as_bytes().get(2) == Some(&b':') {
+		return Some(PathBuf::from(&path[1..]));
+	}
+	Some(PathBuf::from(path))
+}
@@ -0,0 +1,373 @@
+//! Mapping positions between the original and the formatted text.
+//!
+//! Formatting mostly moves whitespace around, so positions are mapped by diffing the non-whitespace
+//! characters of the two texts. A position inside a token maps to the same place in that token.
+//! A position in whitespace which formatting changed maps to the start of the next token
+//! if it is in that token's indentation, and otherwise to just after the preceding token.
+
+use anyhow::{Result, bail};
+use similar::{Algorithm, DiffTag, capture_diff_slices_deadline};
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+/// Diffing gives up on finding the smallest diff after this long, which still gives a usable mapping.
+const DIFF_DEADLINE: Duration = Duration::from_secs(1);
+
+/// The unit offsets are counted in.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OffsetEncoding {
+	/// Bytes of UTF-8.
+	#[default]
+	Utf8,
+	/// UTF-16 code units, as used by JavaScript strings and most editors.
+	Utf16,
+	/// Unicode code points, which are Python string indices.
+	Utf32,
+}
+
+impl FromStr for OffsetEncoding {
+	type Err = anyhow::Error;
+
+	fn from_str(value: &str) -> Result<Self> {
+		match value {
+			"utf-8" => Ok(OffsetEncoding::Utf8),
+			"utf-16" => Ok(OffsetEncoding::Utf16),
+			"utf-32" => Ok(OffsetEncoding::Utf32),
+			_ => bail!("Invalid offset encoding '{}'", value),
+		}
+	}
+}
+
+impl OffsetEncoding {
+	pub(crate) fn len(self, c: char) -> usize {
+		match self {
+			OffsetEncoding::Utf8 => c.len_utf8(),
+			OffsetEncoding::Utf16 => c.len_utf16(),
+			OffsetEncoding::Utf32 => 1,
+		}
+	}
+
+	/// Converts an offset in this encoding into a byte offset.
+	fn to_byte_offset(self, text: &str, offset: usize) -> Result<usize> {
+		let mut position = 0;
+		for (byte_offset, c) in text.char_indices() {
+			if position >= offset {
+				if position > offset {
+					bail!("Offset {} is inside a character", offset);
+				}
+				return Ok(byte_offset);
+			}
+			position += self.len(c);
+		}
+		if position != offset {
+			bail!("Offset {} is past the end of the text", offset);
+		}
+		Ok(text.len())
+	}
+
+	/// Converts a byte offset into an offset in this encoding.
+	fn offset_at(self, text: &str, byte_offset: usize) -> usize {
+		match self {
+			OffsetEncoding::Utf8 => byte_offset,
+			_ => text[..byte_offset].chars().map(|c| self.len(c)).sum(),
+		}
+	}
+}
+
+/// Where a character of one text ended up in the other.
+#[derive(Clone, Copy, Debug)]
+enum Target {
+	/// The character is, or was replaced by, the character at this index.
+	Same(usize),
+	/// The character was removed or added, and belongs just before the character at this index.
+	Before(usize),
+}
+
+/// The non-whitespace characters of a text, with their byte offsets.
+struct Tokens {
+	offsets: Vec<usize>,
+	chars: Vec<char>,
+}
+
+impl Tokens {
+	fn new(text: &str) -> Self {
+		let (offsets, chars) = text
+			.char_indices()
+			.filter(|(_, c)| !c.is_whitespace())
+			.unzip();
+		Tokens { offsets, chars }
+	}
+
+	/// The byte offset just after the character before `index`, or the start of the text.
+	fn end_before(&self, index: usize) -> usize {
+		match index.checked_sub(1) {
+			Some(previous) => self.offsets[previous] + self.chars[previous].len_utf8(),
+			None => 0,
+		}
+	}
+}
+
+/// Maps offsets in the original text to offsets in the formatted text, and back.
+pub struct OffsetMap<'a> {
+	original: &'a str,
+	formatted: &'a str,
+	original_tokens: Tokens,
+	formatted_tokens: Tokens,
+	/// Where each non-whitespace character of the original text is in the formatted text.
+	forward: Vec<Target>,
+	/// Where each non-whitespace character of the formatted text is in the original text.
+	backward: Vec<Target>,
+}
+
+impl<'a> OffsetMap<'a> {
+	pub fn new(original: &'a str, formatted: &'a str) -> Self {
+		let original_tokens = Tokens::new(original);
+		let formatted_tokens = Tokens::new(formatted);
+
+		let mut forward = Vec::with_capacity(original_tokens.chars.len());
+		let mut backward = Vec::with_capacity(formatted_tokens.chars.len());
+		let ops = capture_diff_slices_deadline(
+			Algorithm::Myers,
+			&original_tokens.chars,
+			&formatted_tokens.chars,
+			Some(Instant::now() + DIFF_DEADLINE),
+		);
+		for op in ops {
+			let (tag, old, new) = op.as_tag_tuple();
+			match tag {
+				// Replaced characters (such as changed quotes) are paired up in order.
+				DiffTag::Equal | DiffTag::Replace => {
+					forward.extend(
+						(0..old.len()).map(|i| Target::Same(new.start + i.min(new.len() - 1))),
+					);
+					backward.extend(
+						(0..new.len()).map(|i| Target::Same(old.start + i.min(old.len() - 1))),
+					);
+				}
+				DiffTag::Delete => forward.extend(old.map(|_| Target::Before(new.start))),
+				DiffTag::Insert => backward.extend(new.map(|_| Target::Before(old.start))),
+			}
+		}
+
+		OffsetMap {
+			original,
+			formatted,
+			original_tokens,
+			formatted_tokens,
+			forward,
+			backward,
+		}
+	}
+
+	/// Maps an offset in the original text to the matching offset in the formatted text.
+	pub fn map_offset(&self, offset: usize, encoding: OffsetEncoding) -> Result<usize> {
+		let byte_offset = encoding.to_byte_offset(self.original, offset)?;
+		Ok(encoding.offset_at(self.formatted, self.map_byte_offset(byte_offset)))
+	}
+
+	fn map_byte_offset(&self, byte_offset: usize) -> usize {
+		let (from, to) = (&self.original_tokens, &self.formatted_tokens);
+		// The index of the first non-whitespace character at or after the offset.
+		let next = from.offsets.partition_point(|offset| *offset < byte_offset);
+		let next_start = match self.forward.get(next) {
+			Some(Target::Same(target)) => to.offsets[*target],
+			Some(Target::Before(target)) => to.end_before(*target),
+			None => self.formatted.len(),
+		};
+		if from.offsets.get(next) == Some(&byte_offset) {
+			// Keep the position at the start of the same character.
+			return next_start;
+		}
+
+		// The offset is in whitespace, between the previous and the next non-whitespace characters.
+		let previous_end = match next.checked_sub(1).map(|previous| self.forward[previous]) {
+			None => 0,
+			Some(Target::Same(target)) => to.end_before(target + 1),
+			Some(Target::Before(target)) => to.end_before(target),
+		};
+		let space_start = from.end_before(next);
+		let space_end = from
+			.offsets
+			.get(next)
+			.copied()
+			.unwrap_or(self.original.len());
+		let original_space = &self.original[space_start..space_end];
+
+		if self.formatted.get(previous_end..next_start) == Some(original_space) {
+			// The whitespace is unchanged.
+			previous_end + (byte_offset - space_start)
+		} else if self.original[space_start..byte_offset].contains('\n')
+			&& !self.original[byte_offset..space_end].contains('\n')
+		{
+			// Indentation stays with the line it indents.
+			next_start
+		} else {
+			previous_end
+		}
+	}
+
+	/// Maps offsets in the original text to the matching offsets in the formatted text.
+	pub fn map_offsets(&self, offsets: &[usize], encoding: OffsetEncoding) -> Result<Vec<usize>> {
+		offsets
+			.iter()
+			.map(|offset| self.map_offset(*offset, encoding))
+			.collect()
+	}
+
+	/// Returns a version 3 source map from the formatted text to the original text, as JSON.
+	///
+	/// `source` is the name of the original file. Columns are counted in UTF-16 code units.
+	pub fn source_map(&self, source: &str) -> String {
+		let original_lines = LineIndex::new(self.original);
+		let mut mappings = String::new();
+		let mut state = SegmentState::new();
+
+		let mut generated_column = 0;
+		let mut token = 0;
+		let mut after_whitespace = true;
+		let mut previous_end = None;
+		for (offset, c) in self.formatted.char_indices() {
+			if c == '\n' {
+				mappings.push(';');
+				state.new_line();
+				generated_column = 0;
+				after_whitespace = true;
+				continue;
+			}
+			if c.is_whitespace() {
+				generated_column += c.len_utf16();
+				after_whitespace = true;
+				continue;
+			}
+
+			let original_offset = match self.backward[token] {
+				Target::Same(original_token) => self.original_tokens.offsets[original_token],
+				Target::Before(original_token) => self.original_tokens.end_before(original_token),
+			};
+			// Only start a new segment where a run of characters does not follow on from the last one.
+			if after_whitespace || previous_end != Some(original_offset) {
+				let (original_line, original_column) =
+					original_lines.position(self.original, original_offset);
+				state.push(
+					&mut mappings,
+					generated_column,
+					original_line,
+					original_column,
+				);
+			}
+			debug_assert_eq!(self.formatted_tokens.offsets[token], offset);
+
+			previous_end = Some(original_offset + c.len_utf8());
+			after_whitespace = false;
+			generated_column += c.len_utf16();
+			token += 1;
+		}
+
+		serde_json::json!({
+			"version": 3,
+			"file": source,
+			"sources": [source],
+			"names": [],
+			"mappings": mappings,
+		})
+		.to_string()
+	}
+}
+
+fn utf16_len(text: &str) -> usize {
+	text.chars().map(char::len_utf16).sum()
+}
+
+/// The byte offsets of the start of each line.
+struct LineIndex(Vec<usize>);
+
+impl LineIndex {
+	fn new(text: &str) -> Self {
+		let starts = std::iter::once(0)
+			.chain(text.match_indices('\n').map(|(offset, _)| offset + 1))
+			.collect();
+		LineIndex(starts)
+	}
+
+	/// The 0-based line and UTF-16 column of the byte offset.
+	fn position(&self, text: &str, byte_offset: usize) -> (usize, usize) {
+		let line = self.0.partition_point(|start| *start <= byte_offset) - 1;
+		(line, utf16_len(&text[self.0[line]..byte_offset]))
+	}
+}
+
+/// The values source map segments are encoded relative to.
+struct SegmentState {
+	first_in_line: bool,
+	generated_column: usize,
+	original_line: usize,
+	original_column: usize,
+}
+
+impl SegmentState {
+	fn new() -> Self {
+		SegmentState {
+			first_in_line: true,
+			generated_column: 0,
+			original_line: 0,
+			original_column: 0,
+		}
+	}
+
+	/// Generated columns are relative to the start of each line, and the rest carry on.
+	fn new_line(&mut self) {
+		self.first_in_line = true;
+		self.generated_column = 0;
+	}
+
+	fn push(
+		&mut self,
+		mappings: &mut String,
+		generated_column: usize,
+		original_line: usize,
+		original_column: usize,
+	) {
+		if !self.first_in_line {
+			mappings.push(',');
+		}
+		self.first_in_line = false;
+
+		push_vlq(
+			mappings,
+			generated_column as i64 - self.generated_column as i64,
+		);
+		// Every segment refers to the only source.
+		push_vlq(mappings, 0);
+		push_vlq(mappings, original_line as i64 - self.original_line as i64);
+		push_vlq(
+			mappings,
+			original_column as i64 - self.original_column as i64,
+		);
+
+		self.generated_column = generated_column;
+		self.original_line = original_line;
+		self.original_column = original_column;
+	}
+}
+
+/// Appends a base64 VLQ encoded number.
+fn push_vlq(mappings: &mut String, value: i64) {
+	const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+	let mut value = if value < 0 {
+		(value.unsigned_abs() << 1) | 1
+	} else {
+		(value as u64) << 1
+	};
+	loop {
+		let mut digit = value & 0b11111;
+		value >>= 5;
+		if value > 0 {
+			digit |= 0b100000;
+		}
+		mappings.push(BASE64[digit as usize] as char);
+		if value == 0 {
+			break;
+		}
+	}
+}
@@ -0,0 +1,426 @@
+use anyhow::{Result, bail};
+use deno_ast::swc::parser::Syntax;
+use deno_ast::swc::parser::error::SyntaxError;
+use deno_ast::view::NodeTrait;
+use deno_ast::{MediaType, ModuleSpecifier, ParseDiagnostic, ParsedSource};
+use std::fmt;
+use std::path::Path;
+use std::sync::Arc;
+
+/// The extensions of the files which are formatted when looking through directories.
+pub(crate) const FILE_EXTENSIONS: &[&str] = &["ts", "tsx", "js", "jsx", "mjs", "cjs", "mts", "cts"];
+
+/// The byte order mark, which is not part of the source text given to the parser.
+pub(crate) const BOM: char = '\u{FEFF}';
+
+/// Returns whether the path has one of [`FILE_EXTENSIONS`].
+pub(crate) fn is_formattable(path: &Path) -> bool {
+	lowercase_extension(path).is_some_and(|extension| FILE_EXTENSIONS.contains(&extension.as_str()))
+}
+
+/// A syntax error in the text being formatted.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+	pub message: String,
+	/// The 1-based line of the error.
+	pub line: usize,
+	/// The 1-based column of the error, in characters.
+	pub column: usize,
+}
+
+impl ParseError {
+	fn new(message: String, diagnostic: &ParseDiagnostic) -> Self {
+		let position = diagnostic.display_position();
+		ParseError {
+			message,
+			line: position.line_number,
+			column: position.column_number,
+		}
+	}
+}
+
+impl fmt::Display for ParseError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{}", self.message)
+	}
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parses source text the same way `dprint_plugin_typescript::format_text` does.
+///
+/// Files with a `.js`/`.ts` (and friends) extension which fail to parse, or have one of the syntax errors
+/// dprint refuses to format, are reparsed as JSX/TSX, and that result is used if it has neither.
+pub fn parse_source(path: &Path, extension: Option<&str>, text: Arc<str>) -> Result<ParsedSource> {
+	match parse_inner(path, extension, text.clone()) {
+		Ok(parsed_source) => Ok(parsed_source),
+		Err(error) => {
+			let lowercase_extension = extension
+				.map(|extension| extension.to_string())
+				.or_else(|| lowercase_extension(path));
+			let jsx_path = match lowercase_extension.as_deref() {
+				Some("ts") | Some("cts") | Some("mts") => path.with_extension("tsx"),
+				Some("js") | Some("cjs") | Some("mjs") => path.with_extension("jsx"),
+				_ => return Err(error),
+			};
+			// Return the original error if the file is not JSX either.
+			parse_inner(&jsx_path, None, text).or(Err(error))
+		}
+	}
+}
+
+/// Returns the media type dprint would use for the given path and extension.
+pub fn media_type(path: &Path, extension: Option<&str>) -> MediaType {
+	match extension {
+		Some(extension) => MediaType::from_path(&path.with_extension(extension)),
+		None => MediaType::from_path(path),
+	}
+}
+
+/// A short name for the syntax a file was parsed with.
+pub fn syntax_name(media_type: MediaType) -> &'static str {
+	match media_type {
+		MediaType::TypeScript
+		| MediaType::Mts
+		| MediaType::Cts
+		| MediaType::Dts
+		| MediaType::Dmts
+		| MediaType::Dcts => "typescript",
+		MediaType::Tsx => "tsx",
+		MediaType::JavaScript | MediaType::Mjs | MediaType::Cjs => "javascript",
+		MediaType::Jsx => "jsx",
+		_ => "unknown",
+	}
+}
+
+fn parse_inner(path: &Path, extension: Option<&str>, text: Arc<str>) -> Result<ParsedSource> {
+	let parsed_source = parse_inner_no_diagnostic_check(path, extension, text)?;
+	ensure_no_specific_syntax_errors(&parsed_source)?;
+	Ok(parsed_source)
+}
+
+fn parse_inner_no_diagnostic_check(
+	path: &Path,
+	extension: Option<&str>,
+	text: Arc<str>,
+) -> Result<ParsedSource> {
+	let media_type = media_type(path, extension);
+	let mut syntax = deno_ast::get_syntax(media_type);
+	if let Syntax::Es(es) = &mut syntax {
+		// support decorators in js
+		es.decorators = true;
+	}
+
+	deno_ast::parse_program(deno_ast::ParseParams {
+		specifier: path_to_specifier(path)?,
+		capture_tokens: true,
+		maybe_syntax: Some(syntax),
+		media_type,
+		scope_analysis: false,
+		text,
+	})
+	.map_err(|diagnostic| ParseError::new(format!("{:#}", &diagnostic), &diagnostic).into())
+}
+
+/// Returns an error for the recoverable syntax errors dprint refuses to format,
+/// made up of the offending diagnostics and positioned at the first of them.
+fn ensure_no_specific_syntax_errors(parsed_source: &ParsedSource) -> Result<()> {
+	let diagnostics = parsed_source
+		.diagnostics()
+		.iter()
+		.filter(|diagnostic| {
+			matches!(
+				diagnostic.kind(),
+				// unexpected eof
+				SyntaxError::Eof |
+				// expected identifier
+				SyntaxError::TS1003 |
+				SyntaxError::ExpectedIdent |
+				// expected semi-colon
+				SyntaxError::TS1005 |
+				SyntaxError::ExpectedSemi |
+				// expected expression
+				SyntaxError::TS1109 |
+				// expected token
+				SyntaxError::Expected(_, _) |
+				// various expected
+				SyntaxError::ExpectedDigit { .. } |
+				SyntaxError::ExpectedSemiForExprStmt { .. } |
+				SyntaxError::ExpectedUnicodeEscape |
+				// various unterminated
+				SyntaxError::UnterminatedStrLit |
+				SyntaxError::UnterminatedBlockComment |
+				SyntaxError::UnterminatedJSXContents |
+				SyntaxError::UnterminatedRegExp |
+				SyntaxError::UnterminatedTpl |
+				// unexpected token
+				SyntaxError::Unexpected { .. } |
+				// Merge conflict marker
+				SyntaxError::TS1185
+			)
+		})
+		.collect::<Vec<_>>();
+
+	let Some(first) = diagnostics.first() else {
+		return Ok(());
+	};
+	let message = diagnostics
+		.iter()
+		.map(|diagnostic| diagnostic.to_string())
+		.collect::<Vec<_>>()
+		.join("\n\n");
+	Err(ParseError::new(message, first).into())
+}
+
+fn lowercase_extension(path: &Path) -> Option<String> {
+	path.extension()
+		.and_then(|extension| extension.to_str())
+		.map(|extension| extension.to_lowercase())
+}
+
+fn path_to_specifier(path: &Path) -> Result<ModuleSpecifier> {
+	if let Ok(specifier) = ModuleSpecifier::from_file_path(path) {
+		Ok(specifier)
+	} else if let Some(file_name) = path.file_name() {
+		match ModuleSpecifier::parse(&format!("file:///{}", file_name.to_string_lossy())) {
+			Ok(specifier) => Ok(specifier),
+			Err(err) => bail!(
+				"could not convert path to specifier: '{}', error: {:#}",
+				path.display(),
+				err
+			),
+		}
+	} else {
+		bail!("could not convert path to specifier: '{}'", path.display())
+	}
+}
+
+/// The keywords after which a `/` starts a regular expression rather than being a division.
+const KEYWORDS_BEFORE_EXPRESSION: &[&[u8]] = &[
+	b"return",
+	b"typeof",
+	b"instanceof",
+	b"in",
+	b"of",
+	b"new",
+	b"delete",
+	b"void",
+	b"throw",
+	b"case",
+	b"do",
+	b"else",
+	b"yield",
+	b"await",
+];
+
+/// The keywords whose condition in brackets may be followed by a regular expression, as in `if (a) /b/`.
+const CONTROL_KEYWORDS: &[&[u8]] = &[b"if", b"for", b"while", b"with"];
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Bracket {
+	/// A `(`, and whether it holds the condition of one of the [`CONTROL_KEYWORDS`].
+	Round { control: bool },
+	/// A `[` or `{`.
+	Other,
+	/// The `${` of a template literal.
+	Substitution,
+}
+
+fn is_word_byte(byte: u8) -> bool {
+	byte.is_ascii_alphanumeric() || matches!(byte, b'_' | b'$' | b'\\') || !byte.is_ascii()
+}
+
+/// The offset just past the string starting at `start`, or of the end of its line if it is unterminated.
+fn string_end(bytes: &[u8], start: usize) -> usize {
+	let quote = bytes[start];
+	let mut i = start + 1;
+	while i < bytes.len() {
+		match bytes[i] {
+			b'\\' => i += 1,
+			b'\n' => return i,
+			byte if byte == quote => return i + 1,
+			_ => {}
+		}
+		i += 1;
+	}
+	bytes.len()
+}
+
+/// Skips the text of a template literal from `start`, returning the offset just past its closing backtick,
+/// or past the `${` of a substitution and `true`.
+fn template_text_end(bytes: &[u8], start: usize) -> (usize, bool) {
+	let mut i = start;
+	while i < bytes.len() {
+		match bytes[i] {
+			b'\\' => i += 1,
+			b'`' => return (i + 1, false),
+			b'$' if bytes.get(i + 1) == Some(&b'{') => return (i + 2, true),
+			_ => {}
+		}
+		i += 1;
+	}
+	(bytes.len(), false)
+}
+
+/// The offset just past the regular expression starting at `start`, or `None` if its line ends first.
+fn regex_end(bytes: &[u8], start: usize) -> Option<usize> {
+	let mut in_class = false;
+	let mut i = start + 1;
+	while i < bytes.len() {
+		match bytes[i] {
+			b'\\' => i += 1,
+			b'\n' | b'\r' => return None,
+			b'[' => in_class = true,
+			b']' => in_class = false,
+			b'/' if !in_class => return Some(i + 1),
+			_ => {}
+		}
+		i += 1;
+	}
+	None
+}
+
+/// Returns whether the brackets in the code are nested more than `limit` levels deep, which can be found
+/// without parsing it.
+///
+/// Brackets in strings, comments, regular expressions and the text of template literals are skipped,
+/// and the `${` of a substitution counts as a bracket. Every bracket adds at least one level to the syntax
+/// tree, so this is a lower bound for [`nesting_depth`], as long as regular expressions are told apart
+/// from division the way a parser would. That is guessed from the token before each `/`, and the text
+/// of JSX elements is scanned as if it were code.
+///
+/// This guards the parser's stack, so it is the one place the text is scanned without the parser;
+/// everything else, such as finding ignore comments, uses the parsed source.
+pub fn brackets_deeper_than(text: &str, limit: usize) -> bool {
+	let bytes = text.as_bytes();
+	// There cannot be more levels than opening brackets, which are much quicker to count.
+	let opening_brackets = bytes
+		.iter()
+		.filter(|byte| matches!(byte, b'(' | b'[' | b'{'))
+		.count();
+	if opening_brackets <= limit {
+		return false;
+	}
+
+	let mut brackets = Vec::new();
+	// Whether a `/` here would start a regular expression rather than be a division.
+	let mut regex_allowed = true;
+	let mut after_control_keyword = false;
+	let mut i = 0;
+	while i < bytes.len() {
+		let byte = bytes[i];
+		if byte.is_ascii_whitespace() {
+			i += 1;
+			continue;
+		}
+		let is_control_keyword = std::mem::replace(&mut after_control_keyword, false);
+
+		match byte {
+			b'/' if bytes.get(i + 1) == Some(&b'/') => {
+				i = bytes[i..]
+					.iter()
+					.position(|byte| *byte == b'\n')
+					.map_or(bytes.len(), |end| i + end);
+				after_control_keyword = is_control_keyword;
+				continue;
+			}
+			b'/' if bytes.get(i + 1) == Some(&b'*') => {
+				i = bytes[i + 2..]
+					.windows(2)
+					.position(|window| window == b"*/")
+					.map_or(bytes.len(), |end| i + 2 + end + 2);
+				after_control_keyword = is_control_keyword;
+				continue;
+			}
+			b'/' if regex_allowed => {
+				if let Some(end) = regex_end(bytes, i) {
+					i = end;
+					regex_allowed = false;
+					continue;
+				}
+			}
+			b'\'' | b'"' => {
+				i = string_end(bytes, i);
+				regex_allowed = false;
+				continue;
+			}
+			b'`' => {
+				let (end, substitution) = template_text_end(bytes, i + 1);
+				i = end;
+				regex_allowed = substitution;
+				if substitution {
+					brackets.push(Bracket::Substitution);
+					if brackets.len() > limit {
+						return true;
+					}
+				}
+				continue;
+			}
+			b'(' | b'[' | b'{' => {
+				brackets.push(match byte {
+					b'(' => Bracket::Round {
+						control: is_control_keyword,
+					},
+					_ => Bracket::Other,
+				});
+				if brackets.len() > limit {
+					return true;
+				}
+				regex_allowed = true;
+				i += 1;
+				continue;
+			}
+			b')' | b']' | b'}' => {
+				let bracket = brackets.pop();
+				i += 1;
+				if byte == b'}' && bracket == Some(Bracket::Substitution) {
+					let (end, substitution) = template_text_end(bytes, i);
+					i = end;
+					regex_allowed = substitution;
+					if substitution {
+						brackets.push(Bracket::Substitution);
+					}
+				} else {
+					// A `}` most likely ends a block rather than an object literal.
+					regex_allowed = match bracket {
+						Some(Bracket::Round { control }) => control,
+						_ => byte == b'}',
+					};
+				}
+				continue;
+			}
+			byte if is_word_byte(byte) => {
+				let start = i;
+				while i < bytes.len() && is_word_byte(bytes[i]) {
+					i += 1;
+				}
+				let word = &bytes[start..i];
+				regex_allowed = KEYWORDS_BEFORE_EXPRESSION.contains(&word);
+				after_control_keyword = CONTROL_KEYWORDS.contains(&word);
+				continue;
+			}
+			_ => {}
+		}
+		// Any other punctuation, after which an expression may start.
+		regex_allowed = true;
+		i += 1;
+	}
+	false
+}
+
+/// Returns the depth of the deepest node in the syntax tree, stopping early once it is more than `limit`.
+pub fn nesting_depth(parsed_source: &ParsedSource, limit: usize) -> usize {
+	parsed_source.with_view(|program| {
+		// Walk the tree without recursion, as it may be too deep for the stack.
+		let mut max_depth = 0;
+		let mut stack = vec![(program.as_node(), 1)];
+		while let Some((node, depth)) = stack.pop() {
+			max_depth = max_depth.max(depth);
+			if max_depth > limit {
+				break;
+			}
+			stack.extend(node.children().into_iter().map(|child| (child, depth + 1)));
+		}
+		max_depth
+	})
+}
@@ -0,0 +1,250 @@
+//! Dumping the print items dprint generates for a file, and how the printer resolved their conditions.
+//!
+//! This is for understanding why a file was formatted the way it was, such as which condition decided
+//! that a call's arguments hang. It needs the `tracing` feature.
+
+use crate::formatter::Formatter;
+use crate::ignore::prepare;
+use crate::parse;
+use crate::worker::run_on_worker;
+use anyhow::{Context, Result};
+use dprint_core::formatting::{TraceInfo, TracePrintItem, TracePrintNode, TracingResult};
+use dprint_plugin_typescript::trace_file;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+
+/// One evaluation of a condition while printing.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConditionResolution {
+	pub condition_id: u32,
+	pub name: String,
+	pub print_node_id: u32,
+	/// Which path the printer took, or `None` if it could not be told from the trace,
+	/// such as when the printer went back to an earlier save point.
+	pub value: Option<bool>,
+}
+
+/// The print items generated for a file, the printer's trace through them, and the conditions it resolved.
+///
+/// Serializes to the JSON of dprint's tracing result with a `conditions` field added.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrintItemsDump {
+	#[serde(flatten)]
+	pub tracing: TracingResult,
+	/// The evaluations of conditions, in the order the printer made them.
+	pub conditions: Vec<ConditionResolution>,
+}
+
+/// Works out which path each condition took from the node the printer visited after it.
+fn resolve_conditions(tracing: &TracingResult) -> Vec<ConditionResolution> {
+	let nodes = tracing
+		.print_nodes
+		.iter()
+		.map(|node| (node.print_node_id, node))
+		.collect::<HashMap<_, _>>();
+	tracing
+		.traces
+		.windows(2)
+		.filter_map(|pair| {
+			let node = nodes.get(&pair[0].print_node_id)?;
+			let TracePrintItem::Condition(condition) = &node.print_item else {
+				return None;
+			};
+			let visited = Some(pair[1].print_node_id);
+			let value = if visited == condition.true_path {
+				Some(true)
+			} else if visited == condition.false_path {
+				Some(false)
+			} else if visited == node.next_print_node_id {
+				// The path taken was empty.
+				match (condition.true_path, condition.false_path) {
+					(None, Some(_)) => Some(true),
+					(Some(_), None) => Some(false),
+					_ => None,
+				}
+			} else {
+				None
+			};
+			Some(ConditionResolution {
+				condition_id: condition.condition_id,
+				name: condition.name.clone(),
+				print_node_id: node.print_node_id,
+				value,
+			})
+		})
+		.collect()
+}
+
+fn describe_info(info: &TraceInfo) -> String {
+	let (kind, inner) = match info {
+		TraceInfo::LineNumber(inner) => ("line number", inner),
+		TraceInfo::ColumnNumber(inner) => ("column number", inner),
+		TraceInfo::IsStartOfLine(inner) => ("is start of line", inner),
+		TraceInfo::IndentLevel(inner) => ("indent level", inner),
+		TraceInfo::LineStartColumnNumber(inner) => ("line start column number", inner),
+		TraceInfo::LineStartIndentLevel(inner) => ("line start indent level", inner),
+	};
+	format!("info {} {:?} ({})", kind, inner.name, inner.info_id)
+}
+
+fn describe_values(values: Option<&Vec<Option<bool>>>) -> String {
+	match values {
+		None => "not evaluated".to_string(),
+		Some(values) => values
+			.iter()
+			.map(|value| match value {
+				Some(value) => value.to_string(),
+				None => "?".to_string(),
+			})
+			.collect::<Vec<_>>()
+			.join(", "),
+	}
+}
+
+enum Line<'a> {
+	Node(&'a TracePrintNode),
+	Label(&'static str),
+}
+
+impl PrintItemsDump {
+	/// The dump as indented JSON.
+	pub fn to_json(&self) -> String {
+		serde_json::to_string_pretty(self).expect("print items can always be serialized")
+	}
+
+	/// The print items as an indented tree, one item per line, with the values each condition resolved to.
+	///
+	/// A path shared by several conditions is written out the first time it is reached.
+	pub fn to_text(&self) -> String {
+		let nodes = self
+			.tracing
+			.print_nodes
+			.iter()
+			.map(|node| (node.print_node_id, node))
+			.collect::<HashMap<_, _>>();
+		let mut values = HashMap::<u32, Vec<Option<bool>>>::new();
+		for resolution in &self.conditions {
+			values
+				.entry(resolution.print_node_id)
+				.or_default()
+				.push(resolution.value);
+		}
+
+		let mut text = String::new();
+		let mut written = HashSet::new();
+		// Walk the items without recursion, as they may be nested too deeply for the stack.
+		let mut stack = self
+			.tracing
+			.print_nodes
+			.first()
+			.map(|node| vec![(Line::Node(node), 0)])
+			.unwrap_or_default();
+		while let Some((line, depth)) = stack.pop() {
+			let indent = "  ".repeat(depth);
+			let node = match line {
+				Line::Label(label) => {
+					let _ = writeln!(text, "{}{}", indent, label);
+					continue;
+				}
+				Line::Node(node) => node,
+			};
+			if !written.insert(node.print_node_id) {
+				let _ = writeln!(text, "{}see {}", indent, node.print_node_id);
+				continue;
+			}
+
+			if let Some(next) = node.next_print_node_id.and_then(|id| nodes.get(&id)) {
+				stack.push((Line::Node(next), depth));
+			}
+			let id = node.print_node_id;
+			let description = match &node.print_item {
+				TracePrintItem::String(string) => format!("{:?}", string),
+				TracePrintItem::Signal(signal) => format!("signal {:?}", signal),
+				TracePrintItem::Info(info) => describe_info(info),
+				TracePrintItem::Anchor(anchor) => {
+					format!("anchor {:?} ({})", anchor.name, anchor.anchor_id)
+				}
+				TracePrintItem::ConditionReevaluation(reevaluation) => format!(
+					"reevaluate condition {:?} ({})",
+					reevaluation.name, reevaluation.condition_id
+				),
+				TracePrintItem::RcPath(path) => {
+					if let Some(path) = nodes.get(path) {
+						stack.push((Line::Node(path), depth + 1));
+					}
+					"path".to_string()
+				}
+				TracePrintItem::Condition(condition) => {
+					for (label, path) in [
+						("false:", condition.false_path),
+						("true:", condition.true_path),
+					] {
+						if let Some(path) = path.and_then(|path| nodes.get(&path)) {
+							stack.push((Line::Node(path), depth + 2));
+							stack.push((Line::Label(label), depth + 1));
+						}
+					}
+					format!(
+						"condition {:?} ({}) resolved: {}",
+						condition.name,
+						condition.condition_id,
+						describe_values(values.get(&id))
+					)
+				}
+			};
+			let _ = writeln!(text, "{}{}: {}", indent, id, description);
+		}
+		text
+	}
+}
+
+/// Generates and prints the print items for a file with tracing enabled, on a worker thread with a stack of
+/// [`Formatter::worker_stack_size`] bytes.
+///
+/// Ignore comments are handled as when formatting, so the items are those dprint would see.
+/// Returns an error if the file fails to parse.
+pub fn trace_print_items(
+	formatter: &Formatter,
+	path: &Path,
+	extension: Option<&str>,
+	text: &str,
+) -> Result<PrintItemsDump> {
+	let source_text = text.strip_prefix(parse::BOM).unwrap_or(text).to_string();
+	// dprint decides the syntax from the path alone.
+	let path = match extension {
+		Some(extension) => path.with_extension(extension),
+		None => path.to_path_buf(),
+	};
+	let worker_path = path.clone();
+	let config = formatter.config.clone();
+	let ignore_options = formatter.ignore_options.clone();
+
+	run_on_worker(
+		formatter.worker_stack_size,
+		path,
+		None,
+		Arc::new(AtomicBool::new(false)),
+		move |_| {
+			let parse = |text: &str| {
+				parse::parse_source(&worker_path, None, text.into())
+					.with_context(|| format!("Could not parse '{}'", worker_path.display()))
+			};
+			let prepared = prepare(&parse(&source_text)?, &ignore_options)?;
+			if prepared.is_modified() {
+				parse(&prepared.text)?;
+			}
+			let tracing = trace_file(&worker_path, &prepared.text, &config);
+			let conditions = resolve_conditions(&tracing);
+			Ok(PrintItemsDump {
+				tracing,
+				conditions,
+			})
+		},
+	)
+}
@@ -0,0 +1,178 @@
+//! Formatting every JavaScript and TypeScript file in a directory tree.
+
+use crate::cache::Cache;
+use crate::files::{FileOptions, format_file_with};
+use crate::formatter::Formatter;
+use crate::globs::{glob_set, is_skipped_directory};
+use crate::parse::is_formattable;
+use crate::worker::map_on_workers;
+use ::ignore::WalkBuilder;
+use anyhow::{Context, Result};
+use log::debug;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
+/// The ignore files which are honoured in every directory, as well as `.gitignore` and `.ignore`.
+pub const IGNORE_FILES: &[&str] = &[".prettierignore", ".dprintignore", ".formate-js-ignore"];
+
+/// Options for formatting a directory tree.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProjectOptions {
+	/// Glob patterns for the files to format, relative to the root.
+	/// If empty, files with JavaScript and TypeScript extensions are formatted.
+	pub includes: Vec<String>,
+	/// Glob patterns for the files and directories to skip. Patterns without a `/` match names anywhere.
+	pub excludes: Vec<String>,
+	/// Whether to skip `node_modules` directories.
+	pub skip_node_modules: bool,
+	/// Whether to write the formatted files. If not, the summary says which files would be reformatted.
+	pub write: bool,
+	/// Records which file contents are already formatted, so they are not parsed again.
+	pub cache: Option<Arc<Cache>>,
+}
+
+impl Default for ProjectOptions {
+	fn default() -> Self {
+		ProjectOptions {
+			includes: Vec::new(),
+			excludes: Vec::new(),
+			skip_node_modules: true,
+			write: true,
+			cache: None,
+		}
+	}
+}
+
+#[cfg_attr(
+	feature = "python",
+	pyclass(name = "ProjectSummary", module = "_formate_js", frozen, get_all)
+)]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+/// What happened to each file found when formatting a directory tree.
+pub struct ProjectSummary {
+	/// The files which were reformatted, or would have been if not writing.
+	pub changed: Vec<PathBuf>,
+	/// The files which were already formatted.
+	pub unchanged: Vec<PathBuf>,
+	/// The files left as-is without being parsed, and why (``"minified"`` or ``"oversized"``).
+	pub skipped: Vec<(PathBuf, String)>,
+	/// The files which could not be read, formatted or written, and the error.
+	pub failed: Vec<(PathBuf, String)>,
+}
+
+enum Outcome {
+	Changed,
+	Unchanged,
+	Skipped(String),
+	Failed(String),
+}
+
+/// Finds the files to format under `root`, in order.
+///
+/// `.gitignore` files are honoured whether or not `root` is in a git repository, as are the [`IGNORE_FILES`].
+pub fn find_project_files(root: &Path, options: &ProjectOptions) -> Result<Vec<PathBuf>> {
+	let includes = if options.includes.is_empty() {
+		None
+	} else {
+		Some(glob_set(&options.includes)?)
+	};
+	let excludes = glob_set(&options.excludes)?;
+
+	let mut builder = WalkBuilder::new(root);
+	builder.hidden(false).require_git(false);
+	for name in IGNORE_FILES {
+		builder.add_custom_ignore_filename(name);
+	}
+	let skip_node_modules = options.skip_node_modules;
+	let filter_root = root.to_path_buf();
+	let filter_excludes = excludes.clone();
+	builder.filter_entry(move |entry| {
+		if entry.depth() == 0 {
+			return true;
+		}
+		let skipped = is_skipped_directory(entry.file_name(), skip_node_modules);
+		let relative = entry
+			.path()
+			.strip_prefix(&filter_root)
+			.unwrap_or(entry.path());
+		!skipped && !filter_excludes.is_match(relative)
+	});
+
+	let mut files = Vec::new();
+	for entry in builder.build() {
+		let entry = entry.with_context(|| format!("Could not search '{}'", root.display()))?;
+		if !entry
+			.file_type()
+			.is_some_and(|file_type| file_type.is_file())
+		{
+			continue;
+		}
+		let relative = entry.path().strip_prefix(root).unwrap_or(entry.path());
+		let included = match &includes {
+			Some(includes) => includes.is_match(relative),
+			None => is_formattable(relative),
+		};
+		if included && !excludes.is_match(relative) {
+			files.push(entry.into_path());
+		}
+	}
+	files.sort();
+	debug!(
+		"Found {} files to format in '{}'",
+		files.len(),
+		root.display()
+	);
+	Ok(files)
+}
+
+fn format_file(formatter: &Formatter, path: &Path, options: &FileOptions) -> Outcome {
+	let result = match format_file_with(path, options, |text| formatter.format(path, None, text)) {
+		Ok(result) => result,
+		Err(error) => return Outcome::Failed(format!("{:#}", error)),
+	};
+	match result.skipped {
+		Some(reason) => Outcome::Skipped(reason),
+		None if result.changed => Outcome::Changed,
+		None => Outcome::Unchanged,
+	}
+}
+
+/// Formats the files under `root` in parallel, writing each one by renaming a temporary file over it.
+///
+/// Files which fail to format are listed in the summary rather than stopping the others.
+/// Files are formatted on threads with a stack of [`Formatter::worker_stack_size`] bytes.
+pub fn format_project(
+	root: &Path,
+	formatter: &Formatter,
+	options: &ProjectOptions,
+) -> Result<ProjectSummary> {
+	let files = find_project_files(root, options)?;
+	let file_options = FileOptions {
+		write: options.write,
+		cache: options.cache.clone(),
+		..FileOptions::default()
+	};
+	let formatter = Arc::new(formatter.clone());
+	let outcomes = map_on_workers(formatter.worker_stack_size, files, move |path| {
+		let outcome = format_file(&formatter, &path, &file_options);
+		(path, outcome)
+	});
+
+	let mut summary = ProjectSummary::default();
+	for (path, outcome) in outcomes {
+		match outcome {
+			Outcome::Changed => summary.changed.push(path),
+			Outcome::Unchanged => summary.unchanged.push(path),
+			Outcome::Skipped(reason) => summary.skipped.push((path, reason)),
+			Outcome::Failed(error) => summary.failed.push((path, error)),
+		}
+	}
+	summary.changed.sort();
+	summary.unchanged.sort();
+	summary.skipped.sort();
+	summary.failed.sort();
+	Ok(summary)
+}
@@ -1,4 +1,4 @@
-use crate::configuration::PyConfiguration;
+use crate::configuration::ConfigurationOptions;
 use dprint_core::configuration::NewLineKind;
 use dprint_plugin_typescript::configuration::ConfigurationBuilder;
 use dprint_plugin_typescript::configuration::{
@@ -7,6 +7,7 @@ use dprint_plugin_typescript::configuration::{
 	QuoteProps, QuoteStyle, SameOrNextLinePosition, SemiColonOrComma, SemiColons, SortOrder,
 	TrailingCommas, UseBraces, UseParentheses,
 };
+use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
 use std::str::FromStr;
 
@@ -15,7 +16,12 @@ use std::str::FromStr;
 #[pyclass(name = "ConfigurationBuilder", module = "formate_js")]
 #[repr(transparent)]
 // A wrapper around a [`ConfigurationBuilder`] that can be converted to and from python with `pyo3`.
+// Every method borrows the builder mutably, so using one builder from several threads at once
+// raises a RuntimeError instead of corrupting it.
 /// TypeScript formatting configuration builder.
+///
+/// A builder must only be used by one thread at a time. On free-threaded builds of Python,
+/// calling its methods from several threads at once raises :exc:`RuntimeError`.
 pub struct PyConfigurationBuilder(pub ConfigurationBuilder);
 
 impl PyConfigurationBuilder {
@@ -67,10 +73,9 @@ macro_rules! wrap_enum_fn {
 		impl PyConfigurationBuilder {
 			#[pyo3(signature = (value: "str") -> "ConfigurationBuilder")]
 			fn $name<'a>(mut slf: PyRefMut<'a, Self>, value: &str) -> PyResult<PyRefMut<'a, Self>> {
-				slf.0.$name(
-					<$rust_type>::from_str(value)
-						.unwrap_or_else(|_| panic!("Invalid enum value '{}'", &value)),
-				);
+				slf.0.$name(<$rust_type>::from_str(value).map_err(|_| {
+					PyValueError::new_err(format!("Invalid enum value '{}'", value))
+				})?);
 				Ok(slf)
 			}
 		}
@@ -81,10 +86,9 @@ macro_rules! wrap_enum_fn {
 			#[doc = $doc]
 			#[pyo3(signature = (value: "str") -> "ConfigurationBuilder")]
 			fn $name<'a>(mut slf: PyRefMut<'a, Self>, value: &str) -> PyResult<PyRefMut<'a, Self>> {
-				slf.0.$name(
-					<$rust_type>::from_str(value)
-						.unwrap_or_else(|_| panic!("Invalid enum value '{}'", &value)),
-				);
+				slf.0.$name(<$rust_type>::from_str(value).map_err(|_| {
+					PyValueError::new_err(format!("Invalid enum value '{}'", value))
+				})?);
 				Ok(slf)
 			}
 		}
@@ -114,9 +118,9 @@ impl PyConfigurationBuilder {
 		Ok(slf)
 	}
 
-	fn build<'a>(slf: PyRefMut<'a, Self>) -> PyResult<PyConfiguration> {
+	fn build<'a>(slf: PyRefMut<'a, Self>) -> PyResult<ConfigurationOptions> {
 		let configuration = slf.0.build();
-		Ok(PyConfiguration::from(configuration))
+		Ok(ConfigurationOptions::from(configuration))
 	}
 }
 
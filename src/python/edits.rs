@@ -0,0 +1,60 @@
+use crate::edits::{Position, Range, TextEdit};
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+#[pymethods]
+impl Position {
+	fn __repr__(&self) -> String {
+		format!("Position(line={}, character={})", self.line, self.character)
+	}
+
+	/// Returns the position as a Language Server Protocol ``Position``.
+	fn to_lsp<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+		let position = PyDict::new(py);
+		position.set_item("line", self.line)?;
+		position.set_item("character", self.character)?;
+		Ok(position)
+	}
+}
+
+#[pymethods]
+impl Range {
+	fn __repr__(&self) -> String {
+		format!(
+			"Range(start={}, end={})",
+			self.start.__repr__(),
+			self.end.__repr__()
+		)
+	}
+
+	/// Returns the range as a Language Server Protocol ``Range``.
+	fn to_lsp<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+		let range = PyDict::new(py);
+		range.set_item("start", self.start.to_lsp(py)?)?;
+		range.set_item("end", self.end.to_lsp(py)?)?;
+		Ok(range)
+	}
+}
+
+#[pymethods]
+impl TextEdit {
+	fn __repr__(&self) -> String {
+		format!(
+			"TextEdit(start={}, end={}, new_text={:?})",
+			self.start, self.end, self.new_text
+		)
+	}
+
+	/// Returns the edit as a Language Server Protocol ``TextEdit``.
+	fn to_lsp<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+		let edit = PyDict::new(py);
+		edit.set_item("range", self.range.to_lsp(py)?)?;
+		edit.set_item("newText", &self.new_text)?;
+		Ok(edit)
+	}
+
+	/// Returns the edit as a ``(start, end, new_text)`` tuple.
+	fn to_tuple(&self) -> (usize, usize, String) {
+		(self.start, self.end, self.new_text.clone())
+	}
+}
@@ -0,0 +1,53 @@
+use crate::formatter::{FormatPanicError, NonIdempotentFormattingError};
+use crate::worker::{FormatTimeoutError, TooManyTimedOutError};
+use pyo3::create_exception;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use std::path::Path;
+
+create_exception!(
+	_formate_js,
+	FormatError,
+	PyValueError,
+	"Raised when a file could not be formatted. The ``path`` attribute holds the path of the file, and \
+	``crash_report`` a report to include in a bug report if the formatter crashed."
+);
+
+create_exception!(
+	_formate_js,
+	FormatTimeout,
+	FormatError,
+	"Raised when formatting a file did not finish before the timeout. \
+	Printing cannot be interrupted, so the file may go on being formatted in the background, \
+	and while 8 files which timed out are, further files are refused with this exception."
+);
+
+create_exception!(
+	_formate_js,
+	NonIdempotentFormatting,
+	FormatError,
+	"Raised when formatting a file a second time changed it again. The ``diff`` attribute holds \
+	a unified diff from the first pass to the second."
+);
+
+/// Converts an error from formatting the file at `path` into the matching Python exception.
+pub fn format_error_to_py(py: Python<'_>, error: anyhow::Error, path: &Path) -> PyErr {
+	let err = if error.is::<FormatTimeoutError>() || error.is::<TooManyTimedOutError>() {
+		FormatTimeout::new_err(error.to_string())
+	} else if let Some(non_idempotent) = error.downcast_ref::<NonIdempotentFormattingError>() {
+		let err = NonIdempotentFormatting::new_err(error.to_string());
+		let _ = err.value(py).setattr("diff", &non_idempotent.diff);
+		err
+	} else {
+		FormatError::new_err(error.to_string())
+	};
+
+	let crash_report = error
+		.downcast_ref::<FormatPanicError>()
+		.map(FormatPanicError::crash_report);
+
+	// Setting an attribute on a freshly created exception instance cannot fail in practice.
+	let _ = err.value(py).setattr("path", path);
+	let _ = err.value(py).setattr("crash_report", crash_report);
+	err
+}
@@ -0,0 +1,68 @@
+use crate::cache::Cache;
+use crate::configuration::ConfigurationOptions;
+use crate::files::{DecodeError, FileOptions, format_file};
+use crate::format_result::FormatResult;
+use crate::formatter::Formatter;
+use crate::python::errors::format_error_to_py;
+use crate::python::logging;
+use crate::python::parse_timeout;
+use pyo3::exceptions::{PyOSError, PyValueError};
+use pyo3::prelude::*;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+#[pyfunction(name = "format_file")]
+#[pyo3(signature = (path, config, write = true, backup_suffix = None, timeout = None, cache_dir = None))]
+/// Formats a file on disk, writing it back if formatting changed it.
+///
+/// The file is read as UTF-8, or as UTF-16 if it starts with a byte order mark, and is written back in the
+/// same encoding, keeping any byte order mark. It is written by renaming a temporary file over it,
+/// so it is never left half written, and its permissions are kept.
+/// A file which is already formatted is not written, so its modification time is left alone.
+///
+/// :param path: The file to format.
+/// :param config:
+/// :param write: Whether to write the formatted text back to the file.
+/// :param backup_suffix: If given, the original file is copied to its path with this suffix appended
+/// 	(such as ``".orig"``) before it is reformatted.
+/// :param timeout: The maximum time to spend formatting the file, in seconds.
+/// :param cache_dir: A directory recording which file contents are already formatted with this configuration,
+/// 	so they are not parsed again. It is created if needed and can be shared between processes.
+///
+/// Returns a :class:`~.FormatResult`, or raises :exc:`~.FormatError` when the file failed to parse,
+/// :exc:`~.FormatTimeout` when formatting did not finish in time, :exc:`ValueError` when the file could not
+/// be decoded, and :exc:`OSError` when it could not be read or written.
+pub fn format_file_py(
+	py: Python<'_>,
+	path: PathBuf,
+	config: ConfigurationOptions,
+	write: bool,
+	backup_suffix: Option<String>,
+	timeout: Option<f64>,
+	cache_dir: Option<PathBuf>,
+) -> PyResult<FormatResult> {
+	let timeout = parse_timeout(timeout)?;
+	let formatter =
+		Formatter::new(&config).map_err(|error| PyValueError::new_err(error.to_string()))?;
+	let cache = cache_dir
+		.map(|directory| Cache::open(&directory, &formatter))
+		.transpose()
+		.map_err(|error| PyOSError::new_err(format!("{:#}", error)))?;
+	let options = FileOptions {
+		write,
+		backup_suffix,
+		timeout,
+		cache: cache.map(Arc::new),
+	};
+	logging::sync_level(py);
+	py.detach(|| format_file(Arc::new(formatter), &path, &options))
+		.map_err(|error| {
+			if error.is::<DecodeError>() {
+				PyValueError::new_err(error.to_string())
+			} else if error.downcast_ref::<std::io::Error>().is_some() {
+				PyOSError::new_err(format!("{:#}", error))
+			} else {
+				format_error_to_py(py, error, &path)
+			}
+		})
+}
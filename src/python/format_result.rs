@@ -0,0 +1,17 @@
+use crate::format_result::FormatResult;
+use pyo3::prelude::*;
+
+#[pymethods]
+impl FormatResult {
+	fn __repr__(&self) -> String {
+		if let Some(skipped) = &self.skipped {
+			return format!("FormatResult(skipped={:?})", skipped);
+		}
+		format!(
+			"FormatResult(changed={}, syntax={:?}, changed_lines={})",
+			if self.changed { "True" } else { "False" },
+			self.syntax,
+			self.changed_lines
+		)
+	}
+}
@@ -0,0 +1,235 @@
+use crate::configuration::ConfigurationOptions;
+use crate::edits::TextEdit;
+use crate::format_result::FormatResult;
+use crate::formatter::Formatter;
+use crate::long_lines::{LongLine, find_overlong_lines};
+use crate::mapping::OffsetEncoding;
+use crate::python::errors::format_error_to_py;
+use crate::python::formatter::{MappingRequest, format_py};
+use crate::python::logging;
+use crate::python::parse_timeout;
+use crate::regions::format_changed_regions;
+use crate::worker::format_edits_on_worker;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+#[pyclass(name = "FormatTextOptions", module = "_formate_js")]
+// #[repr(transparent)]
+#[derive(Clone)]
+// A wrapper around a [`FormatTextOptions`] that can be converted to and from python with `pyo3`.
+/// Options for reformatting a file.
+pub struct PyFormatTextOptions {
+	pub path: PathBuf,
+	pub extension: Option<String>,
+	pub text: String,
+	pub config: ConfigurationOptions,
+	// TODO: pub external_formatter: Option<ExternalFormatter>,
+}
+
+// impl From<PyFormatTextOptions> for FormatTextOptions<'_> {
+// 	fn from(value: PyFormatTextOptions) -> Self {
+// 		FormatTextOptions{
+// 			path: &value.path.clone(),
+//             extension: match value.extension {
+// 				Some(extension) => Some(&extension.clone()),
+// 				None => None,
+// 			},
+//             text: value.text,
+//             config: &value.config.0.clone(),
+//             external_formatter: None,
+// 		}
+// 	}
+// }
+
+#[pymethods]
+impl PyFormatTextOptions {
+	#[new]
+	pub fn __init__(
+		path: PathBuf,
+		extension: Option<String>,
+		text: String,
+		config: ConfigurationOptions,
+		// TODO: external_formatter: Option<&ExternalFormatter>,
+	) -> PyResult<Self> {
+		Ok(PyFormatTextOptions {
+			path,
+			extension,
+			text,
+			config,
+			// TODO: external_formatter,
+		})
+	}
+}
+
+#[pyfunction(name = "format_text")]
+#[pyo3(signature = (
+	options,
+	timeout = None,
+	offsets = None,
+	offset_encoding = "utf-8",
+	source_map = false,
+))]
+/// Formats a file.
+///
+/// :param options:
+/// :param timeout: The maximum time to spend formatting the file, in seconds.
+/// :param offsets: Offsets in the text, such as the cursor position, to map to the formatted text.
+/// :param offset_encoding: The unit ``offsets`` are counted in: ``"utf-8"`` bytes, ``"utf-16"`` code units
+/// 	or ``"utf-32"`` code points (Python string indices).
+/// :param source_map: Whether to build a source map from the formatted text to the original text.
+///
+/// Returns a :class:`~.FormatResult`, or raises :exc:`~.FormatError` when the file failed to parse
+/// and :exc:`~.FormatTimeout` when formatting did not finish in time.
+pub fn format_text_py(
+	py: Python<'_>,
+	options: PyFormatTextOptions,
+	timeout: Option<f64>,
+	offsets: Option<Vec<usize>>,
+	offset_encoding: &str,
+	source_map: bool,
+) -> PyResult<FormatResult> {
+	let mapping = MappingRequest::new(offsets, offset_encoding, source_map)?;
+	let formatter = Formatter::new(&options.config)
+		.map_err(|error| PyValueError::new_err(error.to_string()))?;
+	format_py(
+		py,
+		Arc::new(formatter),
+		options.path,
+		options.extension,
+		options.text,
+		timeout,
+		mapping,
+	)
+}
+
+#[pyfunction(name = "format_text_edits")]
+#[pyo3(signature = (options, timeout = None, offset_encoding = "utf-8"))]
+/// Formats a file, returning the smallest edits which turn the text into the formatted text.
+///
+/// :param options:
+/// :param timeout: The maximum time to spend formatting the file, in seconds.
+/// :param offset_encoding: The unit the offsets and characters of the edits are counted in: ``"utf-8"`` bytes,
+/// 	``"utf-16"`` code units or ``"utf-32"`` code points (Python string indices).
+///
+/// Returns a list of :class:`~.TextEdit`, in order and without overlapping, which is empty if the file is
+/// already formatted. Raises :exc:`~.FormatError` when the file failed to parse
+/// and :exc:`~.FormatTimeout` when formatting did not finish in time.
+pub fn format_text_edits_py(
+	py: Python<'_>,
+	options: PyFormatTextOptions,
+	timeout: Option<f64>,
+	offset_encoding: &str,
+) -> PyResult<Vec<TextEdit>> {
+	let encoding: OffsetEncoding = offset_encoding
+		.parse()
+		.map_err(|error: anyhow::Error| PyValueError::new_err(error.to_string()))?;
+	let timeout = parse_timeout(timeout)?;
+	let formatter = Formatter::new(&options.config)
+		.map_err(|error| PyValueError::new_err(error.to_string()))?;
+	let worker_path = options.path.clone();
+	logging::sync_level(py);
+	py.detach(|| {
+		format_edits_on_worker(
+			Arc::new(formatter),
+			worker_path,
+			options.extension,
+			options.text,
+			timeout,
+			encoding,
+		)
+	})
+	.map_err(|error| format_error_to_py(py, error, &options.path))
+}
+
+#[pyfunction(name = "format_changed_regions")]
+#[pyo3(signature = (old_text, new_text, options, path = PathBuf::from("file.ts"), extension = None, timeout = None))]
+/// Formats only the statements of ``new_text`` which overlap the lines changed since ``old_text``.
+///
+/// The rest of the file keeps its original layout, so files can be formatted gradually as they are touched.
+/// Statements inside a changed statement which did not change themselves are left alone too.
+///
+/// :param old_text: The previous version of the file, such as the one in version control.
+/// :param new_text: The current version of the file.
+/// :param options:
+/// :param path: The path to the file, which decides the syntax it is parsed with.
+/// :param extension: The extension to parse the file as instead of the one in ``path``.
+/// :param timeout: The maximum time to spend formatting the file, in seconds.
+///
+/// Returns a :class:`~.FormatResult`, or raises :exc:`~.FormatError` when the new text failed to parse
+/// and :exc:`~.FormatTimeout` when formatting did not finish in time.
+pub fn format_changed_regions_py(
+	py: Python<'_>,
+	old_text: &str,
+	new_text: String,
+	options: ConfigurationOptions,
+	path: PathBuf,
+	extension: Option<String>,
+	timeout: Option<f64>,
+) -> PyResult<FormatResult> {
+	let timeout = parse_timeout(timeout)?;
+	let formatter =
+		Formatter::new(&options).map_err(|error| PyValueError::new_err(error.to_string()))?;
+	let worker_path = path.clone();
+	logging::sync_level(py);
+	py.detach(|| {
+		format_changed_regions(
+			Arc::new(formatter),
+			worker_path,
+			extension,
+			old_text,
+			new_text,
+			timeout,
+		)
+	})
+	.map_err(|error| format_error_to_py(py, error, &path))
+}
+
+#[pymethods]
+impl LongLine {
+	fn __repr__(&self) -> String {
+		format!(
+			"LongLine(line={}, width={}, kind={})",
+			self.line,
+			self.width,
+			match &self.kind {
+				Some(kind) => format!("'{}'", kind),
+				None => "None".to_string(),
+			}
+		)
+	}
+}
+
+#[pyfunction(name = "find_overlong_lines")]
+#[pyo3(signature = (options, timeout = None))]
+/// Formats a file, and finds the lines of the formatted text which are still wider than ``line_width``,
+/// such as those holding long strings, URLs or chains which cannot be broken.
+///
+/// :param options:
+/// :param timeout: The maximum time to spend formatting the file, in seconds.
+///
+/// Returns a list of :class:`~.LongLine`, in order, which is empty if every line fits.
+/// Raises :exc:`~.FormatError` when the file failed to parse
+/// and :exc:`~.FormatTimeout` when formatting did not finish in time.
+pub fn find_overlong_lines_py(
+	py: Python<'_>,
+	options: PyFormatTextOptions,
+	timeout: Option<f64>,
+) -> PyResult<Vec<LongLine>> {
+	let timeout = parse_timeout(timeout)?;
+	let formatter = Formatter::new(&options.config)
+		.map_err(|error| PyValueError::new_err(error.to_string()))?;
+	let path = options.path.clone();
+	logging::sync_level(py);
+	py.detach(|| {
+		find_overlong_lines(
+			Arc::new(formatter),
+			options.path,
+			options.extension,
+			options.text,
+			timeout,
+		)
+	})
+	.map_err(|error| format_error_to_py(py, error, &path))
+}
@@ -0,0 +1,142 @@
+use crate::configuration::ConfigurationOptions;
+use crate::format_result::FormatResult;
+use crate::formatter::Formatter;
+use crate::mapping::{OffsetEncoding, OffsetMap};
+use crate::python::errors::format_error_to_py;
+use crate::python::logging;
+use crate::python::parse_timeout;
+use crate::worker::format_on_worker;
+use pyo3::{exceptions::PyValueError, prelude::*};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+#[pyclass(name = "Formatter", module = "_formate_js", frozen)]
+#[repr(transparent)]
+// A wrapper around a [`Formatter`] that can be used from python with `pyo3`.
+// It is frozen and the wrapped formatter is immutable, so it can be shared freely between threads.
+/// Formats files with a configuration which is resolved once, when the formatter is created.
+///
+/// Reuse a single formatter when formatting many files with the same configuration.
+pub struct PyFormatter(pub Arc<Formatter>);
+
+#[pymethods]
+impl PyFormatter {
+	#[new]
+	pub fn __init__(config: PyRef<'_, ConfigurationOptions>) -> PyResult<Self> {
+		let formatter =
+			Formatter::new(&config).map_err(|error| PyValueError::new_err(error.to_string()))?;
+		Ok(PyFormatter(Arc::new(formatter)))
+	}
+
+	/// Formats a file.
+	///
+	/// :param text: The source to reformat.
+	/// :param path: The name of the file being formatted.
+	/// :param extension: The file extension to use instead of the one from ``path``.
+	/// :param timeout: The maximum time to spend formatting the file, in seconds.
+	/// :param offsets: Offsets in ``text``, such as the cursor position, to map to the formatted text.
+	/// :param offset_encoding: The unit ``offsets`` are counted in: ``"utf-8"`` bytes, ``"utf-16"`` code units
+	/// 	or ``"utf-32"`` code points (Python string indices).
+	/// :param source_map: Whether to build a source map from the formatted text to ``text``.
+	///
+	/// Returns a :class:`~.FormatResult`, or raises :exc:`~.FormatError` when the file failed to parse
+	/// and :exc:`~.FormatTimeout` when formatting did not finish in time.
+	#[pyo3(signature = (
+		text,
+		path,
+		extension = None,
+		timeout = None,
+		offsets = None,
+		offset_encoding = "utf-8",
+		source_map = false,
+	))]
+	#[allow(clippy::too_many_arguments)]
+	fn format(
+		&self,
+		py: Python<'_>,
+		text: String,
+		path: PathBuf,
+		extension: Option<String>,
+		timeout: Option<f64>,
+		offsets: Option<Vec<usize>>,
+		offset_encoding: &str,
+		source_map: bool,
+	) -> PyResult<FormatResult> {
+		let mapping = MappingRequest::new(offsets, offset_encoding, source_map)?;
+		format_py(py, self.0.clone(), path, extension, text, timeout, mapping)
+	}
+}
+
+/// Positions to map from the original text to the formatted text, and whether to build a source map.
+#[derive(Default)]
+pub(super) struct MappingRequest {
+	offsets: Option<Vec<usize>>,
+	encoding: OffsetEncoding,
+	source_map: bool,
+}
+
+impl MappingRequest {
+	pub(super) fn new(
+		offsets: Option<Vec<usize>>,
+		offset_encoding: &str,
+		source_map: bool,
+	) -> PyResult<Self> {
+		Ok(MappingRequest {
+			offsets,
+			encoding: offset_encoding
+				.parse()
+				.map_err(|error: anyhow::Error| PyValueError::new_err(error.to_string()))?,
+			source_map,
+		})
+	}
+
+	fn is_empty(&self) -> bool {
+		self.offsets.is_none() && !self.source_map
+	}
+
+	fn apply(
+		&self,
+		mut result: FormatResult,
+		original: &str,
+		path: &Path,
+	) -> anyhow::Result<FormatResult> {
+		let offset_map = OffsetMap::new(original, &result.text);
+		if let Some(offsets) = &self.offsets {
+			result.mapped_offsets = Some(offset_map.map_offsets(offsets, self.encoding)?);
+		}
+		if self.source_map {
+			result.source_map = Some(offset_map.source_map(&path.to_string_lossy()));
+		}
+		Ok(result)
+	}
+}
+
+/// Formats a file from python on a worker thread, which has a large enough stack for deeply nested files.
+///
+/// The GIL is released while formatting so other python threads can format at the same time.
+pub(super) fn format_py(
+	py: Python<'_>,
+	formatter: Arc<Formatter>,
+	path: PathBuf,
+	extension: Option<String>,
+	text: String,
+	timeout: Option<f64>,
+	mapping: MappingRequest,
+) -> PyResult<FormatResult> {
+	let timeout = parse_timeout(timeout)?;
+	let worker_path = path.clone();
+	let original = (!mapping.is_empty()).then(|| text.clone());
+	logging::sync_level(py);
+	let result = py.detach(|| {
+		let result = format_on_worker(formatter, worker_path, extension, text, timeout)?;
+		Ok(match original {
+			Some(original) => mapping.apply(result, &original, &path),
+			None => Ok(result),
+		})
+	});
+
+	// Offsets which are out of range are the caller's mistake rather than a problem with the file.
+	result
+		.map_err(|error| format_error_to_py(py, error, &path))?
+		.map_err(|error| PyValueError::new_err(error.to_string()))
+}
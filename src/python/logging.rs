@@ -0,0 +1,81 @@
+//! Forwards the crate's log records to Python's :mod:`logging` module.
+//!
+//! Records from a module such as `formate_js::formatter` go to the `formate_js.formatter` logger.
+//! The most verbose level emitted is kept in step with the `formate_js` logger by [`sync_level`],
+//! so while it is disabled a record costs no more than reading that level.
+
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use pyo3::prelude::*;
+
+/// The Python logger the crate's loggers are children of.
+const ROOT_LOGGER: &str = "formate_js";
+
+struct PythonLogger;
+
+static LOGGER: PythonLogger = PythonLogger;
+
+/// Python's number for a log level. `TRACE` is below Python's ``DEBUG``.
+fn python_level(level: Level) -> u32 {
+	match level {
+		Level::Error => 40,
+		Level::Warn => 30,
+		Level::Info => 20,
+		Level::Debug => 10,
+		Level::Trace => 5,
+	}
+}
+
+fn level_filter(python_level: u32) -> LevelFilter {
+	match python_level {
+		0..=5 => LevelFilter::Trace,
+		6..=10 => LevelFilter::Debug,
+		11..=20 => LevelFilter::Info,
+		21..=30 => LevelFilter::Warn,
+		31..=40 => LevelFilter::Error,
+		_ => LevelFilter::Off,
+	}
+}
+
+impl Log for PythonLogger {
+	fn enabled(&self, metadata: &Metadata) -> bool {
+		metadata.level() <= log::max_level()
+	}
+
+	fn log(&self, record: &Record) {
+		if !self.enabled(record.metadata()) {
+			return;
+		}
+		// Records from threads still running while the interpreter shuts down are dropped.
+		let _ = Python::try_attach(|py| -> PyResult<()> {
+			let name = record.target().replace("::", ".");
+			let logger = py.import("logging")?.call_method1("getLogger", (name,))?;
+			let level = python_level(record.level());
+			if logger.call_method1("isEnabledFor", (level,))?.is_truthy()? {
+				logger.call_method1("log", (level, record.args().to_string()))?;
+			}
+			Ok(())
+		});
+	}
+
+	fn flush(&self) {}
+}
+
+/// Sets the most verbose level the crate emits to the effective level of the `formate_js` logger.
+///
+/// Called whenever formatting starts from Python, as the level may have been changed since.
+pub fn sync_level(py: Python<'_>) {
+	let level = py
+		.import("logging")
+		.and_then(|logging| logging.call_method1("getLogger", (ROOT_LOGGER,)))
+		.and_then(|logger| logger.call_method0("getEffectiveLevel"))
+		.and_then(|level| level.extract::<u32>())
+		.unwrap_or(30);
+	log::set_max_level(level_filter(level));
+}
+
+/// Installs the logger which forwards records to Python.
+pub fn init(py: Python<'_>) {
+	// Another logger is only installed already if the module is initialised twice.
+	let _ = log::set_logger(&LOGGER);
+	sync_level(py);
+}
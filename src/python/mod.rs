@@ -0,0 +1,97 @@
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use std::time::Duration;
+mod configuration;
+mod configuration_builder;
+mod edits;
+mod errors;
+mod files;
+mod format_result;
+mod format_text;
+mod formatter;
+mod logging;
+mod print_items;
+mod project;
+mod watch;
+use crate::configuration::ConfigurationOptions;
+use crate::edits::{Position, Range, TextEdit};
+use crate::format_result::FormatResult;
+use crate::long_lines::LongLine;
+use crate::project::ProjectSummary;
+use crate::watch::WatchEvent;
+use configuration_builder::PyConfigurationBuilder;
+use errors::{FormatError, FormatTimeout, NonIdempotentFormatting};
+use files::format_file_py;
+use format_text::{
+	PyFormatTextOptions, find_overlong_lines_py, format_changed_regions_py, format_text_edits_py,
+	format_text_py,
+};
+use formatter::PyFormatter;
+use print_items::debug_print_items_py;
+use project::format_project_py;
+use watch::watch_py;
+
+#[pymodule(gil_used = false)]
+pub fn _formate_js(py: Python, m: &Bound<'_, PyModule>) -> PyResult<()> {
+	logging::init(py);
+
+	m.add_class::<PyConfigurationBuilder>().unwrap();
+	m.add_class::<ConfigurationOptions>().unwrap();
+	m.add_class::<PyFormatTextOptions>().unwrap();
+	m.add_class::<FormatResult>().unwrap();
+	m.add_class::<PyFormatter>().unwrap();
+	m.add_class::<Position>().unwrap();
+	m.add_class::<Range>().unwrap();
+	m.add_class::<TextEdit>().unwrap();
+	m.add_class::<LongLine>().unwrap();
+	m.add_class::<ProjectSummary>().unwrap();
+	m.add_class::<WatchEvent>().unwrap();
+	m.add("FormatError", py.get_type::<FormatError>())?;
+	m.add("FormatTimeout", py.get_type::<FormatTimeout>())?;
+	m.add(
+		"NonIdempotentFormatting",
+		py.get_type::<NonIdempotentFormatting>(),
+	)?;
+
+	let format_text = wrap_pyfunction!(format_text_py, m)?;
+	format_text.setattr("__module__", "_formate_js")?;
+	m.add_function(format_text).unwrap();
+
+	let format_text_edits = wrap_pyfunction!(format_text_edits_py, m)?;
+	format_text_edits.setattr("__module__", "_formate_js")?;
+	m.add_function(format_text_edits).unwrap();
+
+	let format_changed_regions = wrap_pyfunction!(format_changed_regions_py, m)?;
+	format_changed_regions.setattr("__module__", "_formate_js")?;
+	m.add_function(format_changed_regions).unwrap();
+
+	let debug_print_items = wrap_pyfunction!(debug_print_items_py, m)?;
+	debug_print_items.setattr("__module__", "_formate_js")?;
+	m.add_function(debug_print_items).unwrap();
+
+	let format_file = wrap_pyfunction!(format_file_py, m)?;
+	format_file.setattr("__module__", "_formate_js")?;
+	m.add_function(format_file).unwrap();
+
+	let format_project = wrap_pyfunction!(format_project_py, m)?;
+	format_project.setattr("__module__", "_formate_js")?;
+	m.add_function(format_project).unwrap();
+
+	let find_overlong_lines = wrap_pyfunction!(find_overlong_lines_py, m)?;
+	find_overlong_lines.setattr("__module__", "_formate_js")?;
+	m.add_function(find_overlong_lines).unwrap();
+
+	let watch = wrap_pyfunction!(watch_py, m)?;
+	watch.setattr("__module__", "_formate_js")?;
+	m.add_function(watch).unwrap();
+
+	Ok(())
+}
+
+/// Converts a timeout in seconds from Python, raising :exc:`ValueError` if it is negative or not finite.
+fn parse_timeout(timeout: Option<f64>) -> PyResult<Option<Duration>> {
+	timeout
+		.map(Duration::try_from_secs_f64)
+		.transpose()
+		.map_err(|_| PyValueError::new_err("timeout must be a non-negative number"))
+}
@@ -0,0 +1,81 @@
+#[cfg(feature = "tracing")]
+use crate::formatter::Formatter;
+#[cfg(feature = "tracing")]
+use crate::print_items::trace_print_items;
+#[cfg(feature = "tracing")]
+use crate::python::errors::format_error_to_py;
+use crate::python::format_text::PyFormatTextOptions;
+#[cfg(feature = "tracing")]
+use crate::python::logging;
+#[cfg(not(feature = "tracing"))]
+use pyo3::exceptions::PyRuntimeError;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+#[pyfunction(name = "debug_print_items")]
+#[pyo3(signature = (options, format = "text"))]
+/// Shows the print items dprint generates for a file, and which way the printer resolved each condition.
+///
+/// This is for understanding why a file was formatted the way it was,
+/// such as why the arguments of a call expression hang, and for minimal bug reports.
+///
+/// :param options:
+/// :param format: ``"text"`` for an indented tree of the print items, one per line,
+/// 	or ``"json"`` for dprint's tracing result with the condition resolutions added.
+///
+/// In the text, each condition lists the values it resolved to in the order they were evaluated.
+/// ``?`` marks an evaluation whose outcome cannot be told from the trace,
+/// such as for conditions with no paths, which only record information.
+///
+/// Raises :exc:`~.FormatError` when the file failed to parse,
+/// and :exc:`RuntimeError` when formate-js was built without the ``tracing`` feature,
+/// which is off by default as it slows down formatting.
+pub fn debug_print_items_py(
+	py: Python<'_>,
+	options: PyFormatTextOptions,
+	format: &str,
+) -> PyResult<String> {
+	if format != "text" && format != "json" {
+		return Err(PyValueError::new_err(format!(
+			"format must be 'text' or 'json', not {:?}",
+			format
+		)));
+	}
+	dump_print_items(py, &options, format)
+}
+
+#[cfg(feature = "tracing")]
+fn dump_print_items(
+	py: Python<'_>,
+	options: &PyFormatTextOptions,
+	format: &str,
+) -> PyResult<String> {
+	let formatter = Formatter::new(&options.config)
+		.map_err(|error| PyValueError::new_err(error.to_string()))?;
+	logging::sync_level(py);
+	py.detach(|| {
+		let dump = trace_print_items(
+			&formatter,
+			&options.path,
+			options.extension.as_deref(),
+			&options.text,
+		)?;
+		Ok(match format {
+			"json" => dump.to_json(),
+			_ => dump.to_text(),
+		})
+	})
+	.map_err(|error| format_error_to_py(py, error, &options.path))
+}
+
+#[cfg(not(feature = "tracing"))]
+fn dump_print_items(
+	_py: Python<'_>,
+	_options: &PyFormatTextOptions,
+	_format: &str,
+) -> PyResult<String> {
+	Err(PyRuntimeError::new_err(
+		"debug_print_items needs formate-js to be built with the 'tracing' feature, \
+		 such as with `maturin build --features tracing`",
+	))
+}
@@ -0,0 +1,77 @@
+use crate::cache::Cache;
+use crate::configuration::ConfigurationOptions;
+use crate::formatter::Formatter;
+use crate::project::{ProjectOptions, ProjectSummary, format_project};
+use crate::python::logging;
+use pyo3::exceptions::{PyOSError, PyValueError};
+use pyo3::prelude::*;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+#[pymethods]
+impl ProjectSummary {
+	fn __repr__(&self) -> String {
+		format!(
+			"ProjectSummary(changed={}, unchanged={}, skipped={}, failed={})",
+			self.changed.len(),
+			self.unchanged.len(),
+			self.skipped.len(),
+			self.failed.len()
+		)
+	}
+}
+
+#[pyfunction(name = "format_project")]
+#[pyo3(signature = (root, config, include = None, exclude = None, skip_node_modules = true, write = true, cache_dir = None))]
+/// Formats the JavaScript and TypeScript files in a directory tree, in parallel.
+///
+/// ``.gitignore``, ``.prettierignore``, ``.dprintignore`` and ``.formate-js-ignore`` files are honoured,
+/// whether or not ``root`` is in a git repository.
+///
+/// :param root: The directory to format.
+/// :param config:
+/// :param include: Glob patterns for the files to format, relative to ``root``,
+/// 	rather than those with JavaScript and TypeScript extensions.
+/// :param exclude: Glob patterns for files and directories to skip.
+/// 	A pattern without a ``/`` matches a file or directory with that name anywhere.
+/// :param skip_node_modules: Whether to skip ``node_modules`` directories.
+/// :param write: Whether to write the reformatted files. If :py:obj:`False` nothing is changed.
+/// :param cache_dir: A directory recording which file contents are already formatted with this configuration,
+/// 	so they are not parsed again. It is created if needed and can be shared between processes.
+///
+/// Each file is written by renaming a temporary file over it, so it is never left half written.
+/// Files which fail to format are listed in the summary rather than stopping the others.
+#[allow(clippy::too_many_arguments)]
+pub fn format_project_py(
+	py: Python<'_>,
+	root: PathBuf,
+	config: ConfigurationOptions,
+	include: Option<Vec<String>>,
+	exclude: Option<Vec<String>>,
+	skip_node_modules: bool,
+	write: bool,
+	cache_dir: Option<PathBuf>,
+) -> PyResult<ProjectSummary> {
+	let formatter =
+		Formatter::new(&config).map_err(|error| PyValueError::new_err(error.to_string()))?;
+	let cache = cache_dir
+		.map(|directory| Cache::open(&directory, &formatter))
+		.transpose()
+		.map_err(|error| PyOSError::new_err(format!("{:#}", error)))?;
+	let options = ProjectOptions {
+		includes: include.unwrap_or_default(),
+		excludes: exclude.unwrap_or_default(),
+		skip_node_modules,
+		write,
+		cache: cache.map(Arc::new),
+	};
+	logging::sync_level(py);
+	py.detach(|| format_project(&root, &formatter, &options))
+		.map_err(|error| {
+			if error.downcast_ref::<globset::Error>().is_some() {
+				PyValueError::new_err(format!("{:#}", error))
+			} else {
+				PyOSError::new_err(format!("{:#}", error))
+			}
+		})
+}
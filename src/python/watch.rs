@@ -0,0 +1,120 @@
+use crate::configuration::ConfigurationOptions;
+use crate::formatter::Formatter;
+use crate::python::logging;
+use crate::python::parse_timeout;
+use crate::watch::{WatchEvent, WatchEventKind, WatchOptions, Watcher};
+use pyo3::exceptions::{PyOSError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::PyBool;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// How often to stop waiting for changes to check for :kbd:`Control-c`.
+const SIGNAL_INTERVAL: Duration = Duration::from_millis(200);
+
+#[pymethods]
+impl WatchEvent {
+	/// The path to the file.
+	#[getter]
+	fn path(&self) -> PathBuf {
+		self.path.clone()
+	}
+
+	/// ``"formatted"`` if the file was reformatted, ``"unchanged"`` if it was already formatted,
+	/// or ``"failed"`` if it could not be read, formatted or written.
+	#[getter]
+	fn kind(&self) -> &'static str {
+		self.kind.as_str()
+	}
+
+	/// Why the file failed to format.
+	#[getter]
+	fn error(&self) -> Option<String> {
+		self.error.clone()
+	}
+
+	fn __repr__(&self) -> String {
+		format!(
+			"WatchEvent(path={:?}, kind={:?}, error={:?})",
+			self.path.display().to_string(),
+			self.kind.as_str(),
+			self.error
+		)
+	}
+}
+
+#[pyfunction(name = "watch")]
+#[pyo3(signature = (
+	paths,
+	config,
+	on_event = None,
+	include = None,
+	exclude = None,
+	debounce = 0.1,
+	timeout = None,
+))]
+/// Watches files and directories, formatting files in them whenever they are saved.
+///
+/// :param paths: The files and directories to watch. Directories are watched recursively.
+/// :param config:
+/// :param on_event: Called with a :class:`~.WatchEvent` for each changed file once it has been formatted,
+/// 	or has failed to format. Watching stops if it returns :py:obj:`False`.
+/// 	By default reformatted files and failures are printed.
+/// :param include: Glob patterns for the files in watched directories to format,
+/// 	rather than those with JavaScript and TypeScript extensions.
+/// :param exclude: Glob patterns for files and directories to skip.
+/// 	A pattern without a ``/`` matches a file or directory with that name anywhere.
+/// :param debounce: How long a file must go without changing before it is formatted, in seconds.
+/// :param timeout: The maximum time to spend formatting each file, in seconds.
+///
+/// Runs until ``on_event`` returns :py:obj:`False` or raises an exception, or the process is interrupted.
+/// Files which fail to parse are reported without stopping, and the watcher's own writes are ignored.
+#[allow(clippy::too_many_arguments)]
+pub fn watch_py(
+	py: Python<'_>,
+	paths: Vec<PathBuf>,
+	config: ConfigurationOptions,
+	on_event: Option<Bound<'_, PyAny>>,
+	include: Option<Vec<String>>,
+	exclude: Option<Vec<String>>,
+	debounce: f64,
+	timeout: Option<f64>,
+) -> PyResult<()> {
+	let debounce = Duration::try_from_secs_f64(debounce)
+		.map_err(|_| PyValueError::new_err("debounce must be a non-negative number"))?;
+	let timeout = parse_timeout(timeout)?;
+	let formatter =
+		Formatter::new(&config).map_err(|error| PyValueError::new_err(error.to_string()))?;
+	let options = WatchOptions {
+		includes: include.unwrap_or_default(),
+		excludes: exclude.unwrap_or_default(),
+		debounce,
+		timeout,
+	};
+	let mut watcher = Watcher::new(&paths, Arc::new(formatter), &options)
+		.map_err(|error| PyOSError::new_err(format!("{:#}", error)))?;
+
+	loop {
+		logging::sync_level(py);
+		let events = py
+			.detach(|| watcher.poll(SIGNAL_INTERVAL))
+			.map_err(|error| PyOSError::new_err(format!("{:#}", error)))?;
+		for event in events {
+			match &on_event {
+				Some(on_event) => {
+					let result = on_event.call1((event,))?;
+					if result.is(&*PyBool::new(py, false)) {
+						return Ok(());
+					}
+				}
+				None => match event.kind {
+					WatchEventKind::Formatted => eprintln!("Reformatted {}", event.path.display()),
+					WatchEventKind::Unchanged => {}
+					WatchEventKind::Failed => eprintln!("{}", event.error.unwrap_or_default()),
+				},
+			}
+		}
+		py.check_signals()?;
+	}
+}
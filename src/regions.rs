@@ -0,0 +1,292 @@
+//! Formatting only the statements which changed between two versions of a file.
+//!
+//! The whole new version is formatted, and then only the edits which fall in the statements overlapping
+//! changed lines are kept. Statements nested in a changed statement which did not change themselves
+//! keep their layout, so changing the header of a function does not reformat its body.
+
+use crate::edits::text_edits;
+use crate::format_result::FormatResult;
+use crate::formatter::Formatter;
+use crate::mapping::OffsetEncoding;
+use crate::parse;
+use crate::worker::run_on_worker;
+use anyhow::{Context, Result};
+use deno_ast::view::{NodeKind, NodeTrait};
+use deno_ast::{ParsedSource, SourceRanged, StartSourcePos};
+use similar::{DiffTag, TextDiff};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+use std::time::Duration;
+
+/// A statement, or a member of a class, interface or enum, as a byte range of the text.
+struct Statement {
+	range: Range<usize>,
+	/// The index of the closest statement containing this one.
+	parent: Option<usize>,
+}
+
+/// Whether the children of a node of this kind are statements or members.
+fn contains_statements(kind: NodeKind) -> bool {
+	matches!(
+		kind,
+		NodeKind::Module
+			| NodeKind::Script
+			| NodeKind::BlockStmt
+			| NodeKind::SwitchCase
+			| NodeKind::TsModuleBlock
+			| NodeKind::Class
+			| NodeKind::TsInterfaceBody
+			| NodeKind::TsEnumDecl
+	)
+}
+
+/// Finds the statements in a parsed text, in order of where they start. `offset` is added to their ranges.
+fn statements(parsed_source: &ParsedSource, offset: usize) -> Vec<Statement> {
+	parsed_source.with_view(|program| {
+		let start = StartSourcePos::START_SOURCE_POS;
+		let mut statements = Vec::new();
+		// Walk the tree without recursion, as it may be too deep for the stack.
+		let mut stack = vec![(program.as_node(), None)];
+		while let Some((node, parent)) = stack.pop() {
+			let is_statement = node
+				.parent()
+				.is_some_and(|parent| contains_statements(parent.kind()));
+			let parent = if is_statement {
+				statements.push(Statement {
+					range: node.start().as_byte_index(start) + offset
+						..node.end().as_byte_index(start) + offset,
+					parent,
+				});
+				Some(statements.len() - 1)
+			} else {
+				parent
+			};
+			stack.extend(
+				node.children()
+					.into_iter()
+					.rev()
+					.map(|child| (child, parent)),
+			);
+		}
+		statements
+	})
+}
+
+/// The byte ranges of the lines of `new` which were added or changed since `old`.
+///
+/// Where lines were only removed, the line after them counts as changed.
+fn changed_lines(old: &str, new: &str) -> Vec<Range<usize>> {
+	let diff = TextDiff::from_lines(old, new);
+	let mut starts = vec![0];
+	let mut offset = 0;
+	for line in diff.new_slices() {
+		offset += line.len();
+		starts.push(offset);
+	}
+
+	let mut lines = Vec::new();
+	for op in diff.ops() {
+		let new_range = op.new_range();
+		if op.tag() == DiffTag::Equal {
+			continue;
+		}
+		let (first, last) = if new_range.is_empty() {
+			let line = new_range.start.min(starts.len().saturating_sub(2));
+			(line, line + 1)
+		} else {
+			(new_range.start, new_range.end)
+		};
+		if let (Some(start), Some(end)) = (starts.get(first), starts.get(last)) {
+			lines.push(*start..*end);
+		}
+	}
+	lines
+}
+
+/// Extends a byte range to the start and end of the lines it is on, including the final newline.
+fn whole_lines(text: &str, range: &Range<usize>) -> Range<usize> {
+	let start = text[..range.start].rfind('\n').map_or(0, |index| index + 1);
+	let end = text[range.end..]
+		.find('\n')
+		.map_or(text.len(), |index| range.end + index + 1);
+	start..end
+}
+
+/// Which statements are the innermost ones overlapping each changed line, and the changed lines outside any statement.
+fn touched_statements(
+	statements: &[Statement],
+	lines: &[Range<usize>],
+) -> (Vec<bool>, Vec<Range<usize>>) {
+	let overlaps = |range: &Range<usize>, line: &Range<usize>| {
+		range.start < line.end.max(line.start + 1) && range.end > line.start
+	};
+
+	let mut touched = vec![false; statements.len()];
+	let mut loose_lines = Vec::new();
+	for line in lines {
+		let overlapping = statements
+			.iter()
+			.enumerate()
+			.filter(|(_, statement)| overlaps(&statement.range, line))
+			.map(|(index, _)| index)
+			.collect::<Vec<_>>();
+		if overlapping.is_empty() {
+			loose_lines.push(line.clone());
+		}
+		for index in &overlapping {
+			let is_innermost = !overlapping
+				.iter()
+				.any(|other| statements[*other].parent == Some(*index));
+			if is_innermost {
+				touched[*index] = true;
+			}
+		}
+	}
+	(touched, loose_lines)
+}
+
+/// Keeps the formatting edits which belong to statements overlapping `lines`, which are byte ranges of `new`.
+///
+/// An edit belongs to the innermost statement containing it, where a touched statement also takes in the rest
+/// of the lines it is on. An edit next to an untouched statement, such as adding its semicolon, belongs to it.
+fn apply_changed_regions(
+	parsed_source: &ParsedSource,
+	lines: &[Range<usize>],
+	new: &str,
+	formatted: &str,
+) -> String {
+	let offset = if new.starts_with(parse::BOM) {
+		parse::BOM.len_utf8()
+	} else {
+		0
+	};
+	let statements = statements(parsed_source, offset);
+	let (touched, loose_lines) = touched_statements(&statements, lines);
+	let extents = statements
+		.iter()
+		.zip(&touched)
+		.map(|(statement, touched)| {
+			if *touched {
+				whole_lines(new, &statement.range)
+			} else {
+				statement.range.clone()
+			}
+		})
+		.collect::<Vec<_>>();
+	let contains =
+		|range: &Range<usize>, start: usize, end: usize| range.start <= start && end <= range.end;
+
+	let mut text = String::with_capacity(formatted.len());
+	let mut last_end = 0;
+	for edit in text_edits(new, formatted, OffsetEncoding::Utf8) {
+		let owner = extents
+			.iter()
+			.rposition(|extent| contains(extent, edit.start, edit.end));
+		let kept = match owner {
+			Some(index) => touched[index],
+			None => loose_lines
+				.iter()
+				.any(|line| contains(line, edit.start, edit.end)),
+		};
+		if kept {
+			text.push_str(&new[last_end..edit.start]);
+			text.push_str(&edit.new_text);
+			last_end = edit.end;
+		}
+	}
+	text.push_str(&new[last_end..]);
+	text
+}
+
+/// Formats only the statements of `new_text` which overlap lines changed since `old_text`, on a worker thread.
+///
+/// The rest of the file keeps its original layout, so files can be formatted gradually as they are touched.
+/// Returns the same errors as formatting the whole of `new_text`.
+pub fn format_changed_regions(
+	formatter: Arc<Formatter>,
+	path: PathBuf,
+	extension: Option<String>,
+	old_text: &str,
+	new_text: String,
+	timeout: Option<Duration>,
+) -> Result<FormatResult> {
+	let lines = changed_lines(old_text, &new_text);
+	format_regions(
+		formatter,
+		path,
+		extension,
+		new_text,
+		lines,
+		timeout,
+		Arc::new(AtomicBool::new(false)),
+	)
+}
+
+/// Formats only the statements of `text` which overlap the byte range `range`, on a worker thread.
+///
+/// This is range formatting as editors ask for it, with the rest of the file keeping its layout.
+/// Formatting stops early if `cancelled` is set, as with [`format_on_worker_cancellable`](crate::worker::format_on_worker_cancellable).
+pub fn format_range(
+	formatter: Arc<Formatter>,
+	path: PathBuf,
+	extension: Option<String>,
+	text: String,
+	range: Range<usize>,
+	timeout: Option<Duration>,
+	cancelled: Arc<AtomicBool>,
+) -> Result<FormatResult> {
+	format_regions(
+		formatter,
+		path,
+		extension,
+		text,
+		vec![range],
+		timeout,
+		cancelled,
+	)
+}
+
+/// Formats `text` on a worker thread, keeping only the edits in statements which overlap `lines`.
+fn format_regions(
+	formatter: Arc<Formatter>,
+	path: PathBuf,
+	extension: Option<String>,
+	text: String,
+	lines: Vec<Range<usize>>,
+	timeout: Option<Duration>,
+	cancelled: Arc<AtomicBool>,
+) -> Result<FormatResult> {
+	let worker_path = path.clone();
+	run_on_worker(
+		formatter.worker_stack_size,
+		path,
+		timeout,
+		cancelled,
+		move |cancelled| {
+			let result = formatter.format_cancellable(
+				&worker_path,
+				extension.as_deref(),
+				text.clone(),
+				cancelled,
+			)?;
+			if !result.changed || result.skipped.is_some() {
+				return Ok(result);
+			}
+
+			let parsed_source = parse_new_text(&worker_path, extension.as_deref(), &text)?;
+			let formatted = apply_changed_regions(&parsed_source, &lines, &text, &result.text);
+			Ok(result.with_text(&text, formatted))
+		},
+	)
+}
+
+fn parse_new_text(path: &Path, extension: Option<&str>, text: &str) -> Result<ParsedSource> {
+	parse::parse_source(
+		path,
+		extension,
+		text.strip_prefix(parse::BOM).unwrap_or(text).into(),
+	)
+	.with_context(|| format!("Could not parse '{}'", path.display()))
+}
@@ -0,0 +1,68 @@
+use std::path::Path;
+
+/// Heuristics for skipping files that should not be formatted, checked before parsing.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SkipOptions {
+	/// Files larger than this many bytes are skipped.
+	pub max_file_size: Option<usize>,
+	/// Files whose lines are longer than this on average are treated as minified and skipped.
+	pub max_average_line_length: Option<usize>,
+	/// Skip `*.min.js` (and similar) files which end with a `sourceMappingURL` comment.
+	pub skip_minified_files: bool,
+}
+
+/// Why a file was skipped.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SkipReason {
+	Oversized,
+	Minified,
+}
+
+impl SkipReason {
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			SkipReason::Oversized => "oversized",
+			SkipReason::Minified => "minified",
+		}
+	}
+}
+
+/// Returns the reason the file should be skipped, if any.
+pub fn skip_reason(path: &Path, text: &str, options: &SkipOptions) -> Option<SkipReason> {
+	if let Some(max_file_size) = options.max_file_size
+		&& text.len() > max_file_size
+	{
+		return Some(SkipReason::Oversized);
+	}
+
+	if let Some(max_average_line_length) = options.max_average_line_length
+		&& text.chars().count() / text.lines().count().max(1) > max_average_line_length
+	{
+		return Some(SkipReason::Minified);
+	}
+
+	if options.skip_minified_files && has_minified_file_name(path) && has_source_map_trailer(text) {
+		return Some(SkipReason::Minified);
+	}
+
+	None
+}
+
+/// Whether the file name ends in `.min` before its extension, as in `app.min.js`.
+pub(crate) fn has_minified_file_name(path: &Path) -> bool {
+	match path.file_stem() {
+		Some(stem) => stem.to_string_lossy().to_lowercase().ends_with(".min"),
+		None => false,
+	}
+}
+
+fn has_source_map_trailer(text: &str) -> bool {
+	match text.lines().rev().find(|line| !line.trim().is_empty()) {
+		Some(line) => {
+			let line = line.trim_start();
+			(line.starts_with("//#") || line.starts_with("//@"))
+				&& line[3..].trim_start().starts_with("sourceMappingURL=")
+		}
+		None => false,
+	}
+}
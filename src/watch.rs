@@ -0,0 +1,293 @@
+//! Reformatting files as they are saved.
+
+use crate::files::{read_text, write_text};
+use crate::formatter::Formatter;
+use crate::globs::{glob_set, is_skipped_directory};
+use crate::parse::is_formattable;
+use crate::worker::format_on_worker;
+use anyhow::{Context, Result, bail};
+use globset::GlobSet;
+use notify::event::{AccessKind, AccessMode, ModifyKind};
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher as _};
+use std::collections::HashMap;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::time::{Duration, Instant};
+
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
+/// What happened to a file which changed while being watched.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WatchEventKind {
+	/// The file was reformatted and written back.
+	Formatted,
+	/// The file was already formatted.
+	Unchanged,
+	/// The file could not be read, formatted or written.
+	Failed,
+}
+
+impl WatchEventKind {
+	pub fn as_str(self) -> &'static str {
+		match self {
+			WatchEventKind::Formatted => "formatted",
+			WatchEventKind::Unchanged => "unchanged",
+			WatchEventKind::Failed => "failed",
+		}
+	}
+}
+
+/// A file which was formatted, or failed to format, after it changed.
+#[cfg_attr(
+	feature = "python",
+	pyclass(name = "WatchEvent", module = "_formate_js", frozen)
+)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WatchEvent {
+	/// The path to the file.
+	pub path: PathBuf,
+	pub kind: WatchEventKind,
+	/// Why the file failed to format.
+	pub error: Option<String>,
+}
+
+/// Options for watching files.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WatchOptions {
+	/// Glob patterns for the files in watched directories to format.
+	/// If empty, files with JavaScript and TypeScript extensions are formatted.
+	pub includes: Vec<String>,
+	/// Glob patterns for the files and directories to skip. Patterns without a `/` match names anywhere.
+	pub excludes: Vec<String>,
+	/// How long a file must go without changing before it is formatted.
+	pub debounce: Duration,
+	/// The maximum time to spend formatting each file.
+	pub timeout: Option<Duration>,
+}
+
+impl Default for WatchOptions {
+	fn default() -> Self {
+		WatchOptions {
+			includes: Vec::new(),
+			excludes: Vec::new(),
+			debounce: Duration::from_millis(100),
+			timeout: None,
+		}
+	}
+}
+
+/// Watches files and directories, formatting files in them once they stop changing.
+///
+/// Files are only formatted when [`Watcher::poll`] is called.
+pub struct Watcher {
+	formatter: Arc<Formatter>,
+	debounce: Duration,
+	timeout: Option<Duration>,
+	includes: Option<GlobSet>,
+	excludes: GlobSet,
+	/// The directories being watched.
+	directories: Vec<PathBuf>,
+	/// The files being watched, which are formatted whatever the include and exclude patterns say.
+	files: Vec<PathBuf>,
+	events: Receiver<notify::Result<Event>>,
+	/// Files which have changed, and when they last changed.
+	pending: HashMap<PathBuf, Instant>,
+	/// Hashes of the text last written to each file, so that the watcher's own writes are ignored.
+	written: HashMap<PathBuf, u64>,
+	_watcher: RecommendedWatcher,
+}
+
+fn hash_text(text: &str) -> u64 {
+	let mut hasher = DefaultHasher::new();
+	text.hash(&mut hasher);
+	hasher.finish()
+}
+
+impl Watcher {
+	/// Starts watching `paths`. Directories are watched recursively.
+	pub fn new(
+		paths: &[PathBuf],
+		formatter: Arc<Formatter>,
+		options: &WatchOptions,
+	) -> Result<Self> {
+		let (sender, events) = mpsc::channel();
+		let mut watcher = notify::recommended_watcher(sender)?;
+		let mut directories = Vec::new();
+		let mut files = Vec::new();
+		for path in paths {
+			let path = path
+				.canonicalize()
+				.with_context(|| format!("Could not watch '{}'", path.display()))?;
+			if path.is_dir() {
+				watcher
+					.watch(&path, RecursiveMode::Recursive)
+					.with_context(|| format!("Could not watch '{}'", path.display()))?;
+				directories.push(path);
+			} else {
+				// Watch the directory, since editors often save by replacing the file.
+				let parent = path.parent().unwrap_or(&path);
+				watcher
+					.watch(parent, RecursiveMode::NonRecursive)
+					.with_context(|| format!("Could not watch '{}'", path.display()))?;
+				files.push(path);
+			}
+		}
+
+		Ok(Watcher {
+			formatter,
+			debounce: options.debounce,
+			timeout: options.timeout,
+			includes: if options.includes.is_empty() {
+				None
+			} else {
+				Some(glob_set(&options.includes)?)
+			},
+			excludes: glob_set(&options.excludes)?,
+			directories,
+			files,
+			events,
+			pending: HashMap::new(),
+			written: HashMap::new(),
+			_watcher: watcher,
+		})
+	}
+
+	/// Whether a changed file should be formatted.
+	fn is_watched(&self, path: &Path) -> bool {
+		if self.files.iter().any(|file| file == path) {
+			return true;
+		}
+		self.directories.iter().any(|directory| {
+			let Ok(relative) = path.strip_prefix(directory) else {
+				return false;
+			};
+			let skipped = relative.parent().is_some_and(|parent| {
+				parent
+					.components()
+					.any(|component| is_skipped_directory(component.as_os_str(), true))
+			});
+			!skipped
+				&& !self.excludes.is_match(relative)
+				&& match &self.includes {
+					Some(includes) => includes.is_match(relative),
+					None => is_formattable(relative),
+				}
+		})
+	}
+
+	fn record(&mut self, event: Event) {
+		let is_write = matches!(
+			event.kind,
+			EventKind::Create(_)
+				| EventKind::Modify(ModifyKind::Any | ModifyKind::Data(_) | ModifyKind::Name(_))
+				| EventKind::Access(AccessKind::Close(AccessMode::Write))
+		);
+		if !is_write {
+			return;
+		}
+		let now = Instant::now();
+		for path in event.paths {
+			if self.is_watched(&path) {
+				self.pending.insert(path, now);
+			}
+		}
+	}
+
+	/// Waits up to `wait` for files to change, and formats those which have stopped changing.
+	///
+	/// Returns as soon as any file has been formatted or failed to format.
+	/// Files which are unchanged since the watcher last wrote them are skipped without an event.
+	pub fn poll(&mut self, wait: Duration) -> Result<Vec<WatchEvent>> {
+		let deadline = Instant::now() + wait;
+		loop {
+			let settled = self
+				.pending
+				.values()
+				.min()
+				.map_or(deadline, |changed| deadline.min(*changed + self.debounce));
+			match self
+				.events
+				.recv_timeout(settled.saturating_duration_since(Instant::now()))
+			{
+				Ok(event) => self.record(event?),
+				Err(RecvTimeoutError::Timeout) => {}
+				Err(RecvTimeoutError::Disconnected) => bail!("The file watcher stopped"),
+			}
+
+			let events = self.format_settled();
+			if !events.is_empty() || Instant::now() >= deadline {
+				return Ok(events);
+			}
+		}
+	}
+
+	fn format_settled(&mut self) -> Vec<WatchEvent> {
+		let now = Instant::now();
+		let mut settled = self
+			.pending
+			.iter()
+			.filter(|(_, changed)| now.duration_since(**changed) >= self.debounce)
+			.map(|(path, _)| path.clone())
+			.collect::<Vec<_>>();
+		settled.sort();
+		settled
+			.into_iter()
+			.filter_map(|path| {
+				self.pending.remove(&path);
+				self.format_file(path)
+			})
+			.collect()
+	}
+
+	fn format_file(&mut self, path: PathBuf) -> Option<WatchEvent> {
+		// The file may have been deleted or renamed since it changed.
+		if !path.is_file() {
+			return None;
+		}
+		let failed = |path, error: String| WatchEvent {
+			path,
+			kind: WatchEventKind::Failed,
+			error: Some(error),
+		};
+
+		// Files are read and written as the command line and `format_file` do, keeping their encoding
+		// and permissions, and are replaced atomically so an editor never reads one half written.
+		let (text, encoding) = match read_text(&path) {
+			Ok(file) => file,
+			Err(error) => return Some(failed(path, format!("{:#}", error))),
+		};
+		if self.written.get(&path) == Some(&hash_text(&text)) {
+			return None;
+		}
+
+		let result = match format_on_worker(
+			self.formatter.clone(),
+			path.clone(),
+			None,
+			text,
+			self.timeout,
+		) {
+			Ok(result) => result,
+			Err(error) => return Some(failed(path, format!("{:#}", error))),
+		};
+		if !result.changed {
+			return Some(WatchEvent {
+				path,
+				kind: WatchEventKind::Unchanged,
+				error: None,
+			});
+		}
+		if let Err(error) = write_text(&path, &result.text, encoding, None) {
+			return Some(failed(path, format!("{:#}", error)));
+		}
+		self.written.insert(path.clone(), hash_text(&result.text));
+		Some(WatchEvent {
+			path,
+			kind: WatchEventKind::Formatted,
+			error: None,
+		})
+	}
+}
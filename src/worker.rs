@@ -0,0 +1,382 @@
+use crate::edits::TextEdit;
+use crate::format_result::FormatResult;
+use crate::formatter::Formatter;
+use crate::mapping::OffsetEncoding;
+use std::fmt;
+use std::io;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicU8, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError, mpsc};
+use std::thread;
+use std::time::Duration;
+
+/// How long a worker thread waits for another job before exiting.
+const IDLE_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// The most jobs which timed out which may still be running in the background.
+///
+/// Printing cannot be interrupted, so a job which timed out keeps its thread busy until it finishes.
+/// Once this many are, new jobs are refused with [`TooManyTimedOutError`] rather than piling up.
+pub const MAX_TIMED_OUT_JOBS: usize = 8;
+
+/// The number of jobs which timed out and are still running.
+static TIMED_OUT_JOBS: AtomicUsize = AtomicUsize::new(0);
+
+const RUNNING: u8 = 0;
+const FINISHED: u8 = 1;
+const TIMED_OUT: u8 = 2;
+
+/// Marks a job as finished when dropped, even if it panicked, no longer counting it as timed out if it was.
+struct JobState(Arc<AtomicU8>);
+
+impl Drop for JobState {
+	fn drop(&mut self) {
+		if self.0.swap(FINISHED, Ordering::SeqCst) == TIMED_OUT {
+			TIMED_OUT_JOBS.fetch_sub(1, Ordering::SeqCst);
+		}
+	}
+}
+
+type Job = Box<dyn FnOnce() + Send>;
+
+/// A worker thread waiting for a job.
+struct IdleWorker {
+	id: u64,
+	stack_size: usize,
+	jobs: mpsc::Sender<Job>,
+}
+
+/// The worker threads waiting for jobs.
+///
+/// Creating a thread with a stack large enough for deeply nested files takes longer than formatting
+/// a small file, so threads are kept after finishing a job to run later ones.
+struct Pool {
+	/// The process the workers belong to, as a child process does not inherit the threads after a fork.
+	process_id: u32,
+	idle: Vec<IdleWorker>,
+	next_id: u64,
+}
+
+static POOL: Mutex<Pool> = Mutex::new(Pool {
+	process_id: 0,
+	idle: Vec::new(),
+	next_id: 0,
+});
+
+fn pool() -> MutexGuard<'static, Pool> {
+	let mut pool = POOL.lock().unwrap_or_else(PoisonError::into_inner);
+	if pool.process_id != std::process::id() {
+		pool.process_id = std::process::id();
+		pool.idle.clear();
+	}
+	pool
+}
+
+/// Runs `job` on an idle worker thread with a stack of `stack_size` bytes, or on a new one if there are none.
+fn spawn_pooled(stack_size: usize, mut job: Job) -> io::Result<()> {
+	loop {
+		let worker = {
+			let mut pool = pool();
+			let index = pool
+				.idle
+				.iter()
+				.rposition(|worker| worker.stack_size == stack_size);
+			index.map(|index| pool.idle.swap_remove(index))
+		};
+		let Some(worker) = worker else {
+			break;
+		};
+		match worker.jobs.send(job) {
+			Ok(()) => return Ok(()),
+			// The thread died, so try another.
+			Err(mpsc::SendError(returned)) => job = returned,
+		}
+	}
+
+	let id = {
+		let mut pool = pool();
+		pool.next_id += 1;
+		pool.next_id
+	};
+	let (sender, receiver) = mpsc::channel::<Job>();
+	thread::Builder::new()
+		.name("formate-js-worker".to_string())
+		.stack_size(stack_size)
+		.spawn(move || {
+			let mut job = job;
+			loop {
+				job();
+				pool().idle.push(IdleWorker {
+					id,
+					stack_size,
+					jobs: sender.clone(),
+				});
+				job = loop {
+					if let Ok(job) = receiver.recv_timeout(IDLE_TIMEOUT) {
+						break job;
+					}
+					let mut pool = pool();
+					if let Some(index) = pool.idle.iter().position(|worker| worker.id == id) {
+						pool.idle.swap_remove(index);
+						return;
+					}
+					// The worker was taken just as it timed out, so a job is on its way.
+				};
+			}
+		})?;
+	Ok(())
+}
+
+/// Runs `job` on a pooled worker thread with a stack of `stack_size` bytes,
+/// returning a receiver for its result, or for the panic if it panicked.
+fn spawn_on_worker<T: Send + 'static>(
+	stack_size: usize,
+	job: impl FnOnce() -> T + Send + 'static,
+) -> io::Result<mpsc::Receiver<thread::Result<T>>> {
+	let (sender, receiver) = mpsc::channel();
+	spawn_pooled(
+		stack_size,
+		Box::new(move || {
+			// The receiver is gone if the caller already timed out.
+			let _ = sender.send(panic::catch_unwind(AssertUnwindSafe(job)));
+		}),
+	)?;
+	Ok(receiver)
+}
+
+/// Returned when formatting did not finish before the deadline.
+#[derive(Debug)]
+pub struct FormatTimeoutError {
+	pub path: PathBuf,
+	pub timeout: Duration,
+}
+
+impl fmt::Display for FormatTimeoutError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(
+			f,
+			"Formatting '{}' did not finish within {:.3}s",
+			self.path.display(),
+			self.timeout.as_secs_f64()
+		)
+	}
+}
+
+impl std::error::Error for FormatTimeoutError {}
+
+/// Returned when formatting stopped early because it was cancelled.
+#[derive(Debug)]
+pub struct FormatCancelledError;
+
+impl fmt::Display for FormatCancelledError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "Formatting was cancelled")
+	}
+}
+
+impl std::error::Error for FormatCancelledError {}
+
+/// Returned instead of starting a job while [`MAX_TIMED_OUT_JOBS`] jobs which timed out are still running.
+#[derive(Debug)]
+pub struct TooManyTimedOutError {
+	pub path: PathBuf,
+}
+
+impl fmt::Display for TooManyTimedOutError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(
+			f,
+			"Not formatting '{}', as {} files which timed out are still being formatted in the background",
+			self.path.display(),
+			MAX_TIMED_OUT_JOBS
+		)
+	}
+}
+
+impl std::error::Error for TooManyTimedOutError {}
+
+/// Returned when the worker thread died without producing a result.
+#[derive(Debug)]
+pub struct WorkerCrashedError {
+	pub path: PathBuf,
+}
+
+impl fmt::Display for WorkerCrashedError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(
+			f,
+			"The formatter crashed while formatting '{}'",
+			self.path.display()
+		)
+	}
+}
+
+impl std::error::Error for WorkerCrashedError {}
+
+/// Runs `job` on a worker thread with a stack of `stack_size` bytes, giving up once `timeout` has passed,
+/// if there is one.
+///
+/// Worker threads are kept to run later jobs. On timeout, `cancelled` is set, but the job only stops at
+/// its next check of it, so it may go on running in the background until it finishes; its result is
+/// discarded. While [`MAX_TIMED_OUT_JOBS`] are, [`TooManyTimedOutError`] is returned without starting `job`.
+/// A panic in `job` is returned as a [`WorkerCrashedError`] for `path`.
+pub(crate) fn run_on_worker<T: Send + 'static>(
+	stack_size: usize,
+	path: PathBuf,
+	timeout: Option<Duration>,
+	cancelled: Arc<AtomicBool>,
+	job: impl FnOnce(&AtomicBool) -> anyhow::Result<T> + Send + 'static,
+) -> anyhow::Result<T> {
+	if TIMED_OUT_JOBS.load(Ordering::SeqCst) >= MAX_TIMED_OUT_JOBS {
+		return Err(TooManyTimedOutError { path }.into());
+	}
+
+	let state = Arc::new(AtomicU8::new(RUNNING));
+	let job_state = JobState(state.clone());
+	let worker_cancelled = cancelled.clone();
+	let receiver = spawn_on_worker(stack_size, move || {
+		let _job_state = job_state;
+		job(&worker_cancelled)
+	})?;
+
+	let result = match timeout {
+		Some(timeout) => match receiver.recv_timeout(timeout) {
+			Ok(result) => Some(result),
+			Err(mpsc::RecvTimeoutError::Disconnected) => None,
+			Err(mpsc::RecvTimeoutError::Timeout) => {
+				cancelled.store(true, Ordering::Relaxed);
+				// Counted first, so finishing just now cannot take the count below zero.
+				TIMED_OUT_JOBS.fetch_add(1, Ordering::SeqCst);
+				if state
+					.compare_exchange(RUNNING, TIMED_OUT, Ordering::SeqCst, Ordering::SeqCst)
+					.is_err()
+				{
+					TIMED_OUT_JOBS.fetch_sub(1, Ordering::SeqCst);
+				}
+				return Err(FormatTimeoutError { path, timeout }.into());
+			}
+		},
+		None => receiver.recv().ok(),
+	};
+	match result {
+		Some(Ok(result)) => result,
+		Some(Err(_)) | None => Err(WorkerCrashedError { path }.into()),
+	}
+}
+
+/// Calls `job` with each item on pooled worker threads with a stack of `stack_size` bytes,
+/// as many at once as there are CPUs, returning the results in the same order as `items`.
+pub(crate) fn map_on_workers<I, T>(
+	stack_size: usize,
+	items: Vec<I>,
+	job: impl Fn(I) -> T + Send + Sync + 'static,
+) -> Vec<T>
+where
+	I: Send + 'static,
+	T: Send + 'static,
+{
+	let threads = thread::available_parallelism()
+		.map(|threads| threads.get())
+		.unwrap_or(1)
+		.min(items.len());
+
+	let queue = Arc::new(Mutex::new(items.into_iter().enumerate()));
+	let job = Arc::new(job);
+	let receivers = (0..threads)
+		.map(|_| {
+			let queue = queue.clone();
+			let job = job.clone();
+			spawn_on_worker(stack_size, move || {
+				let mut results = Vec::new();
+				loop {
+					let Some((index, item)) = queue.lock().unwrap().next() else {
+						break results;
+					};
+					results.push((index, job(item)));
+				}
+			})
+			.expect("failed to spawn formatting thread")
+		})
+		.collect::<Vec<_>>();
+
+	let mut results = receivers
+		.into_iter()
+		.flat_map(|receiver| match receiver.recv() {
+			Ok(Ok(results)) => results,
+			Ok(Err(payload)) => panic::resume_unwind(payload),
+			Err(_) => panic!("formatting thread died"),
+		})
+		.collect::<Vec<_>>();
+	results.sort_by_key(|(index, _)| *index);
+	results.into_iter().map(|(_, result)| result).collect()
+}
+
+/// Formats a file on a worker thread with a stack of [`Formatter::worker_stack_size`] bytes,
+/// giving up once `timeout` has passed, if there is one.
+///
+/// Deeply nested files need more stack than the caller's thread may have.
+/// dprint cannot be interrupted, so on timeout the worker is told to stop at its next
+/// cancellation check and is left to finish in the background; its result is discarded.
+/// A timeout therefore does not stop the work, and returns as soon as the time is up.
+/// See [`MAX_TIMED_OUT_JOBS`] for how many such files may be formatted in the background at once.
+pub fn format_on_worker(
+	formatter: Arc<Formatter>,
+	path: PathBuf,
+	extension: Option<String>,
+	text: String,
+	timeout: Option<Duration>,
+) -> anyhow::Result<FormatResult> {
+	let cancelled = Arc::new(AtomicBool::new(false));
+	format_on_worker_cancellable(formatter, path, extension, text, timeout, cancelled)
+}
+
+/// Like [`format_on_worker`], but the worker also stops at its next cancellation check once
+/// `cancelled` is set by the caller.
+pub fn format_on_worker_cancellable(
+	formatter: Arc<Formatter>,
+	path: PathBuf,
+	extension: Option<String>,
+	text: String,
+	timeout: Option<Duration>,
+	cancelled: Arc<AtomicBool>,
+) -> anyhow::Result<FormatResult> {
+	let worker_path = path.clone();
+	run_on_worker(
+		formatter.worker_stack_size,
+		path,
+		timeout,
+		cancelled,
+		move |cancelled| {
+			formatter.format_cancellable(&worker_path, extension.as_deref(), text, cancelled)
+		},
+	)
+}
+
+/// Like [`format_on_worker`], but returns the edits which turn `text` into the formatted text,
+/// counting offsets in `encoding`.
+pub fn format_edits_on_worker(
+	formatter: Arc<Formatter>,
+	path: PathBuf,
+	extension: Option<String>,
+	text: String,
+	timeout: Option<Duration>,
+	encoding: OffsetEncoding,
+) -> anyhow::Result<Vec<TextEdit>> {
+	let worker_path = path.clone();
+	run_on_worker(
+		formatter.worker_stack_size,
+		path,
+		timeout,
+		Arc::new(AtomicBool::new(false)),
+		move |cancelled| {
+			formatter.format_edits_cancellable(
+				&worker_path,
+				extension.as_deref(),
+				text,
+				encoding,
+				cancelled,
+			)
+		},
+	)
+}
@@ -0,0 +1,124 @@
+//! Skips files recorded in an on-disk cache as already formatted.
+
+mod common;
+
+use common::temp_dir;
+use formate_js::cache::Cache;
+use formate_js::configuration::ConfigurationOptions;
+use formate_js::files::{FileOptions, format_file};
+use formate_js::formatter::Formatter;
+use formate_js::project::{ProjectOptions, format_project};
+use std::path::Path;
+use std::sync::Arc;
+
+fn formatter(indent_width: u8) -> Arc<Formatter> {
+	Arc::new(
+		Formatter::new(&ConfigurationOptions {
+			indent_width,
+			..ConfigurationOptions::default()
+		})
+		.unwrap(),
+	)
+}
+
+#[test]
+fn entries_depend_on_configuration() {
+	let directory = temp_dir("cache_entries");
+	let a_ts = Path::new("a.ts");
+	let cache = Cache::open(&directory.join("cache"), &formatter(4)).unwrap();
+	assert!(!cache.contains(a_ts, b"let a = 1;\n"));
+	cache.insert(a_ts, b"let a = 1;\n").unwrap();
+	assert!(cache.contains(a_ts, b"let a = 1;\n"));
+	assert!(!cache.contains(a_ts, b"let a = 2;\n"));
+
+	let reopened = Cache::open(&directory.join("cache"), &formatter(4)).unwrap();
+	assert!(reopened.contains(a_ts, b"let a = 1;\n"));
+	let other = Cache::open(&directory.join("cache"), &formatter(2)).unwrap();
+	assert!(!other.contains(a_ts, b"let a = 1;\n"));
+
+	cache.clear().unwrap();
+	assert!(!reopened.contains(a_ts, b"let a = 1;\n"));
+}
+
+#[test]
+fn entries_depend_on_syntax_and_minified_names() {
+	let directory = temp_dir("cache_paths");
+	let cache = Cache::open(&directory.join("cache"), &formatter(4)).unwrap();
+	let contents = b"let a = <b>1</b>;\n";
+	cache.insert(Path::new("src/a.ts"), contents).unwrap();
+
+	assert!(cache.contains(Path::new("lib/b.ts"), contents));
+	assert!(!cache.contains(Path::new("src/a.tsx"), contents));
+	assert!(!cache.contains(Path::new("src/a.js"), contents));
+
+	cache.insert(Path::new("a.js"), contents).unwrap();
+	assert!(!cache.contains(Path::new("a.min.js"), contents));
+}
+
+#[test]
+fn external_formatters_are_not_cached() {
+	let directory = temp_dir("cache_external");
+	let mut formatter = (*formatter(4)).clone();
+	formatter.external_formatter = Some(Arc::new(|_, _, _| Ok(None)));
+	let cache = Cache::open(&directory.join("cache"), &formatter).unwrap();
+	let a_ts = Path::new("a.ts");
+
+	// What the external formatter does cannot be known, so nothing is recorded.
+	cache.insert(a_ts, b"let a = 1;\n").unwrap();
+	assert!(!cache.contains(a_ts, b"let a = 1;\n"));
+}
+
+#[test]
+fn cached_files_are_not_parsed() {
+	let directory = temp_dir("cache_files");
+	let formatter = formatter(4);
+	let options = FileOptions {
+		write: true,
+		cache: Some(Arc::new(
+			Cache::open(&directory.join("cache"), &formatter).unwrap(),
+		)),
+		..FileOptions::default()
+	};
+
+	let path = directory.join("a.ts");
+	std::fs::write(&path, "let  a = 1\n").unwrap();
+	let result = format_file(formatter.clone(), &path, &options).unwrap();
+	assert!(result.changed);
+	// The written contents were recorded, so formatting again does not parse the file.
+	let result = format_file(formatter.clone(), &path, &options).unwrap();
+	assert!(!result.changed);
+	assert_eq!(result.parse_duration, 0.0);
+
+	// A file which fails to parse is never recorded, so a recorded one is trusted without parsing.
+	let broken = directory.join("broken.ts");
+	std::fs::write(&broken, "let b = ;\n").unwrap();
+	assert!(format_file(formatter.clone(), &broken, &options).is_err());
+	options
+		.cache
+		.as_ref()
+		.unwrap()
+		.insert(&broken, b"let b = ;\n")
+		.unwrap();
+	assert!(!format_file(formatter, &broken, &options).unwrap().changed);
+}
+
+#[test]
+fn project_with_cache() {
+	let directory = temp_dir("cache_project");
+	std::fs::create_dir(directory.join("src")).unwrap();
+	std::fs::write(directory.join("src/a.ts"), "let  a = 1\n").unwrap();
+	std::fs::write(directory.join("src/b.ts"), "let b = 2;\n").unwrap();
+	let formatter = formatter(4);
+	let options = ProjectOptions {
+		cache: Some(Arc::new(
+			Cache::open(&directory.join("cache"), &formatter).unwrap(),
+		)),
+		..ProjectOptions::default()
+	};
+
+	let summary = format_project(&directory.join("src"), &formatter, &options).unwrap();
+	assert_eq!(summary.changed.len(), 1);
+	let summary = format_project(&directory.join("src"), &formatter, &options).unwrap();
+	assert_eq!(summary.changed.len(), 0);
+	assert_eq!(summary.unchanged.len(), 2);
+}
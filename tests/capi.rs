@@ -0,0 +1,58 @@
+//! Compiles and runs the C test program against the library.
+#![cfg(feature = "capi")]
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+#[test]
+fn c_test_program() {
+	let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+	// The library is built into `target/<profile>/deps`, next to the test executable.
+	let library_dir = std::env::current_exe()
+		.unwrap()
+		.parent()
+		.map(PathBuf::from)
+		.unwrap();
+	let executable = library_dir.join("test_capi");
+
+	let compiler = std::env::var("CC").unwrap_or_else(|_| "cc".to_string());
+	let status = Command::new(compiler)
+		.arg(manifest_dir.join("tests/capi/test_capi.c"))
+		.arg("-I")
+		.arg(manifest_dir.join("include"))
+		.arg("-L")
+		.arg(&library_dir)
+		.arg("-lformate_js")
+		.arg("-o")
+		.arg(&executable)
+		.status()
+		.expect("could not run the C compiler");
+	assert!(status.success(), "compiling the C test program failed");
+
+	let output = Command::new(&executable)
+		.env(
+			"LD_LIBRARY_PATH",
+			search_path(&library_dir, "LD_LIBRARY_PATH"),
+		)
+		.env(
+			"DYLD_LIBRARY_PATH",
+			search_path(&library_dir, "DYLD_LIBRARY_PATH"),
+		)
+		.output()
+		.unwrap();
+	assert!(
+		output.status.success(),
+		"{}",
+		String::from_utf8_lossy(&output.stderr)
+	);
+	assert_eq!(String::from_utf8_lossy(&output.stdout), "ok\n");
+}
+
+/// Puts the library directory in front of the existing search path, which may be needed to find libpython.
+fn search_path(library_dir: &Path, variable: &str) -> std::ffi::OsString {
+	let mut paths = vec![library_dir.to_path_buf()];
+	if let Some(existing) = std::env::var_os(variable) {
+		paths.extend(std::env::split_paths(&existing));
+	}
+	std::env::join_paths(paths).unwrap()
+}
@@ -0,0 +1,82 @@
+// Exercises the C interface. Exits with a non-zero status on the first failed check.
+#include <stdio.h>
+#include <string.h>
+
+#include "formate_js.h"
+
+#define CHECK(condition)                                                        \
+	do {                                                                        \
+		if (!(condition)) {                                                     \
+			fprintf(stderr, "%s:%d: check failed: %s\n", __FILE__, __LINE__, #condition); \
+			return 1;                                                           \
+		}                                                                       \
+	} while (0)
+
+static int test_format(void) {
+	FormateJsConfig *config = formate_js_config_new("{\"quote_style\": \"alwaysSingle\", \"line_width\": 40}");
+	CHECK(config != NULL);
+	CHECK(formate_js_last_error() == NULL);
+
+	const char *source = "const  x = \"hello\"";
+	size_t len = 0;
+	char *formatted = formate_js_format(config, "code.ts", source, strlen(source), &len);
+	CHECK(formatted != NULL);
+	CHECK(strcmp(formatted, "const x = 'hello';\n") == 0);
+	CHECK(len == strlen(formatted));
+	formate_js_string_free(formatted);
+
+	formate_js_config_free(config);
+	return 0;
+}
+
+static int test_default_config(void) {
+	FormateJsConfig *config = formate_js_config_new(NULL);
+	CHECK(config != NULL);
+
+	const char *source = "let  x=1";
+	char *formatted = formate_js_format(config, "code.js", source, strlen(source), NULL);
+	CHECK(formatted != NULL);
+	CHECK(strcmp(formatted, "let x = 1;\n") == 0);
+	formate_js_string_free(formatted);
+
+	formate_js_config_free(config);
+	return 0;
+}
+
+static int test_syntax_error(void) {
+	FormateJsConfig *config = formate_js_config_new(NULL);
+	CHECK(config != NULL);
+
+	const char *source = "let x = 1;\nlet y = ;\n";
+	char *formatted = formate_js_format(config, "code.js", source, strlen(source), NULL);
+	CHECK(formatted == NULL);
+	CHECK(formate_js_last_error() != NULL);
+	CHECK(strstr(formate_js_last_error(), "Expression expected") != NULL);
+	CHECK(formate_js_last_error_line() == 2);
+	CHECK(formate_js_last_error_column() == 9);
+
+	formate_js_config_free(config);
+	return 0;
+}
+
+static int test_config_errors(void) {
+	CHECK(formate_js_config_new("{\n  \"line_width\": \"wide\"\n}") == NULL);
+	CHECK(strstr(formate_js_last_error(), "invalid type") != NULL);
+	CHECK(formate_js_last_error_line() == 2);
+
+	CHECK(formate_js_config_new("{\"no_such_option\": 1}") == NULL);
+	CHECK(strstr(formate_js_last_error(), "unknown field `no_such_option`") != NULL);
+
+	CHECK(formate_js_config_new("{\"quote_style\": \"sometimes\"}") == NULL);
+	CHECK(strcmp(formate_js_last_error(), "Invalid enum value 'sometimes'") == 0);
+	CHECK(formate_js_last_error_line() == 0);
+	return 0;
+}
+
+int main(void) {
+	if (test_format() || test_default_config() || test_syntax_error() || test_config_errors()) {
+		return 1;
+	}
+	printf("ok\n");
+	return 0;
+}
@@ -0,0 +1,272 @@
+//! Runs the `formate-js` binary on files in a temporary directory.
+
+mod common;
+
+use common::project;
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+use std::process::{Command, Output, Stdio};
+
+const UNFORMATTED: &str = "let  a = \"b\"\n";
+const FORMATTED: &str = "let a = 'b';\n";
+
+fn formate_js(directory: &Path, args: &[&str], stdin: &str) -> Output {
+	let mut child = Command::new(env!("CARGO_BIN_EXE_formate-js"))
+		.args(args)
+		.current_dir(directory)
+		.stdin(Stdio::piped())
+		.stdout(Stdio::piped())
+		.stderr(Stdio::piped())
+		.spawn()
+		.unwrap();
+	child
+		.stdin
+		.take()
+		.unwrap()
+		.write_all(stdin.as_bytes())
+		.unwrap();
+	child.wait_with_output().unwrap()
+}
+
+fn read(directory: &Path, path: &str) -> String {
+	std::fs::read_to_string(directory.join(path)).unwrap()
+}
+
+const FORMATE_TOML: &str = "[hooks.format_js]\npriority = 50\n\n[hooks.format_js.kwargs]\nquote_style = \"alwaysSingle\"\n";
+
+#[test]
+fn format_in_place() {
+	let directory = project(
+		"cli_in_place",
+		&[
+			("formate.toml", FORMATE_TOML),
+			("src/a.ts", UNFORMATTED),
+			("src/b.js", FORMATTED),
+			("src/generated/c.ts", UNFORMATTED),
+			("src/notes.txt", UNFORMATTED),
+			("node_modules/d.js", UNFORMATTED),
+		],
+	);
+
+	let output = formate_js(&directory, &["--exclude", "generated"], "");
+	assert_eq!(output.status.code(), Some(0));
+	assert_eq!(
+		String::from_utf8(output.stderr).unwrap(),
+		"Reformatted ./src/a.ts\n1 file reformatted, 1 file left unchanged\n"
+	);
+	assert_eq!(read(&directory, "src/a.ts"), FORMATTED);
+	assert_eq!(read(&directory, "src/generated/c.ts"), UNFORMATTED);
+	assert_eq!(read(&directory, "src/notes.txt"), UNFORMATTED);
+	assert_eq!(read(&directory, "node_modules/d.js"), UNFORMATTED);
+}
+
+#[test]
+fn ignore_files() {
+	let directory = project(
+		"cli_ignore_files",
+		&[
+			("formate.toml", FORMATE_TOML),
+			(".gitignore", "dist/\n"),
+			("src/.ignore", "b.ts\n"),
+			("src/a.ts", UNFORMATTED),
+			("src/b.ts", UNFORMATTED),
+			("dist/c.js", UNFORMATTED),
+		],
+	);
+
+	let output = formate_js(&directory, &["src/*.ts"], "");
+	assert_eq!(output.status.code(), Some(0));
+	assert_eq!(read(&directory, "src/a.ts"), FORMATTED);
+	assert_eq!(read(&directory, "src/b.ts"), UNFORMATTED);
+
+	std::fs::write(directory.join("src/a.ts"), UNFORMATTED).unwrap();
+	let output = formate_js(&directory, &[], "");
+	assert_eq!(output.status.code(), Some(0));
+	assert_eq!(read(&directory, "src/a.ts"), FORMATTED);
+	assert_eq!(read(&directory, "src/b.ts"), UNFORMATTED);
+	assert_eq!(read(&directory, "dist/c.js"), UNFORMATTED);
+
+	// Files named explicitly are formatted even when ignored.
+	let output = formate_js(&directory, &["src/b.ts"], "");
+	assert_eq!(output.status.code(), Some(0));
+	assert_eq!(read(&directory, "src/b.ts"), FORMATTED);
+}
+
+#[test]
+fn check_and_diff() {
+	let directory = project(
+		"cli_check",
+		&[
+			("formate.toml", FORMATE_TOML),
+			("src/a.ts", UNFORMATTED),
+			("src/b.ts", FORMATTED),
+		],
+	);
+
+	let output = formate_js(&directory, &["--check", "src"], "");
+	assert_eq!(output.status.code(), Some(1));
+	assert!(
+		String::from_utf8(output.stderr)
+			.unwrap()
+			.starts_with("Would reformat src/a.ts\n")
+	);
+
+	let output = formate_js(&directory, &["--diff", "src/*.ts"], "");
+	assert_eq!(output.status.code(), Some(1));
+	assert_eq!(
+		String::from_utf8(output.stdout).unwrap(),
+		"--- src/a.ts\n+++ src/a.ts\n@@ -1 +1 @@\n-let  a = \"b\"\n+let a = 'b';\n"
+	);
+	assert_eq!(read(&directory, "src/a.ts"), UNFORMATTED);
+
+	let output = formate_js(&directory, &["--check", "src/b.ts"], "");
+	assert_eq!(output.status.code(), Some(0));
+
+	let output = formate_js(&directory, &["missing.ts"], "");
+	assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn explicit_files() {
+	let directory = project(
+		"cli_explicit_files",
+		&[
+			("formate.toml", FORMATE_TOML),
+			("a.ts", UNFORMATTED),
+			("notes.txt", UNFORMATTED),
+		],
+	);
+
+	// Files without JavaScript or TypeScript extensions are skipped even when named.
+	let output = formate_js(&directory, &["notes.txt", "a.ts"], "");
+	assert_eq!(output.status.code(), Some(0));
+	assert_eq!(read(&directory, "a.ts"), FORMATTED);
+	assert_eq!(read(&directory, "notes.txt"), UNFORMATTED);
+
+	std::fs::write(
+		directory.join("formate.toml"),
+		format!("{}timeout = -1\n", FORMATE_TOML),
+	)
+	.unwrap();
+	std::fs::write(directory.join("a.ts"), UNFORMATTED).unwrap();
+	let output = formate_js(&directory, &["a.ts"], "");
+	assert_eq!(output.status.code(), Some(2));
+	assert!(
+		String::from_utf8(output.stderr)
+			.unwrap()
+			.contains("'timeout' must be a non-negative number of seconds")
+	);
+	assert_eq!(read(&directory, "a.ts"), UNFORMATTED);
+}
+
+#[test]
+fn stdin_with_dprint_config() {
+	let directory = project(
+		"cli_stdin",
+		&[(
+			"dprint.jsonc",
+			"{\n  // Shared with dprint.\n  \"indentWidth\": 4,\n  \"formateJs\": {\n    \"quoteStyle\": \"alwaysSingle\",\n    \"colour\": \"red\", /* typo */\n  },\n}\n",
+		)],
+	);
+
+	let output = formate_js(
+		&directory,
+		&["--stdin-filepath", "src/component.tsx"],
+		"function f() {\nreturn <div class=\"x\">{\"y\"}</div>\n}\n",
+	);
+	assert_eq!(output.status.code(), Some(0));
+	assert_eq!(
+		String::from_utf8(output.stdout).unwrap(),
+		"function f() {\n    return <div class=\"x\">{'y'}</div>;\n}\n"
+	);
+	assert!(
+		String::from_utf8(output.stderr)
+			.unwrap()
+			.contains("Unknown property in configuration (colour)")
+	);
+
+	let output = formate_js(&directory, &["--stdin-filepath", "a.ts"], "let a = ;\n");
+	assert_eq!(output.status.code(), Some(2));
+	assert!(output.stdout.is_empty());
+}
+
+#[test]
+fn watch() {
+	let directory = project(
+		"cli_watch",
+		&[("formate.toml", FORMATE_TOML), ("src/a.ts", FORMATTED)],
+	);
+
+	let output = formate_js(&directory, &["--include", "*.ts", "src"], "");
+	assert_eq!(output.status.code(), Some(2));
+
+	let mut child = Command::new(env!("CARGO_BIN_EXE_formate-js"))
+		.args(["watch", "src"])
+		.current_dir(&directory)
+		.stderr(Stdio::piped())
+		.spawn()
+		.unwrap();
+	let mut stderr = BufReader::new(child.stderr.take().unwrap()).lines();
+	assert_eq!(stderr.next().unwrap().unwrap(), "Watching for changes");
+
+	std::fs::write(directory.join("src/a.ts"), UNFORMATTED).unwrap();
+	let line = stderr.next().unwrap().unwrap();
+	child.kill().unwrap();
+	child.wait().unwrap();
+	assert!(line.starts_with("Reformatted ") && line.ends_with("src/a.ts"));
+	assert_eq!(read(&directory, "src/a.ts"), FORMATTED);
+}
+
+fn git(directory: &Path, args: &[&str]) -> String {
+	let output = Command::new("git")
+		.args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+		.args(args)
+		.current_dir(directory)
+		.output()
+		.unwrap();
+	assert!(output.status.success(), "{:?}", output);
+	String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn git_staged_and_since() {
+	let directory = project(
+		"cli_git",
+		&[
+			("formate.toml", FORMATE_TOML),
+			("src/a.ts", FORMATTED),
+			("src/b.ts", FORMATTED),
+			("other/c.ts", FORMATTED),
+		],
+	);
+	git(&directory, &["init", "--quiet"]);
+	git(&directory, &["add", "."]);
+	git(&directory, &["commit", "--quiet", "-m", "Initial commit"]);
+
+	std::fs::write(directory.join("src/a.ts"), UNFORMATTED).unwrap();
+	std::fs::write(directory.join("other/c.ts"), UNFORMATTED).unwrap();
+	git(&directory, &["add", "."]);
+	std::fs::write(directory.join("src/b.ts"), UNFORMATTED).unwrap();
+
+	let output = formate_js(&directory, &["--staged", "--check"], "");
+	assert_eq!(output.status.code(), Some(1));
+	assert_eq!(git(&directory, &["show", ":src/a.ts"]), UNFORMATTED);
+
+	let output = formate_js(&directory, &["--staged", "--index-only", "src"], "");
+	assert_eq!(output.status.code(), Some(0));
+	assert_eq!(
+		String::from_utf8(output.stderr).unwrap(),
+		"Reformatted src/a.ts\n1 file reformatted, 0 files left unchanged\n"
+	);
+	assert_eq!(git(&directory, &["show", ":src/a.ts"]), FORMATTED);
+	assert_eq!(read(&directory, "src/a.ts"), UNFORMATTED);
+	assert_eq!(git(&directory, &["show", ":other/c.ts"]), UNFORMATTED);
+
+	let output = formate_js(&directory.join("src"), &["--since", "HEAD"], "");
+	assert_eq!(output.status.code(), Some(0));
+	assert_eq!(read(&directory, "src/b.ts"), FORMATTED);
+	assert_eq!(read(&directory, "other/c.ts"), FORMATTED);
+
+	let output = formate_js(&directory, &["--staged", "--since", "HEAD"], "");
+	assert_eq!(output.status.code(), Some(2));
+}
@@ -0,0 +1,31 @@
+//! Fixtures shared by the integration tests.
+// Each test crate uses only some of them.
+#![allow(dead_code)]
+
+use std::path::{Path, PathBuf};
+
+/// An empty directory named `name` in Cargo's temporary directory for integration tests,
+/// removing whatever an earlier run left there.
+pub fn temp_dir(name: &str) -> PathBuf {
+	let directory = Path::new(env!("CARGO_TARGET_TMPDIR")).join(name);
+	let _ = std::fs::remove_dir_all(&directory);
+	std::fs::create_dir_all(&directory).unwrap();
+	directory
+}
+
+/// Writes `files`, given as their paths relative to `directory` and their contents,
+/// creating the directories they are in.
+pub fn write_files(directory: &Path, files: &[(&str, &str)]) {
+	for (path, text) in files {
+		let path = directory.join(path);
+		std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+		std::fs::write(path, text).unwrap();
+	}
+}
+
+/// A [`temp_dir`] holding `files`.
+pub fn project(name: &str, files: &[(&str, &str)]) -> PathBuf {
+	let directory = temp_dir(name);
+	write_files(&directory, files);
+	directory
+}
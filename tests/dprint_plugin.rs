@@ -0,0 +1,581 @@
+//! Drives the dprint plugin binary with a mock of the dprint CLI.
+
+use anyhow::{Result, bail};
+use serde_json::{Value, json};
+use std::io::{BufReader, ErrorKind, Read, Write};
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+
+/// The version of the process plugin protocol the mock speaks.
+const SCHEMA_VERSION: u32 = 5;
+
+/// Every message ends with these bytes, to detect a plugin and host which disagree on the protocol.
+const SUCCESS_BYTES: [u8; 4] = [255; 4];
+
+/// A message between dprint and the plugin. Messages which answer another message give its id first.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Message {
+	Success(u32),
+	DataResponse(u32, Vec<u8>),
+	Error(u32, Vec<u8>),
+	Close,
+	IsAlive,
+	GetPluginInfo,
+	GetLicenseText,
+	RegisterConfig {
+		config_id: u32,
+		global_config: Vec<u8>,
+		plugin_config: Vec<u8>,
+	},
+	ReleaseConfig(u32),
+	GetConfigDiagnostics(u32),
+	GetFileMatchingInfo(u32),
+	GetResolvedConfig(u32),
+	CheckConfigUpdates(Vec<u8>),
+	Format {
+		file_path: String,
+		start: u32,
+		end: u32,
+		config_id: u32,
+		override_config: Vec<u8>,
+		file_text: Vec<u8>,
+	},
+	/// The formatted text, or `None` if it is unchanged.
+	FormatResponse(u32, Option<Vec<u8>>),
+	CancelFormat(u32),
+	HostFormat {
+		original_message_id: u32,
+		file_path: String,
+		start: u32,
+		end: u32,
+		override_config: Vec<u8>,
+		file_text: Vec<u8>,
+	},
+}
+
+pub fn read_u32(input: &mut impl Read) -> Result<u32> {
+	let mut bytes = [0; 4];
+	input.read_exact(&mut bytes)?;
+	Ok(u32::from_be_bytes(bytes))
+}
+
+fn read_bytes(input: &mut impl Read) -> Result<Vec<u8>> {
+	let len = read_u32(input)? as usize;
+	let mut bytes = vec![0; len];
+	input.read_exact(&mut bytes)?;
+	Ok(bytes)
+}
+
+fn read_string(input: &mut impl Read) -> Result<String> {
+	Ok(String::from_utf8(read_bytes(input)?)?)
+}
+
+fn push_u32(buffer: &mut Vec<u8>, value: u32) {
+	buffer.extend(value.to_be_bytes());
+}
+
+fn push_bytes(buffer: &mut Vec<u8>, bytes: &[u8]) {
+	buffer.extend((bytes.len() as u32).to_be_bytes());
+	buffer.extend(bytes);
+}
+
+impl Message {
+	/// Reads a message and its id, or returns `None` at the end of the input.
+	pub fn read(input: &mut impl Read) -> Result<Option<(u32, Message)>> {
+		let id = match read_u32(input) {
+			Ok(id) => id,
+			Err(error)
+				if error
+					.downcast_ref::<std::io::Error>()
+					.is_some_and(|error| error.kind() == ErrorKind::UnexpectedEof) =>
+			{
+				return Ok(None);
+			}
+			Err(error) => return Err(error),
+		};
+
+		let message = match read_u32(input)? {
+			0 => Message::Success(read_u32(input)?),
+			1 => Message::DataResponse(read_u32(input)?, read_bytes(input)?),
+			2 => Message::Error(read_u32(input)?, read_bytes(input)?),
+			3 => Message::Close,
+			4 => Message::IsAlive,
+			5 => Message::GetPluginInfo,
+			6 => Message::GetLicenseText,
+			7 => Message::RegisterConfig {
+				config_id: read_u32(input)?,
+				global_config: read_bytes(input)?,
+				plugin_config: read_bytes(input)?,
+			},
+			8 => Message::ReleaseConfig(read_u32(input)?),
+			9 => Message::GetConfigDiagnostics(read_u32(input)?),
+			10 => Message::GetFileMatchingInfo(read_u32(input)?),
+			11 => Message::GetResolvedConfig(read_u32(input)?),
+			12 => Message::CheckConfigUpdates(read_bytes(input)?),
+			13 => Message::Format {
+				file_path: read_string(input)?,
+				start: read_u32(input)?,
+				end: read_u32(input)?,
+				config_id: read_u32(input)?,
+				override_config: read_bytes(input)?,
+				file_text: read_bytes(input)?,
+			},
+			14 => {
+				let message_id = read_u32(input)?;
+				let text = match read_u32(input)? {
+					0 => None,
+					1 => Some(read_bytes(input)?),
+					kind => bail!("Unknown format response kind {}", kind),
+				};
+				Message::FormatResponse(message_id, text)
+			}
+			15 => Message::CancelFormat(read_u32(input)?),
+			16 => Message::HostFormat {
+				original_message_id: read_u32(input)?,
+				file_path: read_string(input)?,
+				start: read_u32(input)?,
+				end: read_u32(input)?,
+				override_config: read_bytes(input)?,
+				file_text: read_bytes(input)?,
+			},
+			kind => bail!("Unknown message kind {}", kind),
+		};
+
+		if read_u32(input)?.to_be_bytes() != SUCCESS_BYTES {
+			bail!("Message {} did not end with the success bytes", id);
+		}
+		Ok(Some((id, message)))
+	}
+
+	/// Writes the message with an id, all at once.
+	pub fn write(&self, id: u32, output: &mut impl Write) -> Result<()> {
+		let mut buffer = Vec::new();
+		push_u32(&mut buffer, id);
+		match self {
+			Message::Success(message_id) => {
+				push_u32(&mut buffer, 0);
+				push_u32(&mut buffer, *message_id);
+			}
+			Message::DataResponse(message_id, data) => {
+				push_u32(&mut buffer, 1);
+				push_u32(&mut buffer, *message_id);
+				push_bytes(&mut buffer, data);
+			}
+			Message::Error(message_id, data) => {
+				push_u32(&mut buffer, 2);
+				push_u32(&mut buffer, *message_id);
+				push_bytes(&mut buffer, data);
+			}
+			Message::Close => push_u32(&mut buffer, 3),
+			Message::IsAlive => push_u32(&mut buffer, 4),
+			Message::GetPluginInfo => push_u32(&mut buffer, 5),
+			Message::GetLicenseText => push_u32(&mut buffer, 6),
+			Message::RegisterConfig {
+				config_id,
+				global_config,
+				plugin_config,
+			} => {
+				push_u32(&mut buffer, 7);
+				push_u32(&mut buffer, *config_id);
+				push_bytes(&mut buffer, global_config);
+				push_bytes(&mut buffer, plugin_config);
+			}
+			Message::ReleaseConfig(config_id) => {
+				push_u32(&mut buffer, 8);
+				push_u32(&mut buffer, *config_id);
+			}
+			Message::GetConfigDiagnostics(config_id) => {
+				push_u32(&mut buffer, 9);
+				push_u32(&mut buffer, *config_id);
+			}
+			Message::GetFileMatchingInfo(config_id) => {
+				push_u32(&mut buffer, 10);
+				push_u32(&mut buffer, *config_id);
+			}
+			Message::GetResolvedConfig(config_id) => {
+				push_u32(&mut buffer, 11);
+				push_u32(&mut buffer, *config_id);
+			}
+			Message::CheckConfigUpdates(data) => {
+				push_u32(&mut buffer, 12);
+				push_bytes(&mut buffer, data);
+			}
+			Message::Format {
+				file_path,
+				start,
+				end,
+				config_id,
+				override_config,
+				file_text,
+			} => {
+				push_u32(&mut buffer, 13);
+				push_bytes(&mut buffer, file_path.as_bytes());
+				push_u32(&mut buffer, *start);
+				push_u32(&mut buffer, *end);
+				push_u32(&mut buffer, *config_id);
+				push_bytes(&mut buffer, override_config);
+				push_bytes(&mut buffer, file_text);
+			}
+			Message::FormatResponse(message_id, text) => {
+				push_u32(&mut buffer, 14);
+				push_u32(&mut buffer, *message_id);
+				match text {
+					None => push_u32(&mut buffer, 0),
+					Some(text) => {
+						push_u32(&mut buffer, 1);
+						push_bytes(&mut buffer, text);
+					}
+				}
+			}
+			Message::CancelFormat(message_id) => {
+				push_u32(&mut buffer, 15);
+				push_u32(&mut buffer, *message_id);
+			}
+			Message::HostFormat {
+				original_message_id,
+				file_path,
+				start,
+				end,
+				override_config,
+				file_text,
+			} => {
+				push_u32(&mut buffer, 16);
+				push_u32(&mut buffer, *original_message_id);
+				push_bytes(&mut buffer, file_path.as_bytes());
+				push_u32(&mut buffer, *start);
+				push_u32(&mut buffer, *end);
+				push_bytes(&mut buffer, override_config);
+				push_bytes(&mut buffer, file_text);
+			}
+		}
+		buffer.extend(SUCCESS_BYTES);
+
+		output.write_all(&buffer)?;
+		output.flush()?;
+		Ok(())
+	}
+}
+
+struct Host {
+	child: Child,
+	stdin: ChildStdin,
+	stdout: BufReader<ChildStdout>,
+	next_id: u32,
+}
+
+impl Host {
+	fn start() -> Self {
+		let mut child = Command::new(env!("CARGO_BIN_EXE_formate-js-dprint-plugin"))
+			.args(["--parent-pid", &std::process::id().to_string()])
+			.stdin(Stdio::piped())
+			.stdout(Stdio::piped())
+			.spawn()
+			.unwrap();
+		let mut stdin = child.stdin.take().unwrap();
+		let mut stdout = BufReader::new(child.stdout.take().unwrap());
+
+		stdin.write_all(&0u32.to_be_bytes()).unwrap();
+		stdin.flush().unwrap();
+		assert_eq!(read_u32(&mut stdout).unwrap(), 0);
+		assert_eq!(read_u32(&mut stdout).unwrap(), SCHEMA_VERSION);
+
+		Host {
+			child,
+			stdin,
+			stdout,
+			next_id: 1,
+		}
+	}
+
+	fn send(&mut self, message: Message) -> u32 {
+		let id = self.next_id;
+		self.next_id += 1;
+		message.write(id, &mut self.stdin).unwrap();
+		id
+	}
+
+	fn receive(&mut self) -> Message {
+		Message::read(&mut self.stdout).unwrap().unwrap().1
+	}
+
+	fn data(&mut self, message: Message) -> Value {
+		let id = self.send(message);
+		match self.receive() {
+			Message::DataResponse(message_id, data) if message_id == id => {
+				serde_json::from_slice(&data).unwrap()
+			}
+			response => panic!("unexpected response {:?}", response),
+		}
+	}
+
+	fn register_config(&mut self, config_id: u32, global_config: Value, plugin_config: Value) {
+		let id = self.send(Message::RegisterConfig {
+			config_id,
+			global_config: global_config.to_string().into_bytes(),
+			plugin_config: plugin_config.to_string().into_bytes(),
+		});
+		assert_eq!(self.receive(), Message::Success(id));
+	}
+
+	fn format(&mut self, config_id: u32, override_config: Value, text: &str) -> u32 {
+		self.format_range(
+			config_id,
+			override_config,
+			text.as_bytes(),
+			0..text.len() as u32,
+		)
+	}
+
+	/// Asks for part of a file to be formatted, given as a byte range.
+	fn format_range(
+		&mut self,
+		config_id: u32,
+		override_config: Value,
+		text: &[u8],
+		range: std::ops::Range<u32>,
+	) -> u32 {
+		self.send(Message::Format {
+			file_path: "/project/src/file.ts".to_string(),
+			start: range.start,
+			end: range.end,
+			config_id,
+			override_config: override_config.to_string().into_bytes(),
+			file_text: text.to_vec(),
+		})
+	}
+
+	/// Receives the answer to a format request, returning the formatted text or `None` if it is unchanged.
+	fn formatted(&mut self, id: u32) -> Option<String> {
+		match self.receive() {
+			Message::FormatResponse(message_id, text) if message_id == id => {
+				text.map(|text| String::from_utf8(text).unwrap())
+			}
+			response => panic!("unexpected response {:?}", response),
+		}
+	}
+
+	/// Formats a file, returning the formatted text or `None` if it is unchanged.
+	fn format_text(
+		&mut self,
+		config_id: u32,
+		override_config: Value,
+		text: &str,
+	) -> Option<String> {
+		let id = self.format(config_id, override_config, text);
+		self.formatted(id)
+	}
+
+	/// Asks the plugin to close, and then closes its stdin as dprint does.
+	fn close(mut self) -> bool {
+		let id = self.send(Message::Close);
+		assert_eq!(self.receive(), Message::Success(id));
+		drop(self.stdin);
+		self.child.wait().unwrap().success()
+	}
+}
+
+#[test]
+fn plugin_info() {
+	let mut host = Host::start();
+	let id = host.send(Message::IsAlive);
+	assert_eq!(host.receive(), Message::Success(id));
+
+	let info = host.data(Message::GetPluginInfo);
+	assert_eq!(info["name"], "formate-js");
+	assert_eq!(info["configKey"], "formateJs");
+
+	let id = host.send(Message::GetLicenseText);
+	assert!(matches!(
+		host.receive(),
+		Message::DataResponse(message_id, text) if message_id == id && text.starts_with(b"Copyright")
+	));
+
+	assert!(host.close());
+}
+
+#[test]
+fn configuration() {
+	let mut host = Host::start();
+	host.register_config(
+		1,
+		json!({"lineWidth": 40, "indentWidth": 2, "useTabs": true}),
+		json!({
+			"quoteStyle": "alwaysSingle",
+			"arrowFunction.useParentheses": "force",
+			"ignore_file_comment_aliases": ["no-format"],
+			"useTabs": false,
+			"semiColons": "sometimes",
+			"colour": "red",
+		}),
+	);
+
+	let diagnostics = host.data(Message::GetConfigDiagnostics(1));
+	assert_eq!(
+		diagnostics,
+		json!([
+			{"propertyName": "semiColons", "message": "Invalid enum value 'sometimes'"},
+			{"propertyName": "colour", "message": "Unknown property in configuration"},
+		])
+	);
+
+	let config = host.data(Message::GetResolvedConfig(1));
+	assert_eq!(config["line_width"], 40);
+	assert_eq!(config["indent_width"], 2);
+	assert_eq!(config["use_tabs"], false);
+	assert_eq!(config["quote_style"], "alwaysSingle");
+	assert_eq!(config["arrow_function_use_parentheses"], "force");
+	assert_eq!(config["ignore_file_comment_aliases"], json!(["no-format"]));
+
+	let matching = host.data(Message::GetFileMatchingInfo(1));
+	assert!(
+		matching["fileExtensions"]
+			.as_array()
+			.unwrap()
+			.contains(&json!("tsx"))
+	);
+
+	// A timeout of zero is accepted, as it is in formate.toml.
+	host.register_config(2, json!({}), json!({"timeout": 0}));
+	assert_eq!(host.data(Message::GetConfigDiagnostics(2)), json!([]));
+	host.register_config(3, json!({}), json!({"timeout": -1}));
+	assert_eq!(
+		host.data(Message::GetConfigDiagnostics(3)),
+		json!([{"propertyName": "timeout", "message": "Expected a non-negative number of seconds"}])
+	);
+
+	let id = host.send(Message::ReleaseConfig(1));
+	assert_eq!(host.receive(), Message::Success(id));
+	let id = host.send(Message::GetResolvedConfig(1));
+	assert!(matches!(host.receive(), Message::Error(message_id, _) if message_id == id));
+
+	assert!(host.close());
+}
+
+#[test]
+fn format() {
+	let mut host = Host::start();
+	host.register_config(
+		1,
+		json!({"indentWidth": 2}),
+		json!({"quoteStyle": "alwaysSingle"}),
+	);
+
+	assert_eq!(
+		host.format_text(1, json!({}), "const  f = x=>{ return \"a\" }\n")
+			.as_deref(),
+		Some("const f = x => {\n  return 'a';\n};\n")
+	);
+	assert_eq!(host.format_text(1, json!({}), "let a = 'b';\n"), None);
+	// The formate pragmas are honoured.
+	assert_eq!(
+		host.format_text(1, json!({}), "// formate-ignore-file\nlet  a = \"b\"\n"),
+		None
+	);
+	assert_eq!(
+		host.format_text(1, json!({"semiColons": "asi"}), "let a = 'b';\n")
+			.as_deref(),
+		Some("let a = 'b'\n")
+	);
+
+	let id = host.format(1, json!({}), "let a = ;\n");
+	assert!(matches!(host.receive(), Message::Error(message_id, _) if message_id == id));
+	let id = host.format(2, json!({}), "let a = 'b';\n");
+	assert!(matches!(host.receive(), Message::Error(message_id, _) if message_id == id));
+
+	assert!(host.close());
+}
+
+#[test]
+fn format_range() {
+	let mut host = Host::start();
+	host.register_config(1, json!({}), json!({}));
+
+	// Only the statement overlapping the range is formatted.
+	let text = "let  a = 1\nlet  b = 2\nlet  c = 3\n";
+	let start = text.find("b").unwrap() as u32;
+	let id = host.format_range(1, json!({}), text.as_bytes(), start..start + 1);
+	assert_eq!(
+		host.formatted(id).as_deref(),
+		Some("let  a = 1\nlet b = 2;\nlet  c = 3\n")
+	);
+
+	assert!(host.close());
+}
+
+#[test]
+fn format_invalid_utf8() {
+	let mut host = Host::start();
+	host.register_config(1, json!({}), json!({}));
+
+	// dprint decodes the file lossily.
+	let text = b"let  a = '\xff'\n";
+	let id = host.format_range(1, json!({}), text, 0..text.len() as u32);
+	assert_eq!(
+		host.formatted(id).as_deref(),
+		Some("let a = \"\u{fffd}\";\n")
+	);
+
+	assert!(host.close());
+}
+
+#[test]
+fn embedded_css() {
+	let mut host = Host::start();
+	host.register_config(1, json!({}), json!({"indentWidth": 2}));
+
+	let id = host.format(1, json!({}), "const style = css`a{color:red}`;\n");
+	let (
+		host_format_id,
+		Message::HostFormat {
+			original_message_id,
+			file_path,
+			override_config,
+			file_text,
+			..
+		},
+	) = Message::read(&mut host.stdout).unwrap().unwrap()
+	else {
+		panic!("expected the plugin to ask for the CSS to be formatted");
+	};
+	assert_eq!(original_message_id, id);
+	assert_eq!(file_path, "file.css");
+	assert_eq!(file_text, b"a{color:red}");
+	assert_eq!(
+		serde_json::from_slice::<Value>(&override_config).unwrap(),
+		json!({"indentWidth": 2, "useTabs": false})
+	);
+
+	host.send(Message::FormatResponse(
+		host_format_id,
+		Some(b"a {\n  color: red;\n}\n".to_vec()),
+	));
+	match host.receive() {
+		Message::FormatResponse(message_id, Some(text)) if message_id == id => assert_eq!(
+			String::from_utf8(text).unwrap(),
+			"const style = css`\n  a {\n    color: red;\n  }\n`;\n"
+		),
+		response => panic!("unexpected response {:?}", response),
+	}
+
+	assert!(host.close());
+}
+
+#[test]
+fn cancel_format() {
+	let mut host = Host::start();
+	host.register_config(1, json!({}), json!({}));
+
+	let id = host.format(1, json!({}), "const style = css`a{color:red}`;\n");
+	let (host_format_id, Message::HostFormat { .. }) =
+		Message::read(&mut host.stdout).unwrap().unwrap()
+	else {
+		panic!("expected the plugin to ask for the CSS to be formatted");
+	};
+
+	// The plugin stops waiting on the host, and does not answer the cancelled request.
+	host.send(Message::CancelFormat(id));
+	assert_eq!(host.receive(), Message::CancelFormat(host_format_id));
+	let id = host.send(Message::IsAlive);
+	assert_eq!(host.receive(), Message::Success(id));
+
+	assert!(host.close());
+}
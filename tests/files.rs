@@ -0,0 +1,108 @@
+//! Formats files on disk, keeping their encoding and permissions.
+
+mod common;
+
+use formate_js::configuration::ConfigurationOptions;
+use formate_js::files::{DecodeError, FileOptions, format_file};
+use formate_js::formatter::Formatter;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+fn file(name: &str, contents: &[u8]) -> PathBuf {
+	let path = common::temp_dir(&format!("files_{}", name)).join(name);
+	std::fs::write(&path, contents).unwrap();
+	path
+}
+
+fn formatter() -> Arc<Formatter> {
+	Arc::new(Formatter::new(&ConfigurationOptions::default()).unwrap())
+}
+
+fn write() -> FileOptions {
+	FileOptions {
+		write: true,
+		..FileOptions::default()
+	}
+}
+
+fn utf16_le(text: &str) -> Vec<u8> {
+	"\u{feff}"
+		.encode_utf16()
+		.chain(text.encode_utf16())
+		.flat_map(u16::to_le_bytes)
+		.collect()
+}
+
+#[test]
+fn byte_order_marks() {
+	let path = file("bom.ts", "\u{feff}let  a = 'é'\r\n".as_bytes());
+	let crlf = Formatter::new(&ConfigurationOptions {
+		new_line_kind: "auto".to_string(),
+		..ConfigurationOptions::default()
+	})
+	.unwrap();
+	let result = format_file(Arc::new(crlf), &path, &write()).unwrap();
+	assert_eq!(result.text, "let a = \"é\";\r\n");
+	assert_eq!(
+		std::fs::read(&path).unwrap(),
+		"\u{feff}let a = \"é\";\r\n".as_bytes()
+	);
+
+	let path = file("utf16.ts", &utf16_le("let  b = '日本'\n"));
+	format_file(formatter(), &path, &write()).unwrap();
+	assert_eq!(
+		std::fs::read(&path).unwrap(),
+		utf16_le("let b = \"日本\";\n")
+	);
+
+	let path = file("bom_unchanged.ts", "\u{feff}let d = 4;\n".as_bytes());
+	assert!(!format_file(formatter(), &path, &write()).unwrap().changed);
+
+	let path = file("latin1.ts", b"let c = '\xe9';\n");
+	let error = format_file(formatter(), &path, &write()).unwrap_err();
+	assert!(error.is::<DecodeError>());
+}
+
+#[test]
+fn unchanged_file_is_not_written() {
+	let path = file("unchanged.ts", b"let a = 1;\n");
+	let modified = std::fs::metadata(&path).unwrap().modified().unwrap();
+	let result = format_file(formatter(), &path, &write()).unwrap();
+	assert!(!result.changed);
+	assert_eq!(
+		std::fs::metadata(&path).unwrap().modified().unwrap(),
+		modified
+	);
+}
+
+#[test]
+fn dry_run_and_backup() {
+	let path = file("backup.ts", b"let  a = 1\n");
+	let result = format_file(formatter(), &path, &FileOptions::default()).unwrap();
+	assert!(result.changed);
+	assert_eq!(std::fs::read_to_string(&path).unwrap(), "let  a = 1\n");
+
+	let options = FileOptions {
+		backup_suffix: Some(".orig".to_string()),
+		..write()
+	};
+	format_file(formatter(), &path, &options).unwrap();
+	assert_eq!(std::fs::read_to_string(&path).unwrap(), "let a = 1;\n");
+	assert_eq!(
+		std::fs::read_to_string(path.with_extension("ts.orig")).unwrap(),
+		"let  a = 1\n"
+	);
+}
+
+#[cfg(unix)]
+#[test]
+fn permissions_are_kept() {
+	use std::os::unix::fs::PermissionsExt;
+
+	let path = file("script.js", b"console.log( 1 )\n");
+	std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o750)).unwrap();
+	format_file(formatter(), &path, &write()).unwrap();
+	assert_eq!(std::fs::read_to_string(&path).unwrap(), "console.log(1);\n");
+	let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+	assert_eq!(mode & 0o777, 0o750);
+}
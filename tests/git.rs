@@ -0,0 +1,155 @@
+//! Formats the files staged in a temporary git repository.
+
+mod common;
+
+use common::write_files;
+use formate_js::configuration::ConfigurationOptions;
+use formate_js::formatter::Formatter;
+use formate_js::git::{Repository, StagedOptions, WorktreeUpdate};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+fn git(directory: &Path, args: &[&str]) -> String {
+	let output = Command::new("git")
+		.args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+		.args(args)
+		.current_dir(directory)
+		.output()
+		.unwrap();
+	assert!(output.status.success(), "{:?}", output);
+	String::from_utf8(output.stdout).unwrap()
+}
+
+/// Creates a repository with the files committed.
+fn repository(name: &str, files: &[(&str, &str)]) -> PathBuf {
+	let directory = common::temp_dir(name);
+	git(&directory, &["init", "--quiet"]);
+	write_files(&directory, files);
+	git(&directory, &["add", "."]);
+	git(&directory, &["commit", "--quiet", "-m", "Initial commit"]);
+	directory
+}
+
+fn read(directory: &Path, path: &str) -> String {
+	std::fs::read_to_string(directory.join(path)).unwrap()
+}
+
+#[test]
+fn format_staged() {
+	let directory = repository(
+		"git_staged",
+		&[
+			("partial.ts", "let a = 1;\n\nlet b = 2;\n"),
+			("unstaged.ts", "let c = 3;\n"),
+		],
+	);
+	write_files(
+		&directory,
+		&[
+			("partial.ts", "let  a = 1\n\nlet b = 2;\n"),
+			("staged.js", "let  d = 4\n"),
+			("src/broken.ts", "let e = ;\n"),
+			("notes.txt", "let  f = 6\n"),
+		],
+	);
+	git(&directory, &["add", "."]);
+	// Unstaged changes which should be kept.
+	write_files(
+		&directory,
+		&[
+			("partial.ts", "let  a = 1\n\nlet b = 3;\n"),
+			("unstaged.ts", "let  c = 3\n"),
+		],
+	);
+
+	let repository = Repository::discover(&directory.join("src")).unwrap();
+	let formatter = Formatter::new(&ConfigurationOptions::default()).unwrap();
+	let results = repository
+		.format_staged(
+			&formatter,
+			&StagedOptions {
+				write: true,
+				update_worktree: true,
+				..StagedOptions::default()
+			},
+		)
+		.unwrap();
+
+	let summary = results
+		.iter()
+		.map(|staged| {
+			(
+				staged.file.path.to_str().unwrap(),
+				staged.result.as_ref().map(|result| result.changed).ok(),
+				staged.worktree,
+			)
+		})
+		.collect::<Vec<_>>();
+	assert_eq!(
+		summary,
+		[
+			("partial.ts", Some(true), WorktreeUpdate::Merged),
+			("src/broken.ts", None, WorktreeUpdate::Skipped),
+			("staged.js", Some(true), WorktreeUpdate::Written),
+		]
+	);
+
+	assert_eq!(
+		git(&directory, &["show", ":partial.ts"]),
+		"let a = 1;\n\nlet b = 2;\n"
+	);
+	assert_eq!(read(&directory, "partial.ts"), "let a = 1;\n\nlet b = 3;\n");
+	assert_eq!(git(&directory, &["show", ":staged.js"]), "let d = 4;\n");
+	assert_eq!(read(&directory, "staged.js"), "let d = 4;\n");
+	assert_eq!(read(&directory, "unstaged.ts"), "let  c = 3\n");
+	assert_eq!(git(&directory, &["show", ":notes.txt"]), "let  f = 6\n");
+}
+
+#[test]
+fn conflicting_unstaged_changes() {
+	let directory = repository("git_conflict", &[("a.ts", "let a = 1;\n")]);
+	write_files(&directory, &[("a.ts", "let  a = 1\n")]);
+	git(&directory, &["add", "a.ts"]);
+	write_files(&directory, &[("a.ts", "let  a = 2\n")]);
+
+	let repository = Repository::discover(&directory).unwrap();
+	let formatter = Formatter::new(&ConfigurationOptions::default()).unwrap();
+	let options = StagedOptions {
+		write: true,
+		update_worktree: true,
+		..StagedOptions::default()
+	};
+	let results = repository.format_staged(&formatter, &options).unwrap();
+	assert_eq!(results[0].worktree, WorktreeUpdate::Conflict);
+	assert_eq!(git(&directory, &["show", ":a.ts"]), "let a = 1;\n");
+	assert_eq!(read(&directory, "a.ts"), "let  a = 2\n");
+}
+
+#[test]
+fn changed_since() {
+	let directory = repository(
+		"git_since",
+		&[
+			("a.ts", "let a = 1;\n"),
+			("b.ts", "let b = 2;\n"),
+			("c.ts", ""),
+		],
+	);
+	git(&directory, &["tag", "start"]);
+	write_files(&directory, &[("a.ts", "let  a = 1\n"), ("d.md", "# D\n")]);
+	git(&directory, &["commit", "--quiet", "-am", "Change a"]);
+	write_files(&directory, &[("b.ts", "let  b = 2\n")]);
+	std::fs::remove_file(directory.join("c.ts")).unwrap();
+
+	let repository = Repository::discover(&directory).unwrap();
+	let top_level = repository.top_level().to_path_buf();
+	assert_eq!(
+		repository.changed_since("start", &[]).unwrap(),
+		[top_level.join("a.ts"), top_level.join("b.ts")]
+	);
+	assert_eq!(
+		repository.changed_since("HEAD", &[]).unwrap(),
+		[top_level.join("b.ts")]
+	);
+	assert!(repository.changed_since("missing", &[]).is_err());
+}
@@ -0,0 +1,80 @@
+//! Checks the log records emitted while formatting.
+
+use formate_js::configuration::ConfigurationOptions;
+use formate_js::formatter::Formatter;
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use std::path::Path;
+use std::sync::Mutex;
+
+struct Recorder(Mutex<Vec<(Level, String, String)>>);
+
+impl Log for Recorder {
+	fn enabled(&self, _: &Metadata) -> bool {
+		true
+	}
+
+	fn log(&self, record: &Record) {
+		self.0.lock().unwrap().push((
+			record.level(),
+			record.target().to_string(),
+			record.args().to_string(),
+		));
+	}
+
+	fn flush(&self) {}
+}
+
+static RECORDER: Recorder = Recorder(Mutex::new(Vec::new()));
+
+#[test]
+fn records() {
+	log::set_logger(&RECORDER).unwrap();
+	log::set_max_level(LevelFilter::Debug);
+
+	let formatter = Formatter::new(&ConfigurationOptions {
+		ignore_range_start_comment_text: "formate-ignore-start".to_string(),
+		ignore_range_end_comment_text: "formate-ignore-end".to_string(),
+		..ConfigurationOptions::default()
+	})
+	.unwrap();
+	formatter
+		.format(
+			Path::new("a.tsx"),
+			None,
+			"// formate-ignore-start\nlet  a = 1\n// formate-ignore-end\nlet b = <div/>\n"
+				.to_string(),
+		)
+		.unwrap();
+	formatter
+		.format(
+			Path::new("b.js"),
+			None,
+			"// dprint-ignore-file\nlet  c = 3\n".to_string(),
+		)
+		.unwrap();
+
+	let records = std::mem::take(&mut *RECORDER.0.lock().unwrap());
+	let messages = records
+		.iter()
+		.map(|(level, target, message)| {
+			assert_eq!(*level, Level::Debug);
+			assert_eq!(target, "formate_js::formatter");
+			message.split(" in ").next().unwrap()
+		})
+		.collect::<Vec<_>>();
+	assert_eq!(
+		messages,
+		[
+			"Leaving lines 1-3 of 'a.tsx' as they are because of an ignore comment",
+			"Parsed 'a.tsx' as tsx",
+			"Printed 'a.tsx'",
+			"Leaving 'b.js' as it is because of a file ignore comment",
+		]
+	);
+
+	log::set_max_level(LevelFilter::Off);
+	formatter
+		.format(Path::new("c.ts"), None, "let d = 4;\n".to_string())
+		.unwrap();
+	assert!(RECORDER.0.lock().unwrap().is_empty());
+}
@@ -0,0 +1,65 @@
+//! Finds the lines still wider than the line width after formatting.
+
+use formate_js::configuration::ConfigurationOptions;
+use formate_js::formatter::Formatter;
+use formate_js::long_lines::{LongLine, find_overlong_lines, visual_width};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+fn long_lines(options: ConfigurationOptions, text: &str) -> Vec<LongLine> {
+	find_overlong_lines(
+		Arc::new(Formatter::new(&options).unwrap()),
+		PathBuf::from("file.ts"),
+		None,
+		text.to_string(),
+		None,
+	)
+	.unwrap()
+}
+
+#[test]
+fn width() {
+	assert_eq!(visual_width("abc", 4), 3);
+	assert_eq!(visual_width("\t\tabc", 4), 11);
+	assert_eq!(visual_width("日本", 4), 4);
+}
+
+#[test]
+fn overlong_lines() {
+	let options = ConfigurationOptions {
+		line_width: 40,
+		..ConfigurationOptions::default()
+	};
+	let text = "\
+// See https://example.com/a/very/long/path/to/some/documentation
+const message = \"a string which is far too long to fit on one line\";
+const short = 1;
+function f() {
+	return \"日本語日本語日本語日本語日本語日本語\";
+}
+";
+	let lines = long_lines(options, text);
+	assert_eq!(
+		lines,
+		[
+			LongLine {
+				line: 1,
+				width: 65,
+				kind: Some("Comment".to_string()),
+			},
+			LongLine {
+				// The string is moved to its own line, which is still too long.
+				line: 3,
+				width: 54,
+				kind: Some("Str".to_string()),
+			},
+			LongLine {
+				line: 6,
+				width: 48,
+				kind: Some("Str".to_string()),
+			},
+		]
+	);
+
+	assert!(long_lines(ConfigurationOptions::default(), text).is_empty());
+}
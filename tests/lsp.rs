@@ -0,0 +1,224 @@
+//! Drives the language server binary with a scripted client.
+
+mod common;
+
+use formate_js::lsp::{read_message, write_message};
+use serde_json::{Value, json};
+use std::io::BufReader;
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+
+struct Client {
+	child: Child,
+	stdin: ChildStdin,
+	stdout: BufReader<ChildStdout>,
+	next_id: u64,
+}
+
+impl Client {
+	fn start() -> Self {
+		let mut child = Command::new(env!("CARGO_BIN_EXE_formate-js-lsp"))
+			.stdin(Stdio::piped())
+			.stdout(Stdio::piped())
+			.spawn()
+			.unwrap();
+		let stdin = child.stdin.take().unwrap();
+		let stdout = BufReader::new(child.stdout.take().unwrap());
+		Client {
+			child,
+			stdin,
+			stdout,
+			next_id: 1,
+		}
+	}
+
+	fn request(&mut self, method: &str, params: Value) -> Value {
+		let id = self.next_id;
+		self.next_id += 1;
+		write_message(
+			&mut self.stdin,
+			&json!({"jsonrpc": "2.0", "id": id, "method": method, "params": params}),
+		)
+		.unwrap();
+
+		let response = read_message(&mut self.stdout).unwrap().unwrap();
+		assert_eq!(response["id"], id);
+		response
+	}
+
+	fn notify(&mut self, method: &str, params: Value) {
+		write_message(
+			&mut self.stdin,
+			&json!({"jsonrpc": "2.0", "method": method, "params": params}),
+		)
+		.unwrap();
+	}
+
+	fn open(&mut self, uri: &str, text: &str) {
+		self.notify(
+			"textDocument/didOpen",
+			json!({"textDocument": {"uri": uri, "languageId": "typescript", "version": 1, "text": text}}),
+		);
+	}
+
+	fn exit(mut self) -> bool {
+		assert_eq!(self.request("shutdown", Value::Null)["result"], Value::Null);
+		self.notify("exit", Value::Null);
+		self.child.wait().unwrap().success()
+	}
+}
+
+/// Applies LSP edits to a text with only ASCII characters.
+fn apply(text: &str, edits: &Value) -> String {
+	let line_starts = std::iter::once(0)
+		.chain(text.match_indices('\n').map(|(offset, _)| offset + 1))
+		.collect::<Vec<_>>();
+	let offset = |position: &Value| {
+		line_starts[position["line"].as_u64().unwrap() as usize]
+			+ position["character"].as_u64().unwrap() as usize
+	};
+
+	let mut text = text.to_string();
+	for edit in edits.as_array().unwrap().iter().rev() {
+		text.replace_range(
+			offset(&edit["range"]["start"])..offset(&edit["range"]["end"]),
+			edit["newText"].as_str().unwrap(),
+		);
+	}
+	text
+}
+
+fn workspace(name: &str, formate_toml: &str) -> std::path::PathBuf {
+	let directory = common::temp_dir(name);
+	std::fs::create_dir_all(directory.join("src")).unwrap();
+	std::fs::write(directory.join("formate.toml"), formate_toml).unwrap();
+	directory
+}
+
+fn initialize(client: &mut Client, encodings: &[&str]) -> Value {
+	let response = client.request(
+		"initialize",
+		json!({"processId": null, "rootUri": null, "capabilities": {"general": {"positionEncodings": encodings}}}),
+	);
+	client.notify("initialized", json!({}));
+	response["result"].clone()
+}
+
+#[test]
+fn formatting() {
+	let directory = workspace(
+		"lsp_formatting",
+		"[hooks.format_js]\npriority = 50\n\n[hooks.format_js.kwargs]\nquote_style = \"alwaysSingle\"\n\n[config]\nindent = \"\\t\"\n",
+	);
+	let uri = format!("file://{}/src/code%20file.ts", directory.display());
+	let text = "function  greet( name ){\n  return \"hello, \"+name\n}\n";
+
+	let mut client = Client::start();
+	let capabilities = initialize(&mut client, &["utf-16"]);
+	assert_eq!(capabilities["capabilities"]["positionEncoding"], "utf-16");
+	assert_eq!(
+		capabilities["capabilities"]["documentFormattingProvider"],
+		true
+	);
+
+	client.open(&uri, text);
+	let response = client.request(
+		"textDocument/formatting",
+		json!({"textDocument": {"uri": uri}, "options": {"tabSize": 2, "insertSpaces": true}}),
+	);
+	// The configuration comes from formate.toml rather than the editor's options.
+	assert_eq!(
+		apply(text, &response["result"]),
+		"function greet(name) {\n\treturn 'hello, ' + name;\n}\n"
+	);
+
+	assert!(client.exit());
+}
+
+#[test]
+fn range_and_on_type_formatting() {
+	let directory = workspace("lsp_range", "[hooks]\nformat_js = 50\n");
+	let uri = format!("file://{}/src/code.ts", directory.display());
+	let text = "let  a=1\nlet  b=2\nlet  c=3\n";
+
+	let mut client = Client::start();
+	initialize(&mut client, &["utf-8", "utf-16"]);
+	client.open(&uri, text);
+
+	let response = client.request(
+		"textDocument/rangeFormatting",
+		json!({
+			"textDocument": {"uri": uri},
+			"range": {"start": {"line": 1, "character": 0}, "end": {"line": 1, "character": 8}},
+			"options": {"tabSize": 2, "insertSpaces": true},
+		}),
+	);
+	assert_eq!(
+		apply(text, &response["result"]),
+		"let  a=1\nlet b = 2;\nlet  c=3\n"
+	);
+
+	let text = "let  a=1\nlet  b=2;\nlet  c=3\n";
+	client.notify(
+		"textDocument/didChange",
+		json!({"textDocument": {"uri": uri, "version": 2}, "contentChanges": [{"text": text}]}),
+	);
+	let response = client.request(
+		"textDocument/onTypeFormatting",
+		json!({
+			"textDocument": {"uri": uri},
+			"position": {"line": 1, "character": 9},
+			"ch": ";",
+			"options": {"tabSize": 2, "insertSpaces": true},
+		}),
+	);
+	assert_eq!(
+		apply(text, &response["result"]),
+		"let  a=1\nlet b = 2;\nlet  c=3\n"
+	);
+
+	assert!(client.exit());
+}
+
+#[test]
+fn errors() {
+	let directory = workspace("lsp_errors", "[hooks]\nformat_js = 50\n");
+	let uri = format!("file://{}/src/broken.ts", directory.display());
+
+	let mut client = Client::start();
+	let response = client.request(
+		"textDocument/formatting",
+		json!({"textDocument": {"uri": uri}}),
+	);
+	assert_eq!(response["error"]["code"], -32002);
+
+	initialize(&mut client, &[]);
+	let response = client.request(
+		"textDocument/formatting",
+		json!({"textDocument": {"uri": uri}}),
+	);
+	assert!(
+		response["error"]["message"]
+			.as_str()
+			.unwrap()
+			.ends_with("is not open")
+	);
+
+	client.open(&uri, "let x = ;");
+	let response = client.request(
+		"textDocument/formatting",
+		json!({"textDocument": {"uri": uri}}),
+	);
+	assert_eq!(response["error"]["code"], -32803);
+
+	// Half-typed code is not worth an error while typing.
+	let response = client.request(
+		"textDocument/onTypeFormatting",
+		json!({"textDocument": {"uri": uri}, "position": {"line": 0, "character": 9}, "ch": ";"}),
+	);
+	assert_eq!(response["result"], Value::Null);
+
+	let response = client.request("textDocument/hover", json!({}));
+	assert_eq!(response["error"]["code"], -32601);
+
+	assert!(client.exit());
+}
@@ -0,0 +1,38 @@
+//! Dumps the print items generated for a file.
+#![cfg(feature = "tracing")]
+
+use formate_js::configuration::ConfigurationOptions;
+use formate_js::formatter::Formatter;
+use formate_js::print_items::trace_print_items;
+use std::path::Path;
+
+#[test]
+fn dump() {
+	let formatter = Formatter::new(&ConfigurationOptions::default()).unwrap();
+	let dump = trace_print_items(&formatter, Path::new("a.ts"), None, "call(a, b);\n").unwrap();
+	println!("{}", dump.to_text());
+	assert!(!dump.conditions.is_empty());
+	assert!(
+		dump.conditions
+			.iter()
+			.any(|resolution| resolution.value.is_some())
+	);
+
+	let text = dump.to_text();
+	assert!(text.lines().any(|line| line.ends_with(": \"call\"")));
+	assert!(text.contains(" resolved: "));
+
+	let json = serde_json::from_str::<serde_json::Value>(&dump.to_json()).unwrap();
+	for field in ["traces", "writerNodes", "printNodes", "conditions"] {
+		assert!(json[field].is_array(), "{}", field);
+	}
+}
+
+#[test]
+fn parse_error() {
+	let formatter = Formatter::new(&ConfigurationOptions::default()).unwrap();
+	let error = trace_print_items(&formatter, Path::new("a.ts"), None, "let a = ;\n")
+		.err()
+		.unwrap();
+	assert!(format!("{:#}", error).starts_with("Could not parse 'a.ts'"));
+}
@@ -0,0 +1,107 @@
+//! Formats a temporary directory tree, honouring its ignore files.
+
+mod common;
+
+use common::project;
+use formate_js::configuration::ConfigurationOptions;
+use formate_js::formatter::Formatter;
+use formate_js::project::{ProjectOptions, find_project_files, format_project};
+use std::path::{Path, PathBuf};
+
+fn read(directory: &Path, path: &str) -> String {
+	std::fs::read_to_string(directory.join(path)).unwrap()
+}
+
+fn relative(directory: &Path, paths: &[PathBuf]) -> Vec<String> {
+	paths
+		.iter()
+		.map(|path| path.strip_prefix(directory).unwrap().display().to_string())
+		.collect()
+}
+
+#[test]
+fn format_directory() {
+	let directory = project(
+		"project_format",
+		&[
+			(".gitignore", "dist/\n"),
+			(".formate-js-ignore", "legacy.js\n"),
+			("src/.prettierignore", "generated.ts\n"),
+			("src/a.ts", "let  a = 1\n"),
+			("src/b.ts", "let b = 2;\n"),
+			("src/generated.ts", "let  c = 3\n"),
+			("src/broken.ts", "let d = ;\n"),
+			("legacy.js", "let  e = 5\n"),
+			("dist/index.js", "let  f = 6\n"),
+			("node_modules/package/index.js", "let  g = 7\n"),
+			(
+				"vendor/lib.min.js",
+				"var a=1\n//# sourceMappingURL=lib.min.js.map\n",
+			),
+			("README.md", "# Project\n"),
+		],
+	);
+	let formatter = Formatter::new(&ConfigurationOptions {
+		skip_minified_files: true,
+		..ConfigurationOptions::default()
+	})
+	.unwrap();
+
+	let dry_run = ProjectOptions {
+		write: false,
+		..ProjectOptions::default()
+	};
+	let summary = format_project(&directory, &formatter, &dry_run).unwrap();
+	assert_eq!(relative(&directory, &summary.changed), ["src/a.ts"]);
+	assert_eq!(read(&directory, "src/a.ts"), "let  a = 1\n");
+
+	let summary = format_project(&directory, &formatter, &ProjectOptions::default()).unwrap();
+	assert_eq!(relative(&directory, &summary.changed), ["src/a.ts"]);
+	assert_eq!(relative(&directory, &summary.unchanged), ["src/b.ts"]);
+	assert_eq!(
+		summary.skipped,
+		[(directory.join("vendor/lib.min.js"), "minified".to_string())]
+	);
+	assert_eq!(summary.failed.len(), 1);
+	assert_eq!(summary.failed[0].0, directory.join("src/broken.ts"));
+
+	assert_eq!(read(&directory, "src/a.ts"), "let a = 1;\n");
+	assert_eq!(read(&directory, "src/generated.ts"), "let  c = 3\n");
+	assert_eq!(read(&directory, "legacy.js"), "let  e = 5\n");
+	assert_eq!(read(&directory, "dist/index.js"), "let  f = 6\n");
+	assert_eq!(
+		read(&directory, "node_modules/package/index.js"),
+		"let  g = 7\n"
+	);
+	let leftovers = std::fs::read_dir(directory.join("src"))
+		.unwrap()
+		.filter(|entry| {
+			let name = entry.as_ref().unwrap().file_name();
+			name.to_string_lossy().ends_with(".tmp")
+		})
+		.count();
+	assert_eq!(leftovers, 0);
+}
+
+#[test]
+fn include_and_exclude() {
+	let directory = project(
+		"project_filters",
+		&[
+			("a.ts", ""),
+			("b.js", ""),
+			("tests/c.ts", ""),
+			("node_modules/d.ts", ""),
+			(".hg/e.ts", ""),
+		],
+	);
+	// The other skipped directories are still skipped when `node_modules` is not.
+	let options = ProjectOptions {
+		includes: vec!["*.ts".to_string()],
+		excludes: vec!["tests".to_string()],
+		skip_node_modules: false,
+		..ProjectOptions::default()
+	};
+	let files = find_project_files(&directory, &options).unwrap();
+	assert_eq!(relative(&directory, &files), ["a.ts", "node_modules/d.ts"]);
+}
@@ -0,0 +1,505 @@
+use formate_js::edits::text_edits;
+use formate_js::formatter::{FormatPanicError, NonIdempotentFormattingError};
+use formate_js::mapping::{OffsetEncoding, OffsetMap};
+use formate_js::regions::{format_changed_regions, format_range};
+use formate_js::{ConfigurationOptions, Formatter, parse_formate_toml};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+
+#[test]
+fn format_and_check() {
+	let formatter = Formatter::new(&ConfigurationOptions::default()).unwrap();
+
+	let result = formatter
+		.format(Path::new("code.ts"), None, "let  x=1".to_string())
+		.unwrap();
+	assert!(result.changed);
+	assert_eq!(result.text, "let x = 1;\n");
+
+	assert!(
+		formatter
+			.check(Path::new("code.ts"), None, "let x = 1;\n".to_string())
+			.unwrap()
+	);
+	assert!(
+		!formatter
+			.check(Path::new("code.ts"), None, "let  x=1".to_string())
+			.unwrap()
+	);
+}
+
+#[test]
+fn invalid_option() {
+	let options = ConfigurationOptions {
+		quote_style: "sometimes".to_string(),
+		..Default::default()
+	};
+	let error = Formatter::new(&options).err().unwrap();
+	assert_eq!(error.to_string(), "Invalid enum value 'sometimes'");
+}
+
+#[test]
+fn format_batch() {
+	let formatter = Formatter::new(&ConfigurationOptions::default()).unwrap();
+	let files = (0..20)
+		.map(|i| (PathBuf::from(format!("file{i}.js")), format!("let  x{i}=1")))
+		.chain([(PathBuf::from("broken.js"), "let x = ;".to_string())])
+		.collect::<Vec<_>>();
+
+	let results = formatter.format_batch(files);
+	assert_eq!(results.len(), 21);
+	for (i, (path, result)) in results.iter().take(20).enumerate() {
+		assert_eq!(path, &PathBuf::from(format!("file{i}.js")));
+		assert_eq!(result.as_ref().unwrap().text, format!("let x{i} = 1;\n"));
+	}
+	assert!(results[20].1.is_err());
+}
+
+#[test]
+fn jsx_fallback() {
+	let formatter = Formatter::new(&ConfigurationOptions::default()).unwrap();
+	let format = |path: &str, extension: Option<&str>| {
+		formatter.format(Path::new(path), extension, "const a = <div/>".to_string())
+	};
+
+	// As in dprint, the extension of the path is compared case-insensitively,
+	// but an explicit extension is compared as it is given.
+	assert_eq!(
+		format("code.JS", None).unwrap().text,
+		"const a = <div />;\n"
+	);
+	assert_eq!(
+		format("code", Some("js")).unwrap().text,
+		"const a = <div />;\n"
+	);
+	assert!(format("code", Some("JS")).is_err());
+
+	// The syntax errors dprint refuses to format are reported where they are, after trying JSX.
+	let error = formatter
+		.format(
+			Path::new("code.js"),
+			None,
+			"let a = 1;\nlet b = ;\n".to_string(),
+		)
+		.err()
+		.unwrap();
+	let error = error.downcast_ref::<formate_js::ParseError>().unwrap();
+	assert_eq!((error.line, error.column), (2, 9));
+}
+
+#[test]
+fn brackets_outside_code_are_not_nesting() {
+	let formatter = Formatter::new(&ConfigurationOptions {
+		max_nesting_depth: Some(100),
+		..ConfigurationOptions::default()
+	})
+	.unwrap();
+	let brackets = "([{".repeat(200);
+	for text in [
+		format!("x = \"{brackets}\";\n"),
+		format!("// {brackets}\nx = 1;\n"),
+		format!("/* {brackets} */\nx = 1;\n"),
+		format!("x = /{}/;\n", "\\(".repeat(200)),
+		format!("x = `{brackets}`;\n"),
+	] {
+		let result = formatter.format(Path::new("flat.js"), None, text.clone());
+		assert!(result.is_ok(), "{text}: {:#}", result.err().unwrap());
+	}
+
+	let deep = format!("x = {}1{};\n", "(".repeat(101), ")".repeat(101));
+	let error = formatter
+		.format(Path::new("deep.js"), None, deep)
+		.err()
+		.unwrap();
+	assert_eq!(
+		error.to_string(),
+		"'deep.js' is nested more than 100 levels deep, which is too deep to format"
+	);
+}
+
+#[test]
+fn formate_toml() {
+	let config = parse_formate_toml(
+		r#"
+[hooks.format_js]
+priority = 50
+
+[hooks.format_js.kwargs]
+quote_style = "alwaysSingle"
+timeout = 5
+
+[config]
+indent = "	"
+line_length = 100
+"#,
+	)
+	.unwrap()
+	.unwrap();
+
+	assert_eq!(config.options.quote_style, "alwaysSingle");
+	assert_eq!(config.options.line_width, 100);
+	assert!(config.options.use_tabs);
+	assert_eq!(config.options.indent_width, 4);
+	assert_eq!(config.timeout, Some(5.0));
+
+	for timeout in ["-1", "nan", "inf"] {
+		let error = parse_formate_toml(&format!(
+			"[hooks.format_js.kwargs]\ntimeout = {}\n",
+			timeout
+		))
+		.unwrap_err();
+		assert!(
+			format!("{:#}", error).contains("'timeout' must be a non-negative number of seconds"),
+			"{:#}",
+			error
+		);
+	}
+}
+
+#[test]
+fn pyproject_toml() {
+	let config = parse_formate_toml(
+		r#"
+[tool.formate.hooks]
+format_js = 10
+
+[tool.formate.config]
+indent = "  "
+"#,
+	)
+	.unwrap()
+	.unwrap();
+	assert!(!config.options.use_tabs);
+	assert_eq!(config.options.indent_width, 2);
+
+	assert!(
+		parse_formate_toml("[hooks]\nisort = 50\n")
+			.unwrap()
+			.is_none()
+	);
+
+	let error = parse_formate_toml("[hooks.format_js.kwargs]\nline_widht = 80\n").unwrap_err();
+	assert!(error.to_string().contains("unknown field `line_widht`"));
+}
+
+#[test]
+fn panic_is_caught() {
+	let formatter = Formatter::new(&ConfigurationOptions::default()).unwrap();
+	let error = formatter
+		.format(Path::new("crash.jsx"), None, "-".to_string())
+		.unwrap_err();
+
+	let error = error.downcast_ref::<FormatPanicError>().unwrap();
+	assert_eq!(error.path, PathBuf::from("crash.jsx"));
+	assert_eq!(error.snippet, "-");
+	assert!(
+		error
+			.crash_report()
+			.ends_with("--- start of 'crash.jsx' ---\n-")
+	);
+}
+
+#[test]
+fn verify_idempotent() {
+	let options = ConfigurationOptions {
+		line_width: 40,
+		indent_width: 4,
+		arguments_prefer_hanging: "always".to_string(),
+		verify_idempotent: true,
+		..Default::default()
+	};
+	let formatter = Formatter::new(&options).unwrap();
+	let error = formatter
+		.format(
+			Path::new("code.ts"),
+			None,
+			"t(u, tbdstdddddbbSftt ? dddddmdd : testt);\n".to_string(),
+		)
+		.unwrap_err();
+
+	let error = error
+		.downcast_ref::<NonIdempotentFormattingError>()
+		.unwrap();
+	assert!(error.diff.starts_with("--- first pass\n+++ second pass\n"));
+}
+
+#[test]
+fn offset_map() {
+	let original = "let  x =1;\nfoo( x )";
+	let formatted = "let x = 1;\nfoo(x);\n";
+	let offset_map = OffsetMap::new(original, formatted);
+
+	let offsets = [original.find("x =").unwrap(), original.find(" )").unwrap()];
+	let mapped = offset_map
+		.map_offsets(&offsets, OffsetEncoding::Utf8)
+		.unwrap();
+	assert_eq!(
+		mapped,
+		[
+			formatted.find("x =").unwrap(),
+			formatted.find(");").unwrap()
+		]
+	);
+
+	assert!(offset_map.map_offset(100, OffsetEncoding::Utf16).is_err());
+}
+
+#[test]
+fn edits() {
+	let original = "let  x =1;\nlet y = 2;\nfoo( x )";
+	let formatted = "let x = 1;\nlet y = 2;\nfoo(x);\n";
+	let edits = text_edits(original, formatted, OffsetEncoding::Utf8);
+
+	let mut text = original.to_string();
+	for edit in edits.iter().rev() {
+		text.replace_range(edit.start..edit.end, &edit.new_text);
+	}
+	assert_eq!(text, formatted);
+
+	// The second line is unchanged.
+	assert!(edits.iter().all(|edit| edit.range.start.line != 1));
+	assert_eq!(
+		serde_json::to_value(&edits[0]).unwrap(),
+		serde_json::json!({
+			"range": {"start": {"line": 0, "character": 4}, "end": {"line": 0, "character": 5}},
+			"newText": "",
+		})
+	);
+}
+
+#[test]
+fn edits_with_crlf() {
+	let apply = |original: &str, edits: &[formate_js::edits::TextEdit]| {
+		let mut text = original.to_string();
+		for edit in edits.iter().rev() {
+			text.replace_range(edit.start..edit.end, &edit.new_text);
+		}
+		text
+	};
+
+	// Removing the carriage returns replaces each CRLF whole rather than splitting it.
+	let original = "let a = 1;\r\nlet b = 2;\r\n";
+	let formatted = "let a = 1;\nlet b = 2;\n";
+	let edits = text_edits(original, formatted, OffsetEncoding::Utf8);
+	assert_eq!(apply(original, &edits), formatted);
+	for edit in &edits {
+		assert!(
+			!original[..edit.start].ends_with('\r') || !original[edit.start..].starts_with('\n')
+		);
+		assert!(!original[..edit.end].ends_with('\r') || !original[edit.end..].starts_with('\n'));
+	}
+	assert_eq!(
+		serde_json::to_value(&edits[0]).unwrap(),
+		serde_json::json!({
+			"range": {"start": {"line": 0, "character": 10}, "end": {"line": 1, "character": 0}},
+			"newText": "\n",
+		})
+	);
+
+	// CRLF and a lone CR each end a line.
+	let original = "let a = 1;\r\nlet b = 2;\rlet  c = 3;\n";
+	let formatted = "let a = 1;\r\nlet b = 2;\rlet c = 3;\n";
+	let edits = text_edits(original, formatted, OffsetEncoding::Utf8);
+	assert_eq!(apply(original, &edits), formatted);
+	assert_eq!(
+		serde_json::to_value(&edits).unwrap(),
+		serde_json::json!([{
+			"range": {"start": {"line": 2, "character": 4}, "end": {"line": 2, "character": 5}},
+			"newText": "",
+		}])
+	);
+}
+
+#[test]
+fn ignore_comments() {
+	let formatter = Formatter::new(&ConfigurationOptions::default()).unwrap();
+	let format = |formatter: &Formatter, path: &str, text: &str| {
+		formatter
+			.format(Path::new(path), None, text.to_string())
+			.unwrap()
+			.text
+	};
+
+	// Only comments count, not JSX text which looks like one,
+	// so it is formatted as if there were no range ignore comments.
+	let plain = Formatter::new(&ConfigurationOptions {
+		ignore_range_start_comment_text: String::new(),
+		ignore_range_end_comment_text: String::new(),
+		..ConfigurationOptions::default()
+	})
+	.unwrap();
+	for text in [
+		"const  e = <div>// formate-ignore-start</div>;\n",
+		"const  e = <div>/* formate-ignore-end */</div>;\n",
+	] {
+		assert_eq!(
+			format(&formatter, "a.tsx", text),
+			format(&plain, "a.tsx", text)
+		);
+	}
+
+	let text =
+		"let  a = 1;\r\n// formate-ignore-start\r\nlet   b =  2;\r\n// formate-ignore-end\r\n";
+	assert_eq!(
+		format(&formatter, "a.ts", text),
+		"let a = 1;\n// formate-ignore-start\r\nlet   b =  2;\r\n// formate-ignore-end\n"
+	);
+
+	// Files which do not parse can still be ignored as a whole.
+	let text = "\u{feff}// dprint-ignore-file\nlet = ;\n";
+	assert_eq!(format(&formatter, "a.ts", text), text);
+}
+
+#[test]
+fn format_edits() {
+	let formatter = Formatter::new(&ConfigurationOptions::default()).unwrap();
+	let format_edits = |text: &str| {
+		formatter
+			.format_edits_cancellable(
+				Path::new("code.ts"),
+				None,
+				text.to_string(),
+				OffsetEncoding::Utf8,
+				&AtomicBool::new(false),
+			)
+			.unwrap()
+	};
+
+	let original = "// c\r\n  \n// c\n";
+	let mut text = original.to_string();
+	for edit in format_edits(original).iter().rev() {
+		text.replace_range(edit.start..edit.end, &edit.new_text);
+	}
+	assert_eq!(
+		text,
+		formatter
+			.format(Path::new("code.ts"), None, original.to_string())
+			.unwrap()
+			.text
+	);
+	assert!(format_edits("let x = 1;\n").is_empty());
+}
+
+#[test]
+fn edits_rebuild_formatted_text() {
+	let splits_crlf = |text: &str, offset: usize| {
+		text[..offset].ends_with('\r') && text[offset..].starts_with('\n')
+	};
+	let check = |original: &str, formatted: &str| {
+		let edits = text_edits(original, formatted, OffsetEncoding::Utf8);
+		let mut text = original.to_string();
+		for edit in edits.iter().rev() {
+			text.replace_range(edit.start..edit.end, &edit.new_text);
+		}
+		assert_eq!(
+			text, formatted,
+			"{:?} -> {:?}: {:?}",
+			original, formatted, edits
+		);
+		for edit in &edits {
+			assert!(edit.start <= edit.end && edit.range.start <= edit.range.end);
+			assert!(!splits_crlf(original, edit.start) && !splits_crlf(original, edit.end));
+		}
+		for pair in edits.windows(2) {
+			assert!(pair[0].end < pair[1].start, "{:?}", pair);
+			assert!(pair[0].range.end <= pair[1].range.start, "{:?}", pair);
+		}
+	};
+
+	check("// c\r\n  \n// c\n", "// c\n\n// c\n");
+	check("\n\r\n", "\r\n a \r\n\r\nb");
+	check(";\r a", "\r\n\n\r\n ");
+
+	// Texts with mixed line breaks, built from pieces which often diff next to a CRLF.
+	let pieces = ["\r", "\n", "\r\n", " ", "a", "b", ";", "é", "日"];
+	let mut rng = fastrand::Rng::with_seed(7);
+	let mut text = || {
+		(0..rng.usize(0..12))
+			.map(|_| pieces[rng.usize(..pieces.len())])
+			.collect::<String>()
+	};
+	for _ in 0..20_000 {
+		let (original, formatted) = (text(), text());
+		check(&original, &formatted);
+	}
+}
+
+#[test]
+fn changed_regions() {
+	let formatter = Arc::new(Formatter::new(&ConfigurationOptions::default()).unwrap());
+	let format = |old: &str, new: &str| {
+		format_changed_regions(
+			formatter.clone(),
+			PathBuf::from("code.ts"),
+			None,
+			old,
+			new.to_string(),
+			None,
+		)
+		.unwrap()
+	};
+
+	let old = "let  a=1\nfunction f( x ){\n  let  y=x\n  return  y\n}\nlet  b=2\n";
+	let new = "let  a=1\nfunction f( x ){\n  let  y=x+1\n  return  y\n}\nlet  b=3\n";
+	let result = format(old, new);
+	assert!(result.changed);
+	assert_eq!(
+		result.text,
+		"let  a=1\nfunction f( x ){\n  let y = x + 1;\n  return  y\n}\nlet b = 3;\n"
+	);
+	assert_eq!(result.changed_lines, 4);
+
+	// Changing the header of a block leaves the statements in it alone.
+	let new = "let  a=1\nfunction g( x ){\n  let  y=x\n  return  y\n}\nlet  b=2\n";
+	assert_eq!(
+		format(old, new).text,
+		"let  a=1\nfunction g(x) {\n  let  y=x\n  return  y\n}\nlet  b=2\n"
+	);
+
+	// A statement changed inside an unchanged one is formatted within it.
+	let old = "function f( x ){\n  if (x){\n    let  y=x\n  }\n  let  z=1\n}\n";
+	let new = "function g( x ){\n  if (x){\n    let  y=x+1\n  }\n  let  z=1\n}\n";
+	assert_eq!(
+		format(old, new).text,
+		"function g(x) {\n  if (x){\n    let y = x + 1;\n  }\n  let  z=1\n}\n"
+	);
+
+	assert!(!format(old, old).changed);
+}
+
+#[test]
+fn regions_keep_crlf_line_breaks() {
+	let options = ConfigurationOptions {
+		new_line_kind: "auto".to_string(),
+		..ConfigurationOptions::default()
+	};
+	let formatter = Arc::new(Formatter::new(&options).unwrap());
+
+	let old = "let  a=1\r\nlet  b=2\r\nlet  c=3\r\n";
+	let new = "let  a=1\r\nlet  b=4\r\nlet  c=3\r\n";
+	let result = format_changed_regions(
+		formatter.clone(),
+		PathBuf::from("code.ts"),
+		None,
+		old,
+		new.to_string(),
+		None,
+	)
+	.unwrap();
+	assert_eq!(result.text, "let  a=1\r\nlet b = 4;\r\nlet  c=3\r\n");
+	assert_eq!(result.changed_lines, 2);
+
+	let start = new.find("let  c").unwrap();
+	let result = format_range(
+		formatter,
+		PathBuf::from("code.ts"),
+		None,
+		new.to_string(),
+		start..new.len(),
+		None,
+		Arc::new(AtomicBool::new(false)),
+	)
+	.unwrap();
+	assert_eq!(result.text, "let  a=1\r\nlet  b=4\r\nlet c = 3;\r\n");
+}
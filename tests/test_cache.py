@@ -0,0 +1,38 @@
+# 3rd party
+from domdf_python_tools.paths import PathPlus
+
+# this package
+from formate_js import Configuration
+from formate_js._formate_js import format_file, format_project
+
+
+def test_format_file_cache(tmp_pathplus: PathPlus):
+	cache_dir = tmp_pathplus / "cache"
+	path = tmp_pathplus / "code.ts"
+	path.write_text("let  x = 1\n")
+
+	assert format_file(path, Configuration(), cache_dir=cache_dir).changed
+	assert cache_dir.is_dir()
+
+	result = format_file(path, Configuration(), cache_dir=cache_dir)
+	assert not result.changed
+	assert result.parse_duration == 0
+
+	# A different configuration does not use the entries recorded for another.
+	result = format_file(path, Configuration(indent_width=2), cache_dir=cache_dir)
+	assert not result.changed
+	assert result.parse_duration > 0
+
+
+def test_format_project_cache(tmp_pathplus: PathPlus):
+	(tmp_pathplus / "src").mkdir()
+	(tmp_pathplus / "src" / "a.ts").write_text("let  a = 1\n")
+	(tmp_pathplus / "src" / "b.ts").write_text("let b = 2;\n")
+	cache_dir = tmp_pathplus / "cache"
+
+	summary = format_project(tmp_pathplus / "src", Configuration(), cache_dir=cache_dir)
+	assert len(summary.changed) == 1
+
+	summary = format_project(tmp_pathplus / "src", Configuration(), cache_dir=cache_dir)
+	assert summary.changed == []
+	assert len(summary.unchanged) == 2
@@ -0,0 +1,48 @@
+# 3rd party
+import pytest
+
+# this package
+from formate_js import Configuration, FormatError
+from formate_js._formate_js import format_changed_regions
+
+old_text = """\
+let  a=1
+function f( x ){
+  let  y=x
+  return  y
+}
+let  b=2
+"""
+
+new_text = """\
+let  a=1
+function f( x ){
+  let  y=x+1
+  return  y
+}
+let  b=3
+"""
+
+
+def test_format_changed_regions():
+	result = format_changed_regions(old_text, new_text, Configuration(indent_width=2))
+	assert result.changed
+	assert result.text == """\
+let  a=1
+function f( x ){
+  let y = x + 1;
+  return  y
+}
+let b = 3;
+"""
+
+
+def test_format_changed_regions_unchanged():
+	result = format_changed_regions(old_text, old_text, Configuration())
+	assert not result.changed
+	assert result.text == old_text
+
+
+def test_format_changed_regions_error():
+	with pytest.raises(FormatError):
+		format_changed_regions(old_text, "let x = ;\n", Configuration(), path="code.js")
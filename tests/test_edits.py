@@ -0,0 +1,101 @@
+# stdlib
+from typing import List
+
+# 3rd party
+import pytest
+
+# this package
+from formate_js import Configuration
+from formate_js._formate_js import FormatTextOptions, TextEdit, format_text, format_text_edits
+
+source = """\
+// 😀 stays put
+function  greet( name ){
+  return 'hello, '+name
+}
+
+const x = {a:1}
+"""
+
+
+def _options(text: str) -> FormatTextOptions:
+	return FormatTextOptions("code.ts", None, text, Configuration())
+
+
+def _apply(text: str, edits: List[TextEdit]) -> str:
+	# The offsets are Python string indices with the "utf-32" encoding.
+	for edit in reversed(edits):
+		text = text[:edit.start] + edit.new_text + text[edit.end:]
+	return text
+
+
+def test_edits():
+	formatted = format_text(_options(source)).text
+	edits = format_text_edits(_options(source), offset_encoding="utf-32")
+
+	assert _apply(source, edits) == formatted
+	assert [edit.to_tuple() for edit in edits] == [
+			(24, 25, ''),
+			(31, 32, ''),
+			(36, 36, ')'),
+			(37, 38, ''),
+			(49, 50, '"'),
+			(57, 58, '" '),
+			(59, 59, ' '),
+			(63, 63, ';'),
+			(78, 78, ' '),
+			(80, 80, ' '),
+			(81, 81, ' '),
+			(82, 82, ';'),
+			]
+
+
+def test_lsp_shape():
+	edits = format_text_edits(_options(source), offset_encoding="utf-16")
+
+	# The emoji on the first line is two UTF-16 code units, which only shifts the offsets.
+	assert edits[0].start == 25
+	assert edits[0].to_lsp() == {
+			"range": {"start": {"line": 1, "character": 9}, "end": {"line": 1, "character": 10}},
+			"newText": '',
+			}
+	assert edits[0].range.start.line == 1
+	assert edits[0].range.end.character == 10
+
+
+@pytest.mark.parametrize("encoding, expected", [("utf-8", 17), ("utf-16", 13), ("utf-32", 11)])
+def test_encodings(encoding: str, expected: int):
+	text = "let s = '😀😀';;\n"
+	edits = format_text_edits(_options(text), offset_encoding=encoding)
+
+	assert edits[-1].start == expected
+	assert edits[-1].range.start.character == expected
+
+
+def test_crlf():
+	config = Configuration()
+	config.new_line_kind = "crlf"
+	text = "let  a = 1;\r\nlet  b = 2;\r\n"
+	options = FormatTextOptions("code.ts", None, text, config)
+	edits = format_text_edits(options, offset_encoding="utf-32")
+
+	assert _apply(text, edits) == "let a = 1;\r\nlet b = 2;\r\n"
+	assert [(edit.range.start.line, edit.range.start.character) for edit in edits] == [(0, 4), (1, 3)]
+
+
+
+def test_mixed_line_breaks():
+	# Deleting a line next to a CRLF which becomes an LF.
+	text = "// c\r\n  \n// c\n"
+	edits = format_text_edits(_options(text), offset_encoding="utf-32")
+
+	assert _apply(text, edits) == format_text(_options(text)).text
+
+
+def test_unchanged():
+	assert format_text_edits(_options("let x = 1;\n")) == []
+
+
+def test_invalid_encoding():
+	with pytest.raises(ValueError, match="Invalid offset encoding 'latin-1'"):
+		format_text_edits(_options(source), offset_encoding="latin-1")
@@ -0,0 +1,40 @@
+# 3rd party
+import pytest
+
+# this package
+from formate_js import Configuration, ConfigurationBuilder, FormatError, Formatter
+from formate_js._formate_js import FormatTextOptions, format_text
+
+
+def test_invalid_enum_formatter():
+	with pytest.raises(ValueError, match="Invalid enum value 'sometimes'"):
+		Formatter(Configuration(quote_style="sometimes"))
+
+
+def test_invalid_enum_format_text():
+	options = FormatTextOptions("code.js", None, "x = 1", Configuration(semi_colons="sometimes"))
+
+	with pytest.raises(ValueError, match="Invalid enum value 'sometimes'"):
+		format_text(options)
+
+
+def test_invalid_enum_builder():
+	with pytest.raises(ValueError, match="Invalid enum value 'sometimes'"):
+		ConfigurationBuilder().quote_style("sometimes")
+
+
+def test_syntax_error_has_no_crash_report():
+	with pytest.raises(FormatError) as exc_info:
+		Formatter(Configuration()).format("let x = ;", "broken.js")
+
+	assert exc_info.value.crash_report is None
+
+
+def test_panic_crash_report():
+	# The parser panics on this input rather than reporting a syntax error.
+	with pytest.raises(FormatError, match="The formatter crashed while formatting 'crash.jsx'") as exc_info:
+		Formatter(Configuration()).format('-', "crash.jsx")
+
+	assert exc_info.value.crash_report is not None
+	assert exc_info.value.crash_report.startswith("formate-js ")
+	assert exc_info.value.crash_report.endswith("--- start of 'crash.jsx' ---\n-")
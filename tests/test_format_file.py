@@ -0,0 +1,73 @@
+# stdlib
+import os
+import stat
+
+# 3rd party
+import pytest
+from domdf_python_tools.paths import PathPlus
+
+# this package
+from formate_js import Configuration
+from formate_js._formate_js import FormatError, format_file
+
+
+def test_format_file(tmp_pathplus: PathPlus):
+	path = tmp_pathplus / "code.ts"
+	path.write_bytes(b"\xef\xbb\xbflet  x = 1\n")
+
+	result = format_file(path, Configuration())
+	assert result.changed
+	assert result.text == "let x = 1;\n"
+	assert path.read_bytes() == b"\xef\xbb\xbflet x = 1;\n"
+
+
+def test_format_file_utf16(tmp_pathplus: PathPlus):
+	path = tmp_pathplus / "code.ts"
+	path.write_bytes("\ufefflet  x = '日本'\n".encode("UTF-16-LE"))
+
+	format_file(path, Configuration())
+	assert path.read_bytes() == "\ufefflet x = \"日本\";\n".encode("UTF-16-LE")
+
+
+def test_format_file_unchanged(tmp_pathplus: PathPlus):
+	path = tmp_pathplus / "code.ts"
+	path.write_text("let x = 1;\n")
+	os.utime(path, ns=(1_000_000_000, 1_000_000_000))
+
+	result = format_file(path, Configuration())
+	assert not result.changed
+	assert path.stat().st_mtime_ns == 1_000_000_000
+
+
+@pytest.mark.skipif(os.name == "nt", reason="POSIX permissions")
+def test_format_file_backup_and_permissions(tmp_pathplus: PathPlus):
+	path = tmp_pathplus / "script.js"
+	path.write_text("console.log( 1 )\n")
+	path.chmod(0o750)
+
+	format_file(path, Configuration(), backup_suffix=".orig")
+	assert path.read_text() == "console.log(1);\n"
+	assert stat.S_IMODE(path.stat().st_mode) == 0o750
+	assert (tmp_pathplus / "script.js.orig").read_text() == "console.log( 1 )\n"
+
+
+def test_format_file_dry_run(tmp_pathplus: PathPlus):
+	path = tmp_pathplus / "code.ts"
+	path.write_text("let  x = 1\n")
+
+	assert format_file(path, Configuration(), write=False).changed
+	assert path.read_text() == "let  x = 1\n"
+
+
+def test_format_file_errors(tmp_pathplus: PathPlus):
+	path = tmp_pathplus / "code.ts"
+	path.write_bytes(b"let x = '\xe9';\n")
+	with pytest.raises(ValueError, match="is not valid UTF-8"):
+		format_file(path, Configuration())
+
+	path.write_text("let x = ;\n")
+	with pytest.raises(FormatError):
+		format_file(path, Configuration())
+
+	with pytest.raises(OSError):
+		format_file(tmp_pathplus / "missing.ts", Configuration())
@@ -0,0 +1,56 @@
+# 3rd party
+import pytest
+
+# this package
+from formate_js import Configuration, FormatResult
+from formate_js._formate_js import FormatTextOptions, format_text
+
+
+def _format(source: str, filename: str = "code.ts", **kwargs) -> FormatResult:
+	extension = '.' + filename.rpartition('.')[2]
+	return format_text(FormatTextOptions(filename, extension, source, Configuration(**kwargs)))
+
+
+def test_changed():
+	result = _format("let  x=1\nlet y = 2;\n")
+
+	assert result.text == "let x = 1;\nlet y = 2;\n"
+	assert result.changed
+	assert result.original_len == 20
+	assert result.formatted_len == 22
+	assert result.changed_lines == 2
+	assert result.syntax == "typescript"
+	assert result.parse_duration >= 0
+	assert result.print_duration >= 0
+	assert result.ignore_regions == []
+
+
+def test_unchanged():
+	source = "let x = 1;\n"
+	result = _format(source)
+
+	assert result.text == source
+	assert not result.changed
+	assert result.original_len == result.formatted_len == 11
+	assert result.changed_lines == 0
+
+
+@pytest.mark.parametrize(
+		"filename, source, syntax",
+		[
+				pytest.param("code.ts", "let x: number = 1;\n", "typescript", id="ts"),
+				pytest.param("code.tsx", "const a = <div />;\n", "tsx", id="tsx"),
+				pytest.param("code.js", "let x = 1;\n", "javascript", id="js"),
+				pytest.param("code.js", "const a = <div />;\n", "jsx", id="jsx_in_js"),
+				],
+		)
+def test_syntax(filename: str, source: str, syntax: str):
+	assert _format(source, filename).syntax == syntax
+
+
+def test_ignore_regions():
+	source = "let a = 1;\n// formate-ignore-start\nlet   b\n// formate-ignore-end\nlet c = 3;\n"
+	result = _format(source)
+
+	assert not result.changed
+	assert result.ignore_regions == [(2, 4)]
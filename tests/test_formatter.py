@@ -0,0 +1,42 @@
+# this package
+import formate_js
+from formate_js import Configuration, Formatter, javascript_hook
+
+
+def test_formatter():
+	formatter = Formatter(Configuration(line_width=40))
+
+	result = formatter.format("class Test {/* testing this out to go above 40 */}", "code.ts")
+	assert result.changed
+	assert result.text == "class Test {\n  /* testing this out to go above 40 */\n}\n"
+
+	result = formatter.format("let x = 1;\n", "code.ts")
+	assert not result.changed
+	assert result.text == "let x = 1;\n"
+
+
+def test_formatter_extension():
+	formatter = Formatter(Configuration())
+
+	assert formatter.format("const a = <div/>", "code", ".jsx").text == "const a = <div />;\n"
+
+
+def test_formatter_independent_of_config():
+	config = Configuration(line_width=40)
+	formatter = Formatter(config)
+	config.line_width = 120
+
+	source = "class Test {/* testing this out to go above 40 */}"
+	assert formatter.format(source, "code.ts").text == "class Test {\n  /* testing this out to go above 40 */\n}\n"
+
+
+def test_hook_caches_formatters():
+	formate_js._get_formatter.cache_clear()
+
+	javascript_hook("let  x = 1", "code.js", line_width=40, ignore_node_comment_aliases=["keep"])
+	javascript_hook("let  y = 2", "code.js", ignore_node_comment_aliases=["keep"], line_width=40)
+	javascript_hook("let  z = 3", "code.js", line_width=80)
+
+	cache_info = formate_js._get_formatter.cache_info()
+	assert cache_info.hits == 1
+	assert cache_info.misses == 2
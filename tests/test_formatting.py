@@ -35,7 +35,7 @@ class Spec(NamedTuple):
 	# is_only: bool
 	# is_trace: bool
 	# skip: bool
-	# skip_format_twice: bool
+	skip_format_twice: bool
 	config: str
 
 
@@ -108,7 +108,7 @@ def parse_single_spec(
 	parts = file_text.split("[expect]")
 	start_text = parts[0][:len(parts[0]) - len('\n')]  # remove last newline
 	expected_text = parts[1][len('\n'):]  # remove first newline
-	# lower_case_message_line = message_line.lower()
+	lower_case_message_line = message_line.lower()
 	message_separator = "=="
 	# is_trace = "(trace)" in lower_case_message_line
 
@@ -121,7 +121,7 @@ def parse_single_spec(
 			# is_only = lower_case_message_line.contains("(only)") || is_trace,
 			# is_trace=is_trace,
 			# skip=lower_case_message_line.contains("(skip)"),
-			# skip_format_twice=lower_case_message_line.contains("(skip-format-twice)"),
+			skip_format_twice="(skip-format-twice)" in lower_case_message_line,
 			config=config,
 			)
 
@@ -194,5 +194,6 @@ def test_specs(spec: Spec, file_path: str):
 	assert javascript_hook(
 			spec.file_text,
 			formate_filename=file_path,
+			verify_idempotent=not spec.skip_format_twice,
 			**parse_config(spec.config),
 			) == spec.expected_text
@@ -0,0 +1,52 @@
+# stdlib
+import pathlib
+
+# 3rd party
+import pytest
+
+# this package
+from formate_js import Configuration, FormatError, Formatter, NonIdempotentFormatting, javascript_hook
+
+# From the dprint spec "issue035", which dprint only formats once.
+unstable_source = "t(u, tbdstdddddbbSftt ? dddddmdd : testt);\n"
+unstable_options = dict(line_width=40, indent_width=4, arguments_prefer_hanging="always")
+
+expected_diff = """\
+--- first pass
++++ second pass
+@@ -1,4 +1,3 @@
+-t(u,
+-    tbdstdddddbbSftt
+-        ? dddddmdd
+-        : testt);
++t(u, tbdstdddddbbSftt
++    ? dddddmdd
++    : testt);
+"""
+
+
+def test_not_verified_by_default():
+	result = Formatter(Configuration(**unstable_options)).format(unstable_source, "code.ts")
+	assert result.text == "t(u,\n    tbdstdddddbbSftt\n        ? dddddmdd\n        : testt);\n"
+
+
+def test_non_idempotent():
+	formatter = Formatter(Configuration(verify_idempotent=True, **unstable_options))
+
+	with pytest.raises(NonIdempotentFormatting, match="Formatting 'code.ts' is not idempotent") as exc_info:
+		formatter.format(unstable_source, "code.ts")
+
+	assert isinstance(exc_info.value, FormatError)
+	assert exc_info.value.path == pathlib.Path("code.ts")
+	assert exc_info.value.diff == expected_diff
+
+
+def test_non_idempotent_hook():
+	with pytest.raises(NonIdempotentFormatting):
+		javascript_hook(unstable_source, "code.ts", verify_idempotent=True, **unstable_options)
+
+
+def test_idempotent():
+	formatter = Formatter(Configuration(verify_idempotent=True))
+	assert formatter.format("let  x=1", "code.ts").text == "let x = 1;\n"
+	assert not formatter.format("let x = 1;\n", "code.ts").changed
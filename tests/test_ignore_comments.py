@@ -0,0 +1,110 @@
+# stdlib
+import re
+
+# 3rd party
+import pytest
+
+# this package
+from formate_js import javascript_hook
+
+
+def test_range_ignore():
+	source = """\
+function  a( ) {
+  let x =   1;
+  // formate-ignore-start
+  const   matrix = [
+    1,0,0,
+    0,1,0 ];
+  // formate-ignore-end
+  let   y = 2;
+}
+"""
+	expected = """\
+function a() {
+  let x = 1;
+  // formate-ignore-start
+  const   matrix = [
+    1,0,0,
+    0,1,0 ];
+  // formate-ignore-end
+  let y = 2;
+}
+"""
+
+	assert javascript_hook(source, "code.js") == expected
+
+
+def test_range_ignore_custom_text():
+	source = "let  a = 1;\n/* keep-start */\nlet   b =  2;\n/* keep-end */\n"
+	expected = "let a = 1;\n/* keep-start */\nlet   b =  2;\n/* keep-end */\n"
+
+	output = javascript_hook(
+			source,
+			"code.ts",
+			ignore_range_start_comment_text="keep-start",
+			ignore_range_end_comment_text="keep-end",
+			)
+	assert output == expected
+
+
+@pytest.mark.parametrize(
+		"source, message",
+		[
+				pytest.param(
+						"// formate-ignore-start\nlet a = 1;\n",
+						"'formate-ignore-start' comment without a matching 'formate-ignore-end' comment (line 1)",
+						id="unclosed",
+						),
+				pytest.param(
+						"let a = 1;\n// formate-ignore-end\n",
+						"'formate-ignore-end' comment without a preceding 'formate-ignore-start' comment (line 2)",
+						id="unopened",
+						),
+				pytest.param(
+						"// formate-ignore-start\n// formate-ignore-start\n// formate-ignore-end\n",
+						"Nested 'formate-ignore-start' comment; the one on line 1 is not closed (line 2)",
+						id="nested",
+						),
+				],
+		)
+def test_range_ignore_unbalanced(source: str, message: str):
+	with pytest.raises(ValueError, match=re.escape(message)):
+		javascript_hook(source, "code.js")
+
+
+def test_markers_in_strings():
+	source = "const s = \"// formate-ignore-start\";\nlet   r = /\\/\\/ formate-ignore-end/;\n"
+	expected = "const s = \"// formate-ignore-start\";\nlet r = /\\/\\/ formate-ignore-end/;\n"
+	assert javascript_hook(source, "code.js") == expected
+
+
+
+def test_markers_in_jsx_text():
+	# JSX text which looks like a comment is formatted as if there were no range ignore comments.
+	source = "const  e = <div>// formate-ignore-start</div>;\n"
+	plain = javascript_hook(
+			source,
+			"code.tsx",
+			ignore_range_start_comment_text='',
+			ignore_range_end_comment_text='',
+			)
+	assert javascript_hook(source, "code.tsx") == plain
+
+@pytest.mark.parametrize("comment", ["dprint-ignore", "prettier-ignore", "formate-ignore"])
+def test_node_ignore_aliases(comment: str):
+	source = f"// {comment}\nconst  b = [1,\n   2];\nconst  c = [1,\n   2];\n"
+	expected = f"// {comment}\nconst  b = [1,\n   2];\nconst c = [1, 2];\n"
+	assert javascript_hook(source, "code.js") == expected
+
+
+def test_node_ignore_aliases_disabled():
+	source = "// prettier-ignore\nconst  b = [1,\n   2];\n"
+	expected = "// prettier-ignore\nconst b = [1, 2];\n"
+	assert javascript_hook(source, "code.js", ignore_node_comment_aliases=[]) == expected
+
+
+@pytest.mark.parametrize("comment", ["dprint-ignore-file", "formate-ignore-file"])
+def test_file_ignore_aliases(comment: str):
+	source = f"// {comment}\nlet   a=1\n"
+	assert javascript_hook(source, "code.js") == source
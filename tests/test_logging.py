@@ -0,0 +1,35 @@
+# stdlib
+import logging
+
+# 3rd party
+import pytest
+
+# this package
+from formate_js import Configuration
+from formate_js._formate_js import FormatTextOptions, format_text
+
+
+def _format(path: str, text: str) -> None:
+	format_text(FormatTextOptions(path, None, text, Configuration()))
+
+
+def test_logging(caplog: pytest.LogCaptureFixture):
+	caplog.set_level(logging.DEBUG, logger="formate_js")
+	_format("code.tsx", "let  x = <div/>\n")
+	_format("ignored.js", "// dprint-ignore-file\nlet  y = 1\n")
+
+	records = [(record.name, record.levelno, record.getMessage()) for record in caplog.records]
+	assert records[0][:2] == ("formate_js.formatter", logging.DEBUG)
+	assert records[0][2].startswith("Parsed 'code.tsx' as tsx in ")
+	assert records[1][2].startswith("Printed 'code.tsx' in ")
+	assert records[2] == (
+			"formate_js.formatter",
+			logging.DEBUG,
+			"Leaving 'ignored.js' as it is because of a file ignore comment",
+			)
+
+
+def test_logging_disabled(caplog: pytest.LogCaptureFixture):
+	caplog.set_level(logging.WARNING, logger="formate_js")
+	_format("code.ts", "let  x = 1\n")
+	assert not [record for record in caplog.records if record.name.startswith("formate_js")]
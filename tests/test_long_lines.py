@@ -0,0 +1,29 @@
+# 3rd party
+import pytest
+
+# this package
+from formate_js import Configuration
+from formate_js._formate_js import FormatError, FormatTextOptions, find_overlong_lines
+
+
+def test_find_overlong_lines():
+	text = (
+			"// See https://example.com/a/very/long/path/to/some/documentation\n"
+			'const message = "a string which is far too long to fit on one line";\n'
+			"const short = 1;\n"
+			)
+	options = FormatTextOptions("code.ts", None, text, Configuration(line_width=40))
+	lines = find_overlong_lines(options)
+	assert [(line.line, line.width, line.kind) for line in lines] == [(1, 65, "Comment"), (3, 54, "Str")]
+	assert repr(lines[0]) == "LongLine(line=1, width=65, kind='Comment')"
+
+
+def test_find_overlong_lines_none():
+	options = FormatTextOptions("code.ts", None, "const short = 1;\n", Configuration())
+	assert find_overlong_lines(options) == []
+
+
+def test_find_overlong_lines_errors():
+	options = FormatTextOptions("code.ts", None, "const = ;\n", Configuration())
+	with pytest.raises(FormatError):
+		find_overlong_lines(options)
@@ -0,0 +1,156 @@
+# stdlib
+import json
+from typing import List, Tuple
+
+# 3rd party
+import pytest
+
+# this package
+from formate_js import Configuration, Formatter
+from formate_js._formate_js import FormatTextOptions, format_text
+
+source = "let  x='é😀'+ foo( 1,2 )\nconst y =   {a:1}"
+formatted = "let x = \"é😀\" + foo(1, 2);\nconst y = { a: 1 };\n"
+
+
+formatter = Formatter(Configuration())
+
+
+def test_offsets():
+	offsets = [
+			0,  # start of the file
+			source.index("oo("),  # inside a token
+			source.index("1,"),  # a token with whitespace inserted before it
+			source.index(" )"),  # whitespace which is removed
+			source.index("=   ") + 1,  # whitespace which is shortened
+			source.index('+'),  # a token just after a replaced quote
+			len(source),  # end of the file
+			]
+	result = formatter.format(source, "code.ts", offsets=offsets, offset_encoding="utf-32")
+
+	assert result.text == formatted
+	assert result.mapped_offsets == [
+			0,
+			formatted.index("oo("),
+			formatted.index("1,"),
+			formatted.index(");"),
+			formatted.index(" { a"),
+			formatted.index('+'),
+			formatted.index(";\n", formatted.index('}')),
+			]
+
+
+def test_offset_encodings():
+	# The emoji is four bytes of UTF-8, two UTF-16 code units and one code point.
+	offset = source.index('+')
+	expected = formatted.index('+')
+
+	def utf8(text: str, index: int) -> int:
+		return len(text[:index].encode("utf-8"))
+
+	def utf16(text: str, index: int) -> int:
+		return len(text[:index].encode("utf-16-le")) // 2
+
+	result = formatter.format(source, "code.ts", offsets=[utf8(source, offset)])
+	assert result.mapped_offsets == [utf8(formatted, expected)]
+
+	result = formatter.format(source, "code.ts", offsets=[utf16(source, offset)], offset_encoding="utf-16")
+	assert result.mapped_offsets == [utf16(formatted, expected)]
+
+
+def test_indentation():
+	text = "if (x) {\n\t\t\tfoo();\n}\n"
+	result = formatter.format(text, "code.ts", offsets=[text.index('\t'), text.index("\n}")], offset_encoding="utf-32")
+
+	assert result.text == "if (x) {\n  foo();\n}\n"
+	assert result.mapped_offsets == [result.text.index("foo"), result.text.index("\n}")]
+
+
+def test_unchanged():
+	result = formatter.format(formatted, "code.ts", offsets=list(range(len(formatted) + 1)), offset_encoding="utf-32")
+	assert result.mapped_offsets == list(range(len(formatted) + 1))
+
+
+def test_not_requested():
+	result = formatter.format(source, "code.ts")
+	assert result.mapped_offsets is None
+	assert result.source_map is None
+
+
+def test_invalid_offsets():
+	with pytest.raises(ValueError, match="Offset 1000 is past the end of the text"):
+		formatter.format(source, "code.ts", offsets=[1000])
+
+	with pytest.raises(ValueError, match="Offset 9 is inside a character"):
+		formatter.format(source, "code.ts", offsets=[9])
+
+	with pytest.raises(ValueError, match="Invalid offset encoding 'utf8'"):
+		formatter.format(source, "code.ts", offsets=[0], offset_encoding="utf8")
+
+
+def test_format_text():
+	options = FormatTextOptions("code.ts", None, source, Configuration())
+	result = format_text(options, offsets=[source.index("foo")], offset_encoding="utf-32", source_map=True)
+	assert result.mapped_offsets == [formatted.index("foo")]
+	assert result.source_map is not None
+
+
+_base64 = "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/"
+
+
+def decode_mappings(mappings: str) -> List[Tuple[int, int, int, int]]:
+	"""
+	Decode source map mappings into ``(generated_line, generated_column, original_line, original_column)`` tuples.
+	"""
+
+	segments = []
+	original_line = original_column = 0
+
+	for generated_line, line in enumerate(mappings.split(';')):
+		generated_column = 0
+		for segment in filter(None, line.split(',')):
+			values = []
+			value = shift = 0
+			for char in segment:
+				digit = _base64.index(char)
+				value += (digit & 0b11111) << shift
+				shift += 5
+				if not digit & 0b100000:
+					values.append(-(value >> 1) if value & 1 else value >> 1)
+					value = shift = 0
+
+			generated_column += values[0]
+			assert values[1] == 0
+			original_line += values[2]
+			original_column += values[3]
+			segments.append((generated_line, generated_column, original_line, original_column))
+
+	return segments
+
+
+def test_source_map():
+	result = formatter.format(source, "src/code.ts", source_map=True)
+	assert result.source_map is not None
+	source_map = json.loads(result.source_map)
+
+	assert source_map["version"] == 3
+	assert source_map["sources"] == ["src/code.ts"]
+
+	def utf16_column(line: str, column: int) -> str:
+		return line.encode("utf-16-le")[column * 2:].decode("utf-16-le")
+
+	original_lines = source.split('\n')
+	formatted_lines = formatted.split('\n')
+	segments = decode_mappings(source_map["mappings"])
+	assert segments
+
+	identifiers = 0
+	for generated_line, generated_column, original_line, original_column in segments:
+		generated = utf16_column(formatted_lines[generated_line], generated_column)
+		original = utf16_column(original_lines[original_line], original_column)
+		if generated[0].isalpha():
+			assert generated[0] == original[0]
+			identifiers += 1
+
+	# let, x, foo, const, y and a
+	assert identifiers == 6
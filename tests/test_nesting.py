@@ -0,0 +1,67 @@
+# stdlib
+import pathlib
+
+# 3rd party
+import pytest
+
+# this package
+from formate_js import Configuration, FormatError, Formatter, javascript_hook
+
+depth = 10_000
+
+deep_sources = {
+		"binary_chain": "x = " + " + ".join(f"a{i}" for i in range(depth)) + ';',
+		"nested_ternaries": "x = " + "a ? b : " * depth + "0;",
+		"nested_parens": "x = " + '(' * depth + '1' + ')' * depth + ';',
+		"nested_arrays": "x = " + '[' * depth + '1' + ']' * depth + ';',
+		"curried_arrows": "x = " + "a => " * depth + "0;",
+		"member_chain": "x = a" + ".b" * depth + ';',
+		"unary_chain": "x = " + '!' * depth + "a;",
+		}
+
+
+@pytest.mark.parametrize("source", deep_sources.values(), ids=deep_sources.keys())
+def test_too_deep(source: str):
+	formatter = Formatter(Configuration())
+
+	with pytest.raises(FormatError, match="'deep.js' is nested more than 2000 levels deep") as exc_info:
+		formatter.format(source, "deep.js")
+
+	assert exc_info.value.path == pathlib.Path("deep.js")
+
+
+def test_too_deep_hook():
+	with pytest.raises(FormatError, match="'deep.js' is nested more than 2000 levels deep"):
+		javascript_hook(deep_sources["nested_ternaries"], "deep.js")
+
+
+def test_max_nesting_depth():
+	source = "x = [[[[1]]]];"
+	assert Formatter(Configuration()).format(source, "code.js").text == "x = [[[[1]]]];\n"
+
+	with pytest.raises(FormatError, match="'code.js' is nested more than 4 levels deep"):
+		Formatter(Configuration(max_nesting_depth=4)).format(source, "code.js")
+
+
+@pytest.mark.parametrize(
+		"source",
+		[
+				pytest.param(f'x = "{"(" * depth}";', id="string"),
+				pytest.param(f"// {'[' * depth}\nx = 1;", id="line_comment"),
+				pytest.param(f"/* {'{' * depth} */\nx = 1;", id="block_comment"),
+				pytest.param(f"x = /{'[(]' * depth}/;", id="regex"),
+				pytest.param(f"x = `{'(' * depth}`;", id="template"),
+				]
+		)
+def test_brackets_outside_code(source: str):
+	# Only brackets in the code itself count towards the nesting depth.
+	result = Formatter(Configuration()).format(source, "flat.js")
+	assert result.text == source + '\n'
+
+
+@pytest.mark.parametrize("name", ["binary_chain", "nested_parens"])
+def test_unlimited_depth(name: str):
+	# Formatting happens on a thread with a large stack, so this does not overflow.
+	formatter = Formatter(Configuration(max_nesting_depth=None))
+	result = formatter.format(deep_sources[name], "deep.js")
+	assert result.text.endswith(";\n")
@@ -0,0 +1,58 @@
+# stdlib
+import json
+
+# 3rd party
+import pytest
+
+# this package
+from formate_js import Configuration
+from formate_js._formate_js import FormatError, FormatTextOptions, debug_print_items
+
+
+def _options(text: str) -> FormatTextOptions:
+	return FormatTextOptions("code.ts", None, text, Configuration())
+
+
+def _has_tracing() -> bool:
+	try:
+		debug_print_items(_options("let a = 1;\n"))
+	except RuntimeError:
+		return False
+	return True
+
+
+requires_tracing = pytest.mark.skipif(not _has_tracing(), reason="built without the 'tracing' feature")
+
+
+@requires_tracing
+def test_debug_print_items_text():
+	text = debug_print_items(_options("call(a, b);\n"))
+	assert text.splitlines()[0] == '0: "call"'
+	assert 'condition "multiLineOrHanging"' in text
+	assert " resolved: " in text
+
+
+@requires_tracing
+def test_debug_print_items_json():
+	dump = json.loads(debug_print_items(_options("call(a, b);\n"), format="json"))
+	assert set(dump) == {"traces", "writerNodes", "printNodes", "conditions"}
+	assert dump["printNodes"][0]["printItem"] == {"kind": "string", "content": "call"}
+	assert {"conditionId", "name", "printNodeId", "value"} == set(dump["conditions"][0])
+
+
+@requires_tracing
+def test_debug_print_items_errors():
+	with pytest.raises(FormatError):
+		debug_print_items(_options("let x = ;\n"))
+
+	with pytest.raises(ValueError, match="format must be 'text' or 'json'"):
+		debug_print_items(_options("let x = 1;\n"), format="yaml")  # type: ignore[arg-type]
+
+
+@pytest.mark.skipif(_has_tracing(), reason="built with the 'tracing' feature")
+def test_debug_print_items_without_tracing():
+	with pytest.raises(RuntimeError, match="built with the 'tracing' feature"):
+		debug_print_items(_options("let x = 1;\n"))
+
+	with pytest.raises(ValueError, match="format must be 'text' or 'json'"):
+		debug_print_items(_options("let x = 1;\n"), format="yaml")  # type: ignore[arg-type]
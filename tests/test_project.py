@@ -0,0 +1,55 @@
+# 3rd party
+import pytest
+from domdf_python_tools.paths import PathPlus
+
+# this package
+from formate_js import Configuration
+from formate_js._formate_js import format_project
+
+
+def _write(root: PathPlus, files: dict) -> None:
+	for name, text in files.items():
+		path = root / name
+		path.parent.maybe_make(parents=True)
+		path.write_text(text)
+
+
+def test_format_project(tmp_pathplus: PathPlus):
+	_write(
+			tmp_pathplus,
+			{
+					".gitignore": "dist/\n",
+					".formate-js-ignore": "legacy.js\n",
+					"src/a.ts": "let  a = 1\n",
+					"src/b.ts": "let b = 2;\n",
+					"src/broken.ts": "let c = ;\n",
+					"legacy.js": "let  d = 4\n",
+					"dist/index.js": "let  e = 5\n",
+					"node_modules/package/index.js": "let  f = 6\n",
+					},
+			)
+
+	summary = format_project(tmp_pathplus, Configuration())
+	assert summary.changed == [tmp_pathplus / "src" / "a.ts"]
+	assert summary.unchanged == [tmp_pathplus / "src" / "b.ts"]
+	assert summary.skipped == []
+	assert [path for path, error in summary.failed] == [tmp_pathplus / "src" / "broken.ts"]
+	assert repr(summary) == "ProjectSummary(changed=1, unchanged=1, skipped=0, failed=1)"
+
+	assert (tmp_pathplus / "src" / "a.ts").read_text() == "let a = 1;\n"
+	assert (tmp_pathplus / "legacy.js").read_text() == "let  d = 4\n"
+	assert (tmp_pathplus / "dist" / "index.js").read_text() == "let  e = 5\n"
+	assert (tmp_pathplus / "node_modules" / "package" / "index.js").read_text() == "let  f = 6\n"
+
+
+def test_format_project_dry_run(tmp_pathplus: PathPlus):
+	_write(tmp_pathplus, {"a.ts": "let  a = 1\n", "node_modules/b.ts": "let  b = 2\n"})
+
+	summary = format_project(tmp_pathplus, Configuration(), skip_node_modules=False, write=False)
+	assert summary.changed == [tmp_pathplus / "a.ts", tmp_pathplus / "node_modules" / "b.ts"]
+	assert (tmp_pathplus / "a.ts").read_text() == "let  a = 1\n"
+
+
+def test_format_project_invalid_glob(tmp_pathplus: PathPlus):
+	with pytest.raises(ValueError, match="Invalid glob pattern"):
+		format_project(tmp_pathplus, Configuration(), exclude=["[a"])
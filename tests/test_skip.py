@@ -0,0 +1,50 @@
+# 3rd party
+import pytest
+
+# this package
+from formate_js import Configuration, Formatter, javascript_hook
+
+minified_source = "let  x=1\n//# sourceMappingURL=vendor.min.js.map\n"
+
+
+def test_max_file_size():
+	formatter = Formatter(Configuration(max_file_size=100))
+
+	result = formatter.format("let  x = 1;" * 10, "code.js")
+	assert result.skipped == "oversized"
+	assert not result.changed
+	assert result.text == "let  x = 1;" * 10
+
+	result = formatter.format("let  x = 1;", "code.js")
+	assert result.skipped is None
+	assert result.changed
+
+
+def test_max_average_line_length():
+	formatter = Formatter(Configuration(max_average_line_length=40))
+
+	source = "let  x = 1;" * 5 + '\n'
+	assert formatter.format(source, "code.js").skipped == "minified"
+	assert formatter.format("let  x = 1;\n" * 5, "code.js").skipped is None
+
+
+@pytest.mark.parametrize(
+		"filename, source, skipped",
+		[
+				pytest.param("vendor.min.js", minified_source, "minified", id="minified"),
+				pytest.param("vendor.MIN.mjs", minified_source, "minified", id="minified_uppercase"),
+				pytest.param("vendor.js", minified_source, None, id="not_min_name"),
+				pytest.param("vendor.min.js", "let  x=1\n", None, id="no_source_map"),
+				],
+		)
+def test_skip_minified_files(filename: str, source: str, skipped: str):
+	formatter = Formatter(Configuration(skip_minified_files=True))
+	assert formatter.format(source, filename).skipped == skipped
+
+
+def test_skip_disabled_by_default():
+	assert Formatter(Configuration()).format(minified_source, "vendor.min.js").skipped is None
+
+
+def test_skip_hook():
+	assert javascript_hook(minified_source, "vendor.min.js", skip_minified_files=True) == minified_source
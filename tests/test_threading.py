@@ -0,0 +1,50 @@
+# stdlib
+import sys
+import sysconfig
+from concurrent.futures import ThreadPoolExecutor
+
+# 3rd party
+import pytest
+
+# this package
+from formate_js import Configuration, Formatter, javascript_hook
+
+threads = 32
+sources = [f"const  value{i} = [{i},{i + 1}].map(x=>x*2)" for i in range(2048)]
+expected = [f"const value{i} = [{i}, {i + 1}].map(x => x * 2);\n" for i in range(2048)]
+
+
+def test_shared_formatter():
+	formatter = Formatter(Configuration())
+
+	with ThreadPoolExecutor(max_workers=threads) as executor:
+		results = list(executor.map(lambda source: formatter.format(source, "code.ts").text, sources))
+
+	assert results == expected
+
+
+def test_hook_from_threads():
+	with ThreadPoolExecutor(max_workers=threads) as executor:
+		results = list(executor.map(lambda source: javascript_hook(source, "code.js"), sources))
+
+	assert results == expected
+
+
+def test_configuration_per_thread():
+	# Configurations cannot be changed from several threads at once, but each thread may change its own.
+
+	def format_with_own_configuration(index: int) -> str:
+		config = Configuration()
+		config.line_width = 80 + index % 40
+		config.indent_width = 2
+		return Formatter(config).format(sources[index], "code.ts").text
+
+	with ThreadPoolExecutor(max_workers=threads) as executor:
+		results = list(executor.map(format_with_own_configuration, range(len(sources))))
+
+	assert results == expected
+
+
+@pytest.mark.skipif(not sysconfig.get_config_var("Py_GIL_DISABLED"), reason="Requires a free-threaded build")
+def test_gil_stays_disabled():
+	assert not sys._is_gil_enabled()  # type: ignore[attr-defined]
@@ -0,0 +1,42 @@
+# stdlib
+import pathlib
+
+# 3rd party
+import pytest
+
+# this package
+from formate_js import Configuration, FormatError, Formatter, FormatTimeout, javascript_hook
+
+large_source = "const a = [1,2,3].map(x => x * 2);\n" * 20_000
+
+
+def test_timeout():
+	formatter = Formatter(Configuration())
+
+	with pytest.raises(FormatTimeout, match="Formatting 'large.ts' did not finish within 0.001s") as exc_info:
+		formatter.format(large_source, "large.ts", timeout=0.001)
+
+	assert exc_info.value.path == pathlib.Path("large.ts")
+	assert isinstance(exc_info.value, FormatError)
+
+	# The formatter can still be used afterwards
+	assert formatter.format("let  x = 1", "code.ts", timeout=10).text == "let x = 1;\n"
+
+
+def test_timeout_hook():
+	with pytest.raises(FormatTimeout):
+		javascript_hook(large_source, "large.js", timeout=0.001)
+
+	assert javascript_hook("let  x = 1", "code.js", timeout=10) == "let x = 1;\n"
+
+
+def test_timeout_negative():
+	with pytest.raises(ValueError, match="timeout must be a non-negative number"):
+		Formatter(Configuration()).format("let x = 1;", "code.ts", timeout=-1)
+
+
+def test_format_error():
+	with pytest.raises(FormatError, match="Expression expected") as exc_info:
+		Formatter(Configuration()).format("let = ;", "bad.ts")
+
+	assert exc_info.value.path == pathlib.Path("bad.ts")
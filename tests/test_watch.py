@@ -0,0 +1,64 @@
+# stdlib
+import threading
+import time
+from typing import List
+
+# 3rd party
+import pytest
+from domdf_python_tools.paths import PathPlus
+
+# this package
+from formate_js import Configuration
+from formate_js._formate_js import WatchEvent, watch
+
+
+def _save_later(path: PathPlus, text: str) -> threading.Thread:
+	def save():
+		time.sleep(0.2)
+		path.write_text(text)
+
+	thread = threading.Thread(target=save)
+	thread.start()
+	return thread
+
+
+def test_watch(tmp_pathplus: PathPlus):
+	(tmp_pathplus / "src").mkdir()
+	events: List[WatchEvent] = []
+
+	def on_event(event: WatchEvent) -> bool:
+		events.append(event)
+		return False
+
+	thread = _save_later(tmp_pathplus / "src" / "code.ts", "let  x = 1\n")
+	watch([tmp_pathplus], Configuration(), on_event=on_event, exclude=["generated"])
+	thread.join()
+
+	assert len(events) == 1
+	assert events[0].kind == "formatted"
+	assert events[0].error is None
+	assert events[0].path == (tmp_pathplus / "src" / "code.ts").resolve()
+	assert (tmp_pathplus / "src" / "code.ts").read_text() == "let x = 1;\n"
+
+
+def test_watch_failure(tmp_pathplus: PathPlus):
+	events: List[WatchEvent] = []
+
+	def on_event(event: WatchEvent) -> bool:
+		events.append(event)
+		return False
+
+	thread = _save_later(tmp_pathplus / "broken.js", "let x = ;\n")
+	watch([tmp_pathplus], Configuration(), on_event=on_event, debounce=0)
+	thread.join()
+
+	assert [event.kind for event in events] == ["failed"]
+	assert events[0].error
+
+
+def test_watch_errors(tmp_pathplus: PathPlus):
+	with pytest.raises(OSError, match="Could not watch"):
+		watch([tmp_pathplus / "missing"], Configuration())
+
+	with pytest.raises(ValueError, match="debounce must be a non-negative number"):
+		watch([tmp_pathplus], Configuration(), debounce=-1)
@@ -0,0 +1,48 @@
+//! Formatting which timed out goes on in the background, up to a limit.
+//!
+//! This is its own test binary, as the number of files formatting in the background is shared by the process.
+
+use formate_js::worker::{
+	FormatTimeoutError, MAX_TIMED_OUT_JOBS, TooManyTimedOutError, format_on_worker,
+};
+use formate_js::{ConfigurationOptions, Formatter};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+#[test]
+fn timed_out_jobs_are_limited() {
+	let formatter = Arc::new(Formatter::new(&ConfigurationOptions::default()).unwrap());
+	let large_source = "const a = [1,2,3].map(x => x * 2);\n".repeat(20_000);
+	let format = |text: &str, timeout| {
+		format_on_worker(
+			formatter.clone(),
+			"large.ts".into(),
+			None,
+			text.to_string(),
+			Some(timeout),
+		)
+	};
+
+	for _ in 0..MAX_TIMED_OUT_JOBS {
+		let error = format(&large_source, Duration::from_nanos(1)).unwrap_err();
+		assert!(error.is::<FormatTimeoutError>(), "{error:#}");
+	}
+	let error = format("let  x = 1", Duration::from_secs(10)).unwrap_err();
+	assert!(error.is::<TooManyTimedOutError>(), "{error:#}");
+
+	// Files are formatted again once those in the background finish.
+	let deadline = Instant::now() + Duration::from_secs(60);
+	loop {
+		match format("let  x = 1", Duration::from_secs(10)) {
+			Ok(result) => {
+				assert_eq!(result.text, "let x = 1;\n");
+				break;
+			}
+			Err(error) if error.is::<TooManyTimedOutError>() && Instant::now() < deadline => {
+				thread::sleep(Duration::from_millis(10));
+			}
+			Err(error) => panic!("{error:#}"),
+		}
+	}
+}
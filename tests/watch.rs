@@ -0,0 +1,134 @@
+//! Watches a temporary directory while files in it are saved.
+
+mod common;
+
+use formate_js::configuration::ConfigurationOptions;
+use formate_js::formatter::Formatter;
+use formate_js::watch::{WatchEvent, WatchEventKind, WatchOptions, Watcher};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+fn project(name: &str) -> PathBuf {
+	let directory = common::temp_dir(name);
+	std::fs::create_dir_all(directory.join("src/generated")).unwrap();
+	directory.canonicalize().unwrap()
+}
+
+fn watcher(directory: &Path, options: WatchOptions) -> Watcher {
+	let formatter = Formatter::new(&ConfigurationOptions::default()).unwrap();
+	Watcher::new(&[directory.to_path_buf()], Arc::new(formatter), &options).unwrap()
+}
+
+/// Polls until `count` events have arrived, or a few seconds have passed.
+fn wait_for(watcher: &mut Watcher, count: usize) -> Vec<WatchEvent> {
+	let deadline = Instant::now() + Duration::from_secs(5);
+	let mut events = Vec::new();
+	while events.len() < count && Instant::now() < deadline {
+		events.extend(watcher.poll(Duration::from_millis(100)).unwrap());
+	}
+	events
+}
+
+#[test]
+fn formats_saved_files() {
+	let directory = project("watch_formats");
+	let mut watcher = watcher(
+		&directory,
+		WatchOptions {
+			excludes: vec!["generated".to_string()],
+			..WatchOptions::default()
+		},
+	);
+
+	std::fs::write(directory.join("src/generated/a.ts"), "let  a = 1\n").unwrap();
+	std::fs::write(directory.join("src/notes.txt"), "let  a = 1\n").unwrap();
+	// Several quick saves only format the file once.
+	for text in ["let  b", "let  b = 2", "let  b = 2\n"] {
+		std::fs::write(directory.join("src/b.ts"), text).unwrap();
+	}
+
+	let events = wait_for(&mut watcher, 1);
+	assert_eq!(
+		events,
+		vec![WatchEvent {
+			path: directory.join("src/b.ts"),
+			kind: WatchEventKind::Formatted,
+			error: None,
+		}]
+	);
+	assert_eq!(
+		std::fs::read_to_string(directory.join("src/b.ts")).unwrap(),
+		"let b = 2;\n"
+	);
+
+	// The watcher's own write, and the excluded and non-JavaScript files, give no events.
+	assert_eq!(
+		watcher.poll(Duration::from_millis(500)).unwrap(),
+		Vec::new()
+	);
+	assert_eq!(
+		std::fs::read_to_string(directory.join("src/generated/a.ts")).unwrap(),
+		"let  a = 1\n"
+	);
+}
+
+#[test]
+fn reports_failures() {
+	let directory = project("watch_failures");
+	let mut watcher = watcher(
+		&directory,
+		WatchOptions {
+			includes: vec!["*.js".to_string()],
+			..WatchOptions::default()
+		},
+	);
+
+	std::fs::write(directory.join("src/ignored.ts"), "let  a = 1\n").unwrap();
+	std::fs::write(directory.join("src/broken.js"), "let a = ;\n").unwrap();
+	let events = wait_for(&mut watcher, 1);
+	assert_eq!(events.len(), 1);
+	assert_eq!(events[0].path, directory.join("src/broken.js"));
+	assert_eq!(events[0].kind, WatchEventKind::Failed);
+	assert!(events[0].error.is_some());
+
+	// The watcher carries on after a failure.
+	std::fs::write(directory.join("src/broken.js"), "let a = 1;\n").unwrap();
+	let events = wait_for(&mut watcher, 1);
+	assert_eq!(events.len(), 1);
+	assert_eq!(events[0].kind, WatchEventKind::Unchanged);
+}
+
+#[test]
+fn keeps_encoding_and_permissions() {
+	let directory = project("watch_encoding");
+	let mut watcher = watcher(&directory, WatchOptions::default());
+
+	let path = directory.join("src/utf16.ts");
+	let utf16 = |text: &str| {
+		"\u{feff}"
+			.encode_utf16()
+			.chain(text.encode_utf16())
+			.flat_map(u16::to_le_bytes)
+			.collect::<Vec<_>>()
+	};
+	std::fs::write(&path, utf16("let  a = '日本'\n")).unwrap();
+	#[cfg(unix)]
+	{
+		use std::os::unix::fs::PermissionsExt;
+		std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o754)).unwrap();
+	}
+
+	let events = wait_for(&mut watcher, 1);
+	assert_eq!(events.len(), 1);
+	assert_eq!(events[0].kind, WatchEventKind::Formatted);
+	assert_eq!(std::fs::read(&path).unwrap(), utf16("let a = \"日本\";\n"));
+	#[cfg(unix)]
+	{
+		use std::os::unix::fs::PermissionsExt;
+		let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+		assert_eq!(mode & 0o777, 0o754);
+	}
+	// No temporary file is left next to it.
+	assert_eq!(std::fs::read_dir(directory.join("src")).unwrap().count(), 2);
+}